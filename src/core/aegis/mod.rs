@@ -11,13 +11,52 @@
 //! - Gestion des politiques de sécurité
 //! - Optimisation des ressources de défense
 //! - Intégration avec tous les autres modules ICARUS
+//!
+//! ## Enrichissement par métadonnées
+//!
+//! Les clés suivantes de `ThreatEvent::metadata` ajustent le plan de réponse :
+//!
+//! - `reputation_score` : réputation de la source (0.0 = malveillante, 1.0 = fiable). En dessous
+//!   de `low_reputation_threshold`, l'adresse est bloquée.
+//! - `geoip_country` : code pays ISO 3166-1 alpha-2 de la source, comparé à `high_risk_countries`.
+//! - `asn` : numéro de système autonome de la source (ex. `AS64496`), comparé à `high_risk_asns`.
+//!
+//! - `kill_chain_stage` : étape de la chaîne d'attaque (`reconnaissance`, `exploitation`,
+//!   `installation`, `command_and_control`, `exfiltration`, `impact`), qui remplace l'étape
+//!   déduite du type de menace.
+//!
+//! Chaque indicateur de risque relève la priorité du plan et impose le blocage de la source ;
+//! une menace élevée ou critique provenant d'une source à risque entraîne en plus l'isolation
+//! de la cible. Les valeurs illisibles sont ignorées.
+//!
+//! ## Chaîne d'attaque
+//!
+//! Chaque menace est rattachée à une étape de la chaîne d'attaque (`classify_stage`) et
+//! AEGIS conserve, par source, les étapes atteintes dans l'ordre (`track_chain`). Lorsqu'une
+//! menace de gravité au moins moyenne fait progresser sa source vers une étape plus avancée
+//! que celles déjà observées, le plan est escaladé :
+//! blocage de la source, isolation de la cible à partir de l'installation, et priorité relevée
+//! pour chaque étape déjà franchie.
+//!
+//! Le poids de corrélation d'une source (nombre de menaces observées) décroît de moitié à
+//! chaque `correlation_half_life_secs` sans activité. Il est recalculé à chaque accès ; une
+//! chaîne dont le poids effectif passe sous `min_correlation_weight` est oubliée, de sorte
+//! qu'une activité ancienne ne provoque plus d'escalade.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime};
 
+use log::Level;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::{system_clock, SharedClock};
+use crate::id::{random_ids, SharedIdGenerator};
+use crate::logging::module_log;
+
 /// Configuration du système AEGIS
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AegisConfig {
     /// Niveau d'autonomie (0.0 - 1.0)
     pub autonomy_level: f32,
@@ -33,6 +72,22 @@ pub struct AegisConfig {
     pub enable_policy_learning: bool,
     /// Niveau de journalisation (0 = aucun, 1 = erreurs, 2 = avertissements, 3 = info, 4 = debug)
     pub log_level: u8,
+    /// Réputation (0.0 - 1.0) en dessous de laquelle une source est jugée à risque
+    pub low_reputation_threshold: f32,
+    /// Codes pays (ISO 3166-1 alpha-2) jugés à risque
+    pub high_risk_countries: Vec<String>,
+    /// Systèmes autonomes jugés à risque
+    pub high_risk_asns: Vec<String>,
+    /// Nombre maximal d'événements de menace conservés dans l'historique
+    pub event_history_size: usize,
+    /// Nombre maximal de sources dont la chaîne d'attaque est suivie
+    pub max_tracked_chains: usize,
+    /// Demi-vie du poids de corrélation d'une source inactive (en secondes)
+    pub correlation_half_life_secs: u64,
+    /// Poids effectif en dessous duquel la chaîne d'une source est oubliée (0 = jamais)
+    pub min_correlation_weight: f32,
+    /// Mode simulation : les plans sont journalisés et marqués terminés sans être exécutés
+    pub dry_run: bool,
 }
 
 impl Default for AegisConfig {
@@ -45,12 +100,20 @@ impl Default for AegisConfig {
             enable_resource_optimization: true,
             enable_policy_learning: true,
             log_level: 3,
+            low_reputation_threshold: 0.2,
+            high_risk_countries: Vec::new(),
+            high_risk_asns: Vec::new(),
+            event_history_size: 1000,
+            max_tracked_chains: 10_000,
+            correlation_half_life_secs: 3600,
+            min_correlation_weight: 0.1,
+            dry_run: false,
         }
     }
 }
 
 /// Types de menaces gérées par AEGIS
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ThreatType {
     /// Attaque par déni de service
     DenialOfService,
@@ -72,8 +135,46 @@ pub enum ThreatType {
     UnknownZeroDay,
 }
 
+// Les deux énumérations ont les mêmes variantes : les conversions sont sans perte et
+// exhaustives, afin que toute divergence future soit signalée à la compilation.
+impl From<crate::neural_net::ThreatType> for ThreatType {
+    fn from(threat_type: crate::neural_net::ThreatType) -> Self {
+        use crate::neural_net::ThreatType as Neural;
+        
+        match threat_type {
+            Neural::DenialOfService => ThreatType::DenialOfService,
+            Neural::PortScan => ThreatType::PortScan,
+            Neural::DataExfiltration => ThreatType::DataExfiltration,
+            Neural::SqlInjection => ThreatType::SqlInjection,
+            Neural::Xss => ThreatType::Xss,
+            Neural::BruteForce => ThreatType::BruteForce,
+            Neural::Malware => ThreatType::Malware,
+            Neural::CommandAndControl => ThreatType::CommandAndControl,
+            Neural::UnknownZeroDay => ThreatType::UnknownZeroDay,
+        }
+    }
+}
+
+impl From<ThreatType> for crate::neural_net::ThreatType {
+    fn from(threat_type: ThreatType) -> Self {
+        use crate::neural_net::ThreatType as Neural;
+        
+        match threat_type {
+            ThreatType::DenialOfService => Neural::DenialOfService,
+            ThreatType::PortScan => Neural::PortScan,
+            ThreatType::DataExfiltration => Neural::DataExfiltration,
+            ThreatType::SqlInjection => Neural::SqlInjection,
+            ThreatType::Xss => Neural::Xss,
+            ThreatType::BruteForce => Neural::BruteForce,
+            ThreatType::Malware => Neural::Malware,
+            ThreatType::CommandAndControl => Neural::CommandAndControl,
+            ThreatType::UnknownZeroDay => Neural::UnknownZeroDay,
+        }
+    }
+}
+
 /// Niveaux de gravité des menaces
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum ThreatSeverity {
     /// Informationnel
     Info = 0,
@@ -87,8 +188,41 @@ pub enum ThreatSeverity {
     Critical = 4,
 }
 
+/// Étapes de la chaîne d'attaque (inspirées des tactiques MITRE ATT&CK), de la plus
+/// précoce à la plus avancée
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum KillChainStage {
+    /// Reconnaissance de la cible (scans)
+    Reconnaissance,
+    /// Exploitation d'une vulnérabilité ou accès initial
+    Exploitation,
+    /// Installation d'une charge malveillante
+    Installation,
+    /// Communication avec un serveur de commande et contrôle
+    CommandAndControl,
+    /// Exfiltration de données
+    Exfiltration,
+    /// Atteinte à la disponibilité (déni de service)
+    Impact,
+}
+
+impl KillChainStage {
+    /// Étape correspondant à un libellé de métadonnée (`reconnaissance`, `c2`, ...)
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "reconnaissance" | "recon" => Some(KillChainStage::Reconnaissance),
+            "exploitation" | "exploit" => Some(KillChainStage::Exploitation),
+            "installation" | "install" => Some(KillChainStage::Installation),
+            "command_and_control" | "c2" => Some(KillChainStage::CommandAndControl),
+            "exfiltration" | "exfil" => Some(KillChainStage::Exfiltration),
+            "impact" => Some(KillChainStage::Impact),
+            _ => None,
+        }
+    }
+}
+
 /// Types d'actions de réponse
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ResponseAction {
     /// Surveillance uniquement
     Monitor,
@@ -108,8 +242,78 @@ pub enum ResponseAction {
     EmergencyShutdown,
 }
 
+impl ResponseAction {
+    /// Indique si l'action interrompt le service (isolation, arrêt d'urgence)
+    pub fn is_disruptive(&self) -> bool {
+        matches!(self, ResponseAction::IsolateSystem | ResponseAction::EmergencyShutdown)
+    }
+}
+
+/// Action inverse émise lors de l'annulation d'un plan exécuté
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InverseAction {
+    /// Retirer la source de la liste de blocage (inverse de `BlockIp`)
+    UnblockIp(String),
+    /// Rouvrir l'accès à la cible (inverse de `BlockPort`)
+    UnblockPort(String),
+    /// Reconnecter la cible au réseau (inverse de `IsolateSystem`)
+    ReconnectSystem(String),
+    /// Cesser de rediriger la source vers un honeypot (inverse de `RedirectToHoneypot`)
+    StopRedirect(String),
+}
+
+impl InverseAction {
+    /// Action inverse d'une action de réponse, si elle est réversible
+    ///
+    /// La surveillance et les alertes n'ont pas d'effet à annuler ; les contre-mesures actives
+    /// et l'arrêt d'urgence ne peuvent pas être annulés automatiquement.
+    fn of(action: &ResponseAction, event: &ThreatEvent) -> Option<Self> {
+        match action {
+            ResponseAction::BlockIp => Some(InverseAction::UnblockIp(event.source.clone())),
+            ResponseAction::BlockPort => Some(InverseAction::UnblockPort(event.target.clone())),
+            ResponseAction::IsolateSystem => Some(InverseAction::ReconnectSystem(event.target.clone())),
+            ResponseAction::RedirectToHoneypot => Some(InverseAction::StopRedirect(event.source.clone())),
+            ResponseAction::Monitor
+            | ResponseAction::Alert
+            | ResponseAction::ActiveCountermeasure
+            | ResponseAction::EmergencyShutdown => None,
+        }
+    }
+}
+
+/// Rappel invoqué pour chaque action inverse émise par `rollback_plan`
+///
+/// Typiquement relié à la liste de blocage du NeuroFireWall.
+pub type RollbackHook = Arc<dyn Fn(&InverseAction) + Send + Sync>;
+
+/// Gestionnaire par défaut des actions exécutées par `execute_response_plan` qui n'ont pas de
+/// gestionnaire dédié (voir `AegisOrchestrator::on_action`)
+pub type ActionHook = Arc<dyn Fn(&ResponseAction, &ThreatEvent) + Send + Sync>;
+
+/// Gestionnaire exécutant concrètement une action de réponse pour un événement de menace
+pub type ActionHandler = Box<dyn Fn(&ThreatEvent) -> Result<(), String> + Send + Sync>;
+
+/// Résultat de l'exécution d'une action d'un plan de réponse
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionOutcome {
+    /// Action exécutée
+    pub action: ResponseAction,
+    /// Erreur renvoyée par le gestionnaire (None en cas de succès)
+    pub error: Option<String>,
+    /// Action seulement journalisée, sans gestionnaire invoqué (mode `dry_run`)
+    #[serde(default)]
+    pub simulated: bool,
+}
+
+impl ActionOutcome {
+    /// Indique si l'action a été exécutée avec succès
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
 /// Événement de menace
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreatEvent {
     /// Identifiant unique de l'événement
     pub id: String,
@@ -123,14 +327,96 @@ pub struct ThreatEvent {
     pub source: String,
     /// Cible de la menace
     pub target: String,
-    /// Horodatage de détection
+    /// Horodatage de détection (par défaut l'instant de réception)
+    #[serde(default = "SystemTime::now")]
     pub timestamp: SystemTime,
     /// Données supplémentaires spécifiques à la menace
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
 }
 
-/// Plan de réponse à une menace
+impl ThreatEvent {
+    /// Encode l'événement au format binaire bincode (plus compact et plus rapide que JSON)
+    pub fn to_bincode(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|e| format!("Encodage bincode de l'événement de menace impossible: {}", e))
+    }
+    
+    /// Décode un événement encodé par `to_bincode`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, String> {
+        bincode::deserialize(bytes).map_err(|e| format!("Décodage bincode de l'événement de menace impossible: {}", e))
+    }
+}
+
+/// Critères de recherche dans l'historique des menaces
+///
+/// Un critère absent (`None`) n'est pas appliqué ; l'intervalle de temps est inclusif.
+#[derive(Debug, Clone, Default)]
+pub struct ThreatFilter {
+    /// Type de menace recherché
+    pub threat_type: Option<ThreatType>,
+    /// Gravité minimale
+    pub min_severity: Option<ThreatSeverity>,
+    /// Source exacte de la menace
+    pub source: Option<String>,
+    /// Détectées à partir de cet instant
+    pub since: Option<SystemTime>,
+    /// Détectées jusqu'à cet instant
+    pub until: Option<SystemTime>,
+}
+
+impl ThreatFilter {
+    /// Indique si un événement satisfait tous les critères du filtre
+    pub fn matches(&self, event: &ThreatEvent) -> bool {
+        self.threat_type.as_ref().is_none_or(|threat_type| *threat_type == event.threat_type)
+            && self.min_severity.is_none_or(|severity| event.severity >= severity)
+            && self.source.as_ref().is_none_or(|source| *source == event.source)
+            && self.since.is_none_or(|since| event.timestamp >= since)
+            && self.until.is_none_or(|until| event.timestamp <= until)
+    }
+}
+
+/// Étape de la chaîne d'attaque d'une menace
+///
+/// La métadonnée `kill_chain_stage` prévaut lorsqu'elle est reconnue ; sinon l'étape est
+/// déduite du type de menace.
+pub fn classify_stage(event: &ThreatEvent) -> KillChainStage {
+    if let Some(stage) = event.metadata.get("kill_chain_stage").and_then(|label| KillChainStage::from_label(label)) {
+        return stage;
+    }
+    
+    match event.threat_type {
+        ThreatType::PortScan => KillChainStage::Reconnaissance,
+        ThreatType::SqlInjection | ThreatType::Xss | ThreatType::BruteForce | ThreatType::UnknownZeroDay => {
+            KillChainStage::Exploitation
+        }
+        ThreatType::Malware => KillChainStage::Installation,
+        ThreatType::CommandAndControl => KillChainStage::CommandAndControl,
+        ThreatType::DataExfiltration => KillChainStage::Exfiltration,
+        ThreatType::DenialOfService => KillChainStage::Impact,
+    }
+}
+
+/// Chaîne d'attaque suivie pour une source
 #[derive(Debug, Clone)]
+struct KillChain {
+    /// Étapes atteintes, dans l'ordre de leur première observation
+    stages: Vec<KillChainStage>,
+    /// Dernière menace observée (éviction des chaînes inactives)
+    last_seen: SystemTime,
+    /// Poids de corrélation à l'instant `last_seen`
+    weight: f32,
+}
+
+impl KillChain {
+    /// Poids de corrélation effectif à l'instant `now`, après décroissance exponentielle
+    fn effective_weight(&self, now: SystemTime, half_life: Duration) -> f32 {
+        let elapsed = now.duration_since(self.last_seen).unwrap_or_default();
+        self.weight * 0.5f32.powf(elapsed.as_secs_f32() / half_life.as_secs_f32())
+    }
+}
+
+/// Plan de réponse à une menace
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResponsePlan {
     /// Identifiant unique du plan
     pub id: String,
@@ -146,10 +432,16 @@ pub struct ResponsePlan {
     pub timeout_seconds: u64,
     /// État d'exécution du plan
     pub status: ResponsePlanStatus,
+    /// Résultats des actions exécutées, dans l'ordre d'exécution
+    #[serde(default)]
+    pub action_outcomes: Vec<ActionOutcome>,
+    /// Plan exécuté en mode `dry_run` : aucune de ses actions n'a réellement été exécutée
+    #[serde(default)]
+    pub simulated: bool,
 }
 
 /// État d'exécution d'un plan de réponse
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ResponsePlanStatus {
     /// Créé mais pas encore exécuté
     Created,
@@ -202,7 +494,7 @@ pub struct PolicyRule {
 }
 
 /// Statistiques d'AEGIS
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AegisStats {
     /// Nombre total de menaces détectées
     pub total_threats_detected: u64,
@@ -225,7 +517,7 @@ pub struct AegisStats {
 }
 
 /// État du système AEGIS
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AegisState {
     /// Initialisation en cours
     Initializing,
@@ -246,6 +538,20 @@ pub struct AegisOrchestrator {
     config: AegisConfig,
     state: Arc<Mutex<AegisState>>,
     stats: Arc<Mutex<AegisStats>>,
+    /// Derniers événements de menace traités (au plus `event_history_size`), du plus ancien au plus récent
+    history: Arc<Mutex<VecDeque<ThreatEvent>>>,
+    /// Rappel d'annulation des actions exécutées
+    rollback_hook: Option<RollbackHook>,
+    /// Gestionnaire par défaut des actions de réponse sans gestionnaire dédié
+    action_hook: Option<ActionHook>,
+    /// Gestionnaires dédiés des actions de réponse (prioritaires sur `action_hook`)
+    handlers: HashMap<ResponseAction, ActionHandler>,
+    /// Chaînes d'attaque par source (au plus `max_tracked_chains`)
+    chains: Arc<Mutex<HashMap<String, KillChain>>>,
+    /// Source de temps (horodatage des plans de réponse)
+    clock: SharedClock,
+    /// Générateur des identifiants de plans de réponse
+    ids: SharedIdGenerator,
     // Les champs suivants seront implémentés dans les versions futures
     // policy_manager: PolicyManager,
     // response_coordinator: ResponseCoordinator,
@@ -255,6 +561,11 @@ pub struct AegisOrchestrator {
 }
 
 impl AegisOrchestrator {
+    /// Augmentation de priorité par indicateur de risque issu des métadonnées
+    const RISK_PRIORITY_BOOST: u8 = 20;
+    /// Augmentation de priorité par étape de la chaîne d'attaque déjà franchie par la source
+    const CHAIN_PRIORITY_BOOST: u8 = 10;
+    
     /// Crée une nouvelle instance d'AEGIS
     pub fn new(config: AegisConfig) -> Self {
         let stats = AegisStats {
@@ -273,10 +584,37 @@ impl AegisOrchestrator {
             config,
             state: Arc::new(Mutex::new(AegisState::Initializing)),
             stats: Arc::new(Mutex::new(stats)),
+            history: Arc::new(Mutex::new(VecDeque::new())),
+            rollback_hook: None,
+            action_hook: None,
+            handlers: HashMap::new(),
+            chains: Arc::new(Mutex::new(HashMap::new())),
+            clock: system_clock(),
+            ids: random_ids(),
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
     
+    /// Remplace la source de temps (horloge simulée dans les tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+    
+    /// Remplace le générateur d'identifiants (identifiants séquentiels dans les tests)
+    pub fn set_id_generator(&mut self, ids: SharedIdGenerator) {
+        self.ids = ids;
+    }
+    
+    /// Source de temps de l'orchestrateur (reprise par l'étape AEGIS du pipeline)
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+    
+    /// Générateur d'identifiants de l'orchestrateur (repris par l'étape AEGIS du pipeline)
+    pub fn id_generator(&self) -> SharedIdGenerator {
+        self.ids.clone()
+    }
+    
     /// Initialise le système AEGIS
     pub fn initialize(&mut self) -> Result<(), String> {
         // Cette fonction sera implémentée dans les versions futures
@@ -285,6 +623,8 @@ impl AegisOrchestrator {
         let mut state = self.state.lock().unwrap();
         *state = AegisState::Operational;
         
+        module_log!(self.config.log_level, Level::Info, "AEGIS opérationnel");
+        
         Ok(())
     }
     
@@ -322,8 +662,8 @@ impl AegisOrchestrator {
         };
         
         // Créer le plan de réponse
-        let plan = ResponsePlan {
-            id: format!("plan-{}", uuid::Uuid::new_v4()),
+        let mut plan = ResponsePlan {
+            id: self.ids.next_id("plan"),
             threat_event: event.clone(),
             actions,
             priority: match event.severity {
@@ -333,11 +673,26 @@ impl AegisOrchestrator {
                 ThreatSeverity::High => 70,
                 ThreatSeverity::Critical => 90,
             },
-            created_at: SystemTime::now(),
+            created_at: self.clock.now(),
             timeout_seconds: 300,
             status: ResponsePlanStatus::Created,
+            action_outcomes: Vec::new(),
+            simulated: false,
         };
         
+        // Ajuster le plan selon les métadonnées de la menace et la progression de la source
+        self.enrich_plan(&mut plan);
+        self.escalate_for_chain(&mut plan);
+        
+        // Conserver l'événement dans l'historique borné
+        {
+            let mut history = self.history.lock().unwrap();
+            history.push_back(event);
+            while history.len() > self.config.event_history_size {
+                history.pop_front();
+            }
+        }
+        
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
         stats.total_threats_detected += 1;
@@ -347,14 +702,248 @@ impl AegisOrchestrator {
         let response_time_ms = start_time.elapsed().as_millis() as f64;
         stats.avg_response_time_ms = (stats.avg_response_time_ms * (stats.response_plans_generated - 1) as f64 + response_time_ms) / stats.response_plans_generated as f64;
         
+        module_log!(
+            self.config.log_level,
+            Level::Info,
+            "Plan {} généré pour {:?} depuis {}: {:?}",
+            plan.id,
+            plan.threat_event.threat_type,
+            plan.threat_event.source,
+            plan.actions
+        );
+        
         Ok(plan)
     }
     
+    /// Traite un lot de menaces simultanées et renvoie les plans par priorité décroissante
+    ///
+    /// Les événements de même type, même source et même cible sont corrélés : seul le plus
+    /// grave (puis le plus fiable) est traité, avec le nombre d'événements regroupés dans la
+    /// métadonnée `correlated_events`.
+    pub fn triage(&self, events: Vec<ThreatEvent>) -> Result<Vec<ResponsePlan>, String> {
+        let mut correlated: Vec<(ThreatEvent, usize)> = Vec::new();
+        for event in events {
+            let duplicate = correlated.iter_mut().find(|(kept, _)| {
+                kept.threat_type == event.threat_type && kept.source == event.source && kept.target == event.target
+            });
+            match duplicate {
+                Some((kept, count)) => {
+                    *count += 1;
+                    if (event.severity, event.confidence) > (kept.severity, kept.confidence) {
+                        *kept = event;
+                    }
+                }
+                None => correlated.push((event, 1)),
+            }
+        }
+        
+        let mut plans = correlated
+            .into_iter()
+            .map(|(mut event, count)| {
+                if count > 1 {
+                    event.metadata.insert("correlated_events".to_string(), count.to_string());
+                }
+                self.process_threat_event(event)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        plans.sort_by_key(|plan| std::cmp::Reverse(plan.priority));
+        
+        Ok(plans)
+    }
+    
+    /// Recherche dans l'historique les menaces correspondant au filtre, de la plus ancienne à la plus récente
+    pub fn query_threats(&self, filter: ThreatFilter) -> Vec<ThreatEvent> {
+        let history = self.history.lock().unwrap();
+        history.iter().filter(|event| filter.matches(event)).cloned().collect()
+    }
+    
+    /// Compte les indicateurs de risque présents dans les métadonnées d'une menace
+    ///
+    /// Voir la documentation du module pour les clés reconnues.
+    fn risk_indicators(&self, metadata: &HashMap<String, String>) -> Vec<String> {
+        let mut indicators = Vec::new();
+        
+        if let Some(reputation) = metadata.get("reputation_score").and_then(|value| value.trim().parse::<f32>().ok()) {
+            if reputation < self.config.low_reputation_threshold {
+                indicators.push(format!("reputation_score={}", reputation));
+            }
+        }
+        
+        if let Some(country) = metadata.get("geoip_country") {
+            let country = country.trim();
+            if self.config.high_risk_countries.iter().any(|risky| risky.eq_ignore_ascii_case(country)) {
+                indicators.push(format!("geoip_country={}", country));
+            }
+        }
+        
+        if let Some(asn) = metadata.get("asn") {
+            let asn = asn.trim();
+            if self.config.high_risk_asns.iter().any(|risky| risky.eq_ignore_ascii_case(asn)) {
+                indicators.push(format!("asn={}", asn));
+            }
+        }
+        
+        indicators
+    }
+    
+    /// Escalade un plan de réponse lorsque la source présente des indicateurs de risque
+    fn enrich_plan(&self, plan: &mut ResponsePlan) {
+        let indicators = self.risk_indicators(&plan.threat_event.metadata);
+        if indicators.is_empty() {
+            return;
+        }
+        
+        let mut escalation = vec![ResponseAction::Alert, ResponseAction::BlockIp];
+        if plan.threat_event.severity >= ThreatSeverity::High {
+            escalation.push(ResponseAction::IsolateSystem);
+        }
+        for action in escalation {
+            if !plan.actions.contains(&action) {
+                plan.actions.push(action);
+            }
+        }
+        
+        let boost = Self::RISK_PRIORITY_BOOST.saturating_mul(indicators.len() as u8);
+        plan.priority = plan.priority.saturating_add(boost).min(100);
+        
+        module_log!(
+            self.config.log_level,
+            Level::Debug,
+            "Plan {} escaladé (indicateurs de risque: {})",
+            plan.id,
+            indicators.join(", ")
+        );
+    }
+    
+    /// Enregistre l'étape de la menace dans la chaîne de sa source et escalade le plan
+    /// si la source progresse vers une étape plus avancée (gravité au moins moyenne)
+    fn escalate_for_chain(&self, plan: &mut ResponsePlan) {
+        let stage = classify_stage(&plan.threat_event);
+        let now = self.clock.now();
+        
+        let mut chains = self.chains.lock().unwrap();
+        let weight = self.live_chain(&mut chains, &plan.threat_event.source, now).map_or(0.0, |(_, weight)| weight);
+        if !chains.contains_key(&plan.threat_event.source) && chains.len() >= self.config.max_tracked_chains {
+            // Éviction de la chaîne restée inactive le plus longtemps
+            if let Some(stale) = chains.iter().min_by_key(|(_, chain)| chain.last_seen).map(|(source, _)| source.clone()) {
+                chains.remove(&stale);
+            }
+        }
+        let chain = chains
+            .entry(plan.threat_event.source.clone())
+            .or_insert_with(|| KillChain { stages: Vec::new(), last_seen: now, weight: 0.0 });
+        chain.last_seen = now;
+        chain.weight = weight + 1.0;
+        
+        let earlier_stages = chain.stages.iter().filter(|reached| **reached < stage).count();
+        let new_stage = !chain.stages.contains(&stage);
+        if new_stage {
+            chain.stages.push(stage);
+        }
+        drop(chains);
+        
+        // Les menaces mineures sont suivies sans escalade
+        if !new_stage || earlier_stages == 0 || plan.threat_event.severity < ThreatSeverity::Medium {
+            return;
+        }
+        
+        let mut escalation = vec![ResponseAction::Alert, ResponseAction::BlockIp];
+        if stage >= KillChainStage::Installation {
+            escalation.push(ResponseAction::IsolateSystem);
+        }
+        for action in escalation {
+            if !plan.actions.contains(&action) {
+                plan.actions.push(action);
+            }
+        }
+        
+        let boost = Self::CHAIN_PRIORITY_BOOST.saturating_mul(earlier_stages as u8);
+        plan.priority = plan.priority.saturating_add(boost).min(100);
+        
+        module_log!(
+            self.config.log_level,
+            Level::Info,
+            "Source {} en progression vers {:?} ({} étape(s) antérieure(s)), plan {} escaladé",
+            plan.threat_event.source,
+            stage,
+            earlier_stages,
+            plan.id
+        );
+    }
+    
+    /// Demi-vie du poids de corrélation
+    fn correlation_half_life(&self) -> Duration {
+        Duration::from_secs(self.config.correlation_half_life_secs)
+    }
+    
+    /// Chaîne d'une source avec son poids effectif ; la chaîne est oubliée si ce poids est
+    /// passé sous le seuil
+    fn live_chain<'a>(
+        &self,
+        chains: &'a mut HashMap<String, KillChain>,
+        source: &str,
+        now: SystemTime,
+    ) -> Option<(&'a mut KillChain, f32)> {
+        let weight = chains.get(source)?.effective_weight(now, self.correlation_half_life());
+        if weight < self.config.min_correlation_weight {
+            chains.remove(source);
+            module_log!(self.config.log_level, Level::Debug, "Chaîne d'attaque de {} oubliée (inactive)", source);
+            return None;
+        }
+        
+        chains.get_mut(source).map(|chain| (chain, weight))
+    }
+    
+    /// Étapes de la chaîne d'attaque atteintes par une source, dans l'ordre de leur
+    /// première observation (vide si la chaîne a été oubliée)
+    pub fn track_chain(&self, source: &str) -> Vec<KillChainStage> {
+        let mut chains = self.chains.lock().unwrap();
+        self.live_chain(&mut chains, source, self.clock.now())
+            .map(|(chain, _)| chain.stages.clone())
+            .unwrap_or_default()
+    }
+    
+    /// Poids de corrélation effectif d'une source : nombre de menaces observées, diminué de
+    /// moitié à chaque demi-vie d'inactivité (0 si la chaîne a été oubliée)
+    pub fn correlation_weight(&self, source: &str) -> f32 {
+        let mut chains = self.chains.lock().unwrap();
+        self.live_chain(&mut chains, source, self.clock.now()).map_or(0.0, |(_, weight)| weight)
+    }
+    
+    /// Enregistre le gestionnaire par défaut, invoqué pour chaque action d'un plan exécuté qui
+    /// n'a pas de gestionnaire dédié
+    ///
+    /// Une action n'est confiée qu'à un seul gestionnaire : celui enregistré pour elle avec
+    /// `register_handler` passe en premier et remplace alors ce rappel.
+    pub fn on_action<F>(&mut self, hook: F)
+    where
+        F: Fn(&ResponseAction, &ThreatEvent) + Send + Sync + 'static,
+    {
+        self.action_hook = Some(Arc::new(hook));
+    }
+    
+    /// Enregistre le gestionnaire dédié exécutant une action de réponse (remplace le précédent)
+    ///
+    /// Prioritaire sur le gestionnaire par défaut installé par `on_action`.
+    pub fn register_handler<F>(&mut self, action: ResponseAction, handler: F)
+    where
+        F: Fn(&ThreatEvent) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.handlers.insert(action, Box::new(handler));
+    }
+    
     /// Exécute un plan de réponse
+    ///
+    /// Chaque action est confiée à son gestionnaire dédié (`register_handler`), à défaut au
+    /// gestionnaire par défaut (`on_action`) ; une action sans aucun gestionnaire est seulement
+    /// journalisée. Toutes les actions sont tentées et
+    /// leurs résultats consignés dans `action_outcomes` ; le plan échoue si l'une d'elles échoue.
+    ///
+    /// En mode `dry_run`, les actions sont seulement journalisées : aucun gestionnaire ni rappel
+    /// n'est invoqué, le plan et ses résultats sont marqués `simulated`, le plan est marqué
+    /// terminé et n'est pas compté dans les statistiques d'exécution.
     pub fn execute_response_plan(&self, plan: &mut ResponsePlan) -> Result<(), String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle simule l'exécution du plan
-        
         // Vérifier l'état du système
         let state = self.state.lock().unwrap();
         if *state != AegisState::Operational {
@@ -362,18 +951,125 @@ impl AegisOrchestrator {
         }
         drop(state);
         
-        // Simuler l'exécution du plan
+        plan.action_outcomes.clear();
+        plan.simulated = self.config.dry_run;
+        
+        if self.config.dry_run {
+            for action in &plan.actions {
+                if action.is_disruptive() {
+                    module_log!(self.config.log_level, Level::Warn, "[simulation] Plan {}: action perturbatrice {:?} simulée", plan.id, action);
+                } else {
+                    module_log!(self.config.log_level, Level::Info, "[simulation] Plan {}: action {:?} non exécutée", plan.id, action);
+                }
+                plan.action_outcomes.push(ActionOutcome {
+                    action: action.clone(),
+                    error: None,
+                    simulated: true,
+                });
+            }
+            plan.status = ResponsePlanStatus::Completed;
+            return Ok(());
+        }
+        
         plan.status = ResponsePlanStatus::InProgress;
         
-        // Simuler un délai d'exécution
-        std::thread::sleep(Duration::from_millis(100));
+        for action in &plan.actions {
+            let result = match (self.handlers.get(action), &self.action_hook) {
+                (Some(handler), _) => handler(&plan.threat_event),
+                (None, Some(hook)) => {
+                    hook(action, &plan.threat_event);
+                    Ok(())
+                }
+                (None, None) => {
+                    module_log!(self.config.log_level, Level::Info, "Plan {}: aucun gestionnaire pour l'action {:?}", plan.id, action);
+                    Ok(())
+                }
+            };
+            if let Err(e) = &result {
+                module_log!(self.config.log_level, Level::Error, "Plan {}: échec de l'action {:?}: {}", plan.id, action, e);
+            }
+            
+            plan.action_outcomes.push(ActionOutcome {
+                action: action.clone(),
+                error: result.err(),
+                simulated: false,
+            });
+        }
         
-        // Simuler une exécution réussie
-        plan.status = ResponsePlanStatus::Completed;
+        let failures: Vec<String> = plan
+            .action_outcomes
+            .iter()
+            .filter_map(|outcome| outcome.error.as_ref().map(|e| format!("{:?}: {}", outcome.action, e)))
+            .collect();
         
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
-        stats.response_plans_completed += 1;
+        if failures.is_empty() {
+            plan.status = ResponsePlanStatus::Completed;
+            stats.response_plans_completed += 1;
+            Ok(())
+        } else {
+            let reason = failures.join("; ");
+            plan.status = ResponsePlanStatus::Failed(reason.clone());
+            stats.response_plans_failed += 1;
+            Err(format!("Échec du plan {}: {}", plan.id, reason))
+        }
+    }
+    
+    /// Annule un plan qui n'a pas encore été exécuté jusqu'au bout
+    ///
+    /// Un plan terminé doit être annulé avec `rollback_plan`.
+    pub fn cancel_plan(&self, plan: &mut ResponsePlan) -> Result<(), String> {
+        if plan.status == ResponsePlanStatus::Completed {
+            return Err(format!("Le plan {} est déjà exécuté, utilisez rollback_plan", plan.id));
+        }
+        
+        plan.status = ResponsePlanStatus::Cancelled;
+        module_log!(self.config.log_level, Level::Info, "Plan {} annulé", plan.id);
+        
+        Ok(())
+    }
+    
+    /// Enregistre le rappel invoqué pour chaque action inverse d'un plan annulé
+    pub fn on_rollback<F>(&mut self, hook: F)
+    where
+        F: Fn(&InverseAction) + Send + Sync + 'static,
+    {
+        self.rollback_hook = Some(Arc::new(hook));
+    }
+    
+    /// Annule les effets d'un plan exécuté (faux positif par exemple)
+    ///
+    /// Les actions inverses sont émises dans l'ordre inverse de l'exécution via le rappel
+    /// enregistré avec `on_rollback`. Un plan simulé (`dry_run`) n'a rien modifié et ne peut
+    /// pas être annulé : ses actions inverses lèveraient des mesures prises par d'autres plans.
+    pub fn rollback_plan(&self, plan: &ResponsePlan) -> Result<(), String> {
+        if plan.status != ResponsePlanStatus::Completed {
+            return Err(format!("Le plan {} n'a pas été exécuté, état actuel: {:?}", plan.id, plan.status));
+        }
+        if plan.simulated {
+            return Err(format!("Le plan {} a été simulé, aucune action à annuler", plan.id));
+        }
+        
+        let inverse_actions: Vec<InverseAction> = plan
+            .actions
+            .iter()
+            .rev()
+            .filter_map(|action| InverseAction::of(action, &plan.threat_event))
+            .collect();
+        if inverse_actions.is_empty() {
+            return Ok(());
+        }
+        
+        let hook = self
+            .rollback_hook
+            .as_ref()
+            .ok_or_else(|| format!("Aucun rappel d'annulation enregistré pour le plan {}", plan.id))?;
+        for inverse_action in &inverse_actions {
+            hook(inverse_action);
+        }
+        
+        module_log!(self.config.log_level, Level::Info, "Plan {} annulé: {:?}", plan.id, inverse_actions);
         
         Ok(())
     }
@@ -383,11 +1079,28 @@ impl AegisOrchestrator {
         self.state.lock().unwrap().clone()
     }
     
+    /// Obtient la configuration du système
+    pub fn get_config(&self) -> &AegisConfig {
+        &self.config
+    }
+    
     /// Obtient les statistiques actuelles
     pub fn get_stats(&self) -> AegisStats {
         self.stats.lock().unwrap().clone()
     }
     
+    /// Remet à zéro les compteurs statistiques sans modifier l'état opérationnel
+    ///
+    /// Peut être appelé sans risque entre deux phases d'un benchmark (échauffement, mesure).
+    pub fn reset_stats(&self) {
+        let mut stats = self.stats.lock().unwrap();
+        *stats = AegisStats {
+            active_policies: stats.active_policies,
+            resource_utilization: stats.resource_utilization,
+            ..Default::default()
+        };
+    }
+    
     /// Arrête le système AEGIS
     pub fn shutdown(&self) -> Result<(), String> {
         // Cette fonction sera implémentée dans les versions futures
@@ -396,6 +1109,8 @@ impl AegisOrchestrator {
         let mut state = self.state.lock().unwrap();
         *state = AegisState::Shutdown;
         
+        module_log!(self.config.log_level, Level::Info, "AEGIS arrêté");
+        
         Ok(())
     }
 }
@@ -404,6 +1119,8 @@ impl AegisOrchestrator {
 mod tests {
     use super::*;
     
+    use crate::clock::{Clock, MockClock};
+    
     #[test]
     fn test_aegis_initialization() {
         let config = AegisConfig::default();
@@ -466,4 +1183,488 @@ mod tests {
         
         assert_eq!(plan.status, ResponsePlanStatus::Completed);
     }
+    
+    #[test]
+    fn test_threat_type_conversion_from_neural_net() {
+        use crate::neural_net::ThreatType as Neural;
+        
+        let expected = [
+            (Neural::DenialOfService, ThreatType::DenialOfService),
+            (Neural::PortScan, ThreatType::PortScan),
+            (Neural::DataExfiltration, ThreatType::DataExfiltration),
+            (Neural::SqlInjection, ThreatType::SqlInjection),
+            (Neural::Xss, ThreatType::Xss),
+            (Neural::BruteForce, ThreatType::BruteForce),
+            (Neural::Malware, ThreatType::Malware),
+            (Neural::CommandAndControl, ThreatType::CommandAndControl),
+            (Neural::UnknownZeroDay, ThreatType::UnknownZeroDay),
+        ];
+        
+        for (neural, aegis) in expected {
+            assert_eq!(ThreatType::from(neural.clone()), aegis);
+            assert_eq!(Neural::from(aegis), neural);
+        }
+    }
+    
+    #[test]
+    fn test_reset_stats_keeps_state() {
+        let config = AegisConfig::default();
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.initialize().unwrap();
+        
+        let event = ThreatEvent {
+            id: String::from("threat-reset"),
+            threat_type: ThreatType::BruteForce,
+            severity: ThreatSeverity::High,
+            confidence: 0.9,
+            source: String::from("192.168.1.100"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        aegis.process_threat_event(event).unwrap();
+        assert_eq!(aegis.get_stats().response_plans_generated, 1);
+        
+        aegis.reset_stats();
+        
+        let stats = aegis.get_stats();
+        assert_eq!(stats.total_threats_detected, 0);
+        assert_eq!(stats.response_plans_generated, 0);
+        assert_eq!(stats.avg_response_time_ms, 0.0);
+        assert_eq!(aegis.get_state(), AegisState::Operational);
+    }
+    
+    #[test]
+    fn test_metadata_enrichment_escalates_plan() {
+        let config = AegisConfig {
+            high_risk_asns: vec!["AS64496".to_string()],
+            ..Default::default()
+        };
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.initialize().unwrap();
+        
+        let event = |metadata: &[(&str, &str)]| ThreatEvent {
+            id: String::from("threat-enrichment"),
+            threat_type: ThreatType::SqlInjection,
+            severity: ThreatSeverity::Medium,
+            confidence: 0.7,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.10"),
+            timestamp: SystemTime::now(),
+            metadata: metadata.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        };
+        
+        let baseline = aegis.process_threat_event(event(&[("reputation_score", "0.9")])).unwrap();
+        assert!(!baseline.actions.contains(&ResponseAction::BlockIp));
+        assert_eq!(baseline.priority, 50);
+        
+        let escalated = aegis.process_threat_event(event(&[("reputation_score", "0.05")])).unwrap();
+        assert!(escalated.actions.contains(&ResponseAction::BlockIp));
+        assert!(!escalated.actions.contains(&ResponseAction::IsolateSystem));
+        assert_eq!(escalated.priority, 70);
+        
+        // Deux indicateurs cumulés ; une valeur illisible est ignorée
+        let both = aegis
+            .process_threat_event(event(&[("reputation_score", "0.05"), ("asn", "as64496"), ("geoip_country", "??")]))
+            .unwrap();
+        assert_eq!(both.priority, 90);
+        
+        let unreadable = aegis.process_threat_event(event(&[("reputation_score", "low")])).unwrap();
+        assert!(!unreadable.actions.contains(&ResponseAction::BlockIp));
+    }
+    
+    #[test]
+    fn test_query_threat_history() {
+        let config = AegisConfig { event_history_size: 4, ..Default::default() };
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.initialize().unwrap();
+        
+        let start = SystemTime::now();
+        let severities = [
+            ThreatSeverity::Critical,
+            ThreatSeverity::Low,
+            ThreatSeverity::High,
+            ThreatSeverity::Medium,
+            ThreatSeverity::Critical,
+        ];
+        for (i, severity) in severities.into_iter().enumerate() {
+            let event = ThreatEvent {
+                id: format!("threat-{}", i),
+                threat_type: if i % 2 == 0 { ThreatType::BruteForce } else { ThreatType::PortScan },
+                severity,
+                confidence: 0.8,
+                source: format!("192.168.1.{}", i % 2),
+                target: String::from("192.168.1.1"),
+                timestamp: start + Duration::from_secs(i as u64),
+                metadata: HashMap::new(),
+            };
+            aegis.process_threat_event(event).unwrap();
+        }
+        
+        // Le premier événement a été évincé de l'historique
+        let ids = |events: Vec<ThreatEvent>| events.into_iter().map(|event| event.id).collect::<Vec<_>>();
+        assert_eq!(ids(aegis.query_threats(ThreatFilter::default())), ["threat-1", "threat-2", "threat-3", "threat-4"]);
+        
+        let severe = aegis.query_threats(ThreatFilter { min_severity: Some(ThreatSeverity::High), ..Default::default() });
+        assert_eq!(ids(severe), ["threat-2", "threat-4"]);
+        
+        let filtered = aegis.query_threats(ThreatFilter {
+            threat_type: Some(ThreatType::PortScan),
+            source: Some(String::from("192.168.1.1")),
+            since: Some(start + Duration::from_secs(2)),
+            until: Some(start + Duration::from_secs(3)),
+            ..Default::default()
+        });
+        assert_eq!(ids(filtered), ["threat-3"]);
+    }
+    
+    #[test]
+    fn test_cancel_and_rollback_plan() {
+        let config = AegisConfig::default();
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.initialize().unwrap();
+        
+        let emitted = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&emitted);
+        aegis.on_rollback(move |action| sink.lock().unwrap().push(action.clone()));
+        
+        let event = ThreatEvent {
+            id: String::from("threat-rollback"),
+            threat_type: ThreatType::BruteForce,
+            severity: ThreatSeverity::High,
+            confidence: 0.9,
+            source: String::from("192.168.1.100"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        // Un plan non exécuté ne peut pas être annulé par rollback, mais peut l'être par cancel
+        let mut pending = aegis.process_threat_event(event.clone()).unwrap();
+        assert!(aegis.rollback_plan(&pending).is_err());
+        aegis.cancel_plan(&mut pending).unwrap();
+        assert_eq!(pending.status, ResponsePlanStatus::Cancelled);
+        
+        let mut plan = aegis.process_threat_event(event).unwrap();
+        assert!(plan.actions.contains(&ResponseAction::BlockIp));
+        aegis.execute_response_plan(&mut plan).unwrap();
+        assert!(aegis.cancel_plan(&mut plan).is_err());
+        
+        aegis.rollback_plan(&plan).unwrap();
+        assert_eq!(*emitted.lock().unwrap(), vec![InverseAction::UnblockIp(String::from("192.168.1.100"))]);
+    }
+    
+    #[test]
+    fn test_triage_orders_and_deduplicates() {
+        let config = AegisConfig::default();
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.initialize().unwrap();
+        
+        let event = |threat_type: ThreatType, severity: ThreatSeverity, source: &str| ThreatEvent {
+            id: format!("threat-{}", uuid::Uuid::new_v4()),
+            threat_type,
+            severity,
+            confidence: 0.8,
+            source: source.to_string(),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let plans = aegis
+            .triage(vec![
+                event(ThreatType::PortScan, ThreatSeverity::Info, "10.0.0.1"),
+                event(ThreatType::DenialOfService, ThreatSeverity::Critical, "10.0.0.2"),
+                event(ThreatType::PortScan, ThreatSeverity::Medium, "10.0.0.1"),
+                event(ThreatType::BruteForce, ThreatSeverity::Low, "10.0.0.1"),
+                event(ThreatType::DenialOfService, ThreatSeverity::Critical, "10.0.0.2"),
+            ])
+            .unwrap();
+        
+        let summary: Vec<(ThreatType, ThreatSeverity, u8)> = plans
+            .iter()
+            .map(|plan| (plan.threat_event.threat_type.clone(), plan.threat_event.severity, plan.priority))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ThreatType::DenialOfService, ThreatSeverity::Critical, 90),
+                (ThreatType::PortScan, ThreatSeverity::Medium, 50),
+                (ThreatType::BruteForce, ThreatSeverity::Low, 30),
+            ]
+        );
+        assert_eq!(plans[0].threat_event.metadata.get("correlated_events").map(String::as_str), Some("2"));
+        assert!(!plans[2].threat_event.metadata.contains_key("correlated_events"));
+        assert_eq!(aegis.get_stats().response_plans_generated, 3);
+    }
+    
+    #[test]
+    fn test_kill_chain_progression_escalates() {
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        
+        let event = |threat_type: ThreatType, source: &str| ThreatEvent {
+            id: format!("threat-{}", uuid::Uuid::new_v4()),
+            threat_type,
+            severity: ThreatSeverity::Medium,
+            confidence: 0.8,
+            source: source.to_string(),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let recon = aegis.process_threat_event(event(ThreatType::PortScan, "10.0.0.7")).unwrap();
+        let exploit = aegis.process_threat_event(event(ThreatType::SqlInjection, "10.0.0.7")).unwrap();
+        let exfil = aegis.process_threat_event(event(ThreatType::DataExfiltration, "10.0.0.7")).unwrap();
+        
+        assert_eq!(
+            aegis.track_chain("10.0.0.7"),
+            vec![KillChainStage::Reconnaissance, KillChainStage::Exploitation, KillChainStage::Exfiltration]
+        );
+        assert!(aegis.track_chain("10.0.0.8").is_empty());
+        
+        // Chaque étape plus avancée escalade davantage la réponse
+        assert_eq!(recon.priority, 50);
+        assert_eq!(exploit.priority, 60);
+        assert!(exploit.actions.contains(&ResponseAction::BlockIp));
+        assert!(!exploit.actions.contains(&ResponseAction::IsolateSystem));
+        assert_eq!(exfil.priority, 70);
+        assert!(exfil.actions.contains(&ResponseAction::IsolateSystem));
+        
+        // La même exfiltration depuis une source inconnue n'est pas escaladée
+        let isolated = aegis.process_threat_event(event(ThreatType::DataExfiltration, "10.0.0.8")).unwrap();
+        assert_eq!(isolated.priority, 50);
+        assert!(!isolated.actions.contains(&ResponseAction::IsolateSystem));
+        
+        // La métadonnée prévaut sur le type de menace
+        let mut labelled = event(ThreatType::UnknownZeroDay, "10.0.0.9");
+        labelled.metadata.insert("kill_chain_stage".to_string(), "C2".to_string());
+        assert_eq!(classify_stage(&labelled), KillChainStage::CommandAndControl);
+    }
+    
+    #[test]
+    fn test_stale_correlations_decay() {
+        let clock = Arc::new(MockClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000)));
+        let config = AegisConfig { correlation_half_life_secs: 600, min_correlation_weight: 0.1, ..Default::default() };
+        let mut aegis = AegisOrchestrator::new(config);
+        aegis.set_clock(clock.clone());
+        aegis.initialize().unwrap();
+        
+        let event = |threat_type: ThreatType| ThreatEvent {
+            id: format!("threat-{}", uuid::Uuid::new_v4()),
+            threat_type,
+            severity: ThreatSeverity::Medium,
+            confidence: 0.8,
+            source: String::from("10.0.0.7"),
+            target: String::from("192.168.1.1"),
+            timestamp: clock.now(),
+            metadata: HashMap::new(),
+        };
+        
+        aegis.process_threat_event(event(ThreatType::PortScan)).unwrap();
+        aegis.process_threat_event(event(ThreatType::PortScan)).unwrap();
+        aegis.process_threat_event(event(ThreatType::SqlInjection)).unwrap();
+        assert_eq!(aegis.correlation_weight("10.0.0.7"), 3.0);
+        
+        // Deux demi-vies : le poids est divisé par quatre, la chaîne est conservée
+        clock.advance(Duration::from_secs(1200));
+        assert!((aegis.correlation_weight("10.0.0.7") - 0.75).abs() < 1e-4);
+        assert_eq!(aegis.track_chain("10.0.0.7").len(), 2);
+        
+        // Cinq demi-vies : 3 / 32 < 0.1, la chaîne est oubliée
+        clock.advance(Duration::from_secs(1800));
+        assert_eq!(aegis.correlation_weight("10.0.0.7"), 0.0);
+        assert!(aegis.track_chain("10.0.0.7").is_empty());
+        
+        // Une nouvelle exfiltration repart d'une chaîne vide et n'est pas escaladée
+        let exfil = aegis.process_threat_event(event(ThreatType::DataExfiltration)).unwrap();
+        assert_eq!(exfil.priority, 50);
+        assert_eq!(aegis.track_chain("10.0.0.7"), vec![KillChainStage::Exfiltration]);
+    }
+    
+    #[test]
+    fn test_threat_event_bincode_round_trip() {
+        let mut metadata = HashMap::new();
+        metadata.insert("kill_chain_stage".to_string(), "exploitation".to_string());
+        metadata.insert("payload".to_string(), "' OR 1=1 --".to_string());
+        let event = ThreatEvent {
+            id: String::from("threat-42"),
+            threat_type: ThreatType::SqlInjection,
+            severity: ThreatSeverity::High,
+            confidence: 0.93,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.10"),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            metadata,
+        };
+        
+        let bytes = event.to_bincode().unwrap();
+        let decoded = ThreatEvent::from_bincode(&bytes).unwrap();
+        assert_eq!(decoded.id, event.id);
+        assert_eq!(decoded.threat_type, event.threat_type);
+        assert_eq!(decoded.severity, event.severity);
+        assert_eq!(decoded.confidence, event.confidence);
+        assert_eq!(decoded.source, event.source);
+        assert_eq!(decoded.target, event.target);
+        assert_eq!(decoded.timestamp, event.timestamp);
+        assert_eq!(decoded.metadata, event.metadata);
+        
+        // L'encodage binaire est plus compact que JSON
+        assert!(bytes.len() < serde_json::to_vec(&event).unwrap().len());
+        
+        assert!(ThreatEvent::from_bincode(&bytes[..bytes.len() / 2]).is_err());
+    }
+    
+    #[test]
+    fn test_dry_run_skips_execution() {
+        let event = ThreatEvent {
+            id: String::from("threat-critical"),
+            threat_type: ThreatType::UnknownZeroDay,
+            severity: ThreatSeverity::Critical,
+            confidence: 0.99,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let executed = Arc::new(Mutex::new(Vec::new()));
+        let run = |dry_run: bool| {
+            let mut aegis = AegisOrchestrator::new(AegisConfig { dry_run, ..Default::default() });
+            let sink = Arc::clone(&executed);
+            aegis.on_action(move |action, _| sink.lock().unwrap().push(action.clone()));
+            aegis.initialize().unwrap();
+            
+            let mut plan = aegis.process_threat_event(event.clone()).unwrap();
+            aegis.execute_response_plan(&mut plan).unwrap();
+            (plan, aegis.get_stats())
+        };
+        
+        let (plan, stats) = run(true);
+        assert!(plan.actions.iter().any(ResponseAction::is_disruptive));
+        assert_eq!(plan.status, ResponsePlanStatus::Completed);
+        assert!(plan.simulated);
+        assert_eq!(plan.action_outcomes.len(), plan.actions.len());
+        assert!(plan.action_outcomes.iter().all(|outcome| outcome.simulated && outcome.succeeded()));
+        assert!(executed.lock().unwrap().is_empty());
+        assert_eq!(stats.response_plans_completed, 0);
+        
+        let (plan, stats) = run(false);
+        assert!(!plan.simulated);
+        assert!(plan.action_outcomes.iter().all(|outcome| !outcome.simulated));
+        assert_eq!(*executed.lock().unwrap(), plan.actions);
+        assert_eq!(stats.response_plans_completed, 1);
+    }
+    
+    #[test]
+    fn test_simulated_plan_cannot_be_rolled_back() {
+        let event = ThreatEvent {
+            id: String::from("threat-critical"),
+            threat_type: ThreatType::UnknownZeroDay,
+            severity: ThreatSeverity::Critical,
+            confidence: 0.99,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let rolled_back = Arc::new(Mutex::new(Vec::new()));
+        let mut aegis = AegisOrchestrator::new(AegisConfig { dry_run: true, ..Default::default() });
+        let sink = Arc::clone(&rolled_back);
+        aegis.on_rollback(move |action| sink.lock().unwrap().push(action.clone()));
+        aegis.initialize().unwrap();
+        
+        let mut plan = aegis.process_threat_event(event).unwrap();
+        aegis.execute_response_plan(&mut plan).unwrap();
+        assert!(plan.simulated);
+        
+        // Aucune action inverse n'est émise pour des actions jamais exécutées
+        assert!(aegis.rollback_plan(&plan).is_err());
+        assert!(rolled_back.lock().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_registered_handlers_execute_actions() {
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        
+        let blocked = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&blocked);
+        aegis.register_handler(ResponseAction::BlockIp, move |event| {
+            recorder.lock().unwrap().push(event.source.clone());
+            Ok(())
+        });
+        
+        let event = ThreatEvent {
+            id: String::from("threat-handler"),
+            threat_type: ThreatType::BruteForce,
+            severity: ThreatSeverity::High,
+            confidence: 0.9,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let mut plan = aegis.process_threat_event(event.clone()).unwrap();
+        aegis.execute_response_plan(&mut plan).unwrap();
+        assert_eq!(plan.status, ResponsePlanStatus::Completed);
+        assert_eq!(*blocked.lock().unwrap(), vec![String::from("203.0.113.7")]);
+        assert_eq!(plan.action_outcomes.len(), plan.actions.len());
+        assert!(plan.action_outcomes.iter().all(ActionOutcome::succeeded));
+        
+        // L'échec d'un gestionnaire fait échouer le plan sans interrompre les autres actions
+        aegis.register_handler(ResponseAction::BlockIp, |_| Err(String::from("pare-feu injoignable")));
+        let mut plan = aegis.process_threat_event(event).unwrap();
+        assert!(aegis.execute_response_plan(&mut plan).is_err());
+        assert!(matches!(&plan.status, ResponsePlanStatus::Failed(reason) if reason.contains("pare-feu injoignable")));
+        assert_eq!(plan.action_outcomes.len(), plan.actions.len());
+        assert_eq!(plan.action_outcomes.iter().filter(|outcome| !outcome.succeeded()).count(), 1);
+        assert_eq!(aegis.get_stats().response_plans_failed, 1);
+    }
+    
+    #[test]
+    fn test_dedicated_handler_takes_precedence_over_default() {
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        
+        let handled = Arc::new(Mutex::new(Vec::new()));
+        let defaulted = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&handled);
+        aegis.register_handler(ResponseAction::BlockIp, move |_| {
+            recorder.lock().unwrap().push(ResponseAction::BlockIp);
+            Ok(())
+        });
+        let recorder = Arc::clone(&defaulted);
+        aegis.on_action(move |action, _| recorder.lock().unwrap().push(action.clone()));
+        
+        let event = ThreatEvent {
+            id: String::from("threat-precedence"),
+            threat_type: ThreatType::BruteForce,
+            severity: ThreatSeverity::High,
+            confidence: 0.9,
+            source: String::from("203.0.113.7"),
+            target: String::from("192.168.1.1"),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        
+        let mut plan = aegis.process_threat_event(event).unwrap();
+        assert!(plan.actions.contains(&ResponseAction::BlockIp));
+        aegis.execute_response_plan(&mut plan).unwrap();
+        
+        // Chaque action n'est traitée qu'une fois : BlockIp par son gestionnaire, les autres
+        // par le gestionnaire par défaut
+        assert_eq!(*handled.lock().unwrap(), vec![ResponseAction::BlockIp]);
+        let expected: Vec<ResponseAction> = plan
+            .actions
+            .iter()
+            .filter(|action| **action != ResponseAction::BlockIp)
+            .cloned()
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(*defaulted.lock().unwrap(), expected);
+    }
 }
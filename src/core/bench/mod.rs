@@ -0,0 +1,305 @@
+//! # Benchmarks
+//! 
+//! `Benchmark` exécute une opération en deux phases : un échauffement (`warmup`), non mesuré,
+//! qui remplit les caches et déclenche les allocations paresseuses, puis la phase mesurée
+//! (`iterations`), dont sont tirées les durées et la mémoire consommée.
+//! 
+//! ```ignore
+//! let result = Benchmark::new("NeuroFireWall Packet Analysis")
+//!     .warmup(10)
+//!     .iterations(1000)
+//!     .run(|| {
+//!         firewall.analyze_packet(packet.clone()).unwrap();
+//!     })?;
+//! println!("{}", result);
+//! ```
+//! 
+//! La mémoire est mesurée par un allocateur global qui comptabilise les octets alloués
+//! (fonctionnalité `alloc-stats`). Ce comptage ralentissant toutes les allocations du processus,
+//! la fonctionnalité n'est pas activée par défaut et doit être demandée pour les benchmarks
+//! (`cargo test --release --features alloc-stats`). Sans elle, `BenchmarkResult::memory_usage_kb` vaut `None`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Instant;
+
+/// Résultat d'un benchmark
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    /// Nom du benchmark
+    pub name: String,
+    /// Nombre d'itérations d'échauffement (non mesurées)
+    pub warmup: usize,
+    /// Nombre d'itérations mesurées
+    pub iterations: usize,
+    /// Durée moyenne d'une itération (en microsecondes)
+    pub avg_duration_us: f64,
+    /// Durée minimale d'une itération (en microsecondes)
+    pub min_duration_us: f64,
+    /// Durée maximale d'une itération (en microsecondes)
+    pub max_duration_us: f64,
+    /// Durée médiane d'une itération (en microsecondes)
+    pub p50_duration_us: f64,
+    /// 95e centile des durées d'itération (en microsecondes)
+    pub p95_duration_us: f64,
+    /// 99e centile des durées d'itération (en microsecondes)
+    pub p99_duration_us: f64,
+    /// Pic de mémoire allouée au-delà de celle déjà utilisée au début de la phase mesurée
+    /// (en Ko), `None` si la mesure n'est pas disponible
+    pub memory_usage_kb: Option<f64>,
+    /// Métadonnées supplémentaires (configuration testée, taille des données...)
+    pub metadata: HashMap<String, String>,
+}
+
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Benchmark: {} ===", self.name)?;
+        writeln!(f, "Itérations: {} (échauffement: {})", self.iterations, self.warmup)?;
+        writeln!(f, "Durée moyenne: {:.2} µs", self.avg_duration_us)?;
+        writeln!(f, "Durée min: {:.2} µs", self.min_duration_us)?;
+        writeln!(f, "Durée max: {:.2} µs", self.max_duration_us)?;
+        writeln!(
+            f,
+            "Centiles: p50 {:.2} µs, p95 {:.2} µs, p99 {:.2} µs",
+            self.p50_duration_us, self.p95_duration_us, self.p99_duration_us
+        )?;
+        match self.memory_usage_kb {
+            Some(memory_usage_kb) => writeln!(f, "Utilisation mémoire: {:.2} Ko", memory_usage_kb)?,
+            None => writeln!(f, "Utilisation mémoire: non mesurée")?,
+        }
+        
+        let mut metadata: Vec<_> = self.metadata.iter().collect();
+        metadata.sort();
+        for (key, value) in metadata {
+            writeln!(f, "  {}: {}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Benchmark d'une opération, configuré par ses phases d'échauffement et de mesure
+#[derive(Debug, Clone)]
+pub struct Benchmark {
+    name: String,
+    warmup: usize,
+    iterations: usize,
+    metadata: HashMap<String, String>,
+}
+
+impl Benchmark {
+    /// Nombre d'itérations d'échauffement par défaut
+    pub const DEFAULT_WARMUP: usize = 5;
+    /// Nombre d'itérations mesurées par défaut
+    pub const DEFAULT_ITERATIONS: usize = 100;
+    
+    /// Crée un benchmark avec les phases par défaut
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            warmup: Self::DEFAULT_WARMUP,
+            iterations: Self::DEFAULT_ITERATIONS,
+            metadata: HashMap::new(),
+        }
+    }
+    
+    /// Remplace le nombre d'itérations d'échauffement (0 pour n'en faire aucune)
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+    
+    /// Remplace le nombre d'itérations mesurées
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+    
+    /// Ajoute une métadonnée reportée dans le résultat
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_string(), value.to_string());
+        self
+    }
+    
+    /// Exécute l'échauffement puis la phase mesurée
+    ///
+    /// Le compteur d'allocations est global au processus : les allocations d'autres threads
+    /// pendant la phase mesurée sont également comptabilisées.
+    pub fn run<F: FnMut()>(&self, mut operation: F) -> Result<BenchmarkResult, String> {
+        if self.iterations == 0 {
+            return Err(format!("Le benchmark {} doit mesurer au moins une itération", self.name));
+        }
+        
+        for _ in 0..self.warmup {
+            operation();
+        }
+        
+        let mut durations = Vec::with_capacity(self.iterations);
+        let baseline = allocation::reset_peak();
+        for _ in 0..self.iterations {
+            let start = Instant::now();
+            operation();
+            durations.push(start.elapsed().as_secs_f64() * 1e6);
+        }
+        let memory_usage_kb = baseline
+            .zip(allocation::peak_bytes())
+            .map(|(baseline, peak)| peak.saturating_sub(baseline) as f64 / 1024.0);
+        
+        durations.sort_by(f64::total_cmp);
+        Ok(BenchmarkResult {
+            name: self.name.clone(),
+            warmup: self.warmup,
+            iterations: self.iterations,
+            avg_duration_us: durations.iter().sum::<f64>() / durations.len() as f64,
+            min_duration_us: durations[0],
+            max_duration_us: durations[durations.len() - 1],
+            p50_duration_us: percentile(&durations, 50.0),
+            p95_duration_us: percentile(&durations, 95.0),
+            p99_duration_us: percentile(&durations, 99.0),
+            memory_usage_kb,
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+/// Centile `p` (0 - 100) d'un échantillon trié par ordre croissant, par la méthode du rang
+/// le plus proche : la plus petite valeur dont au moins `p` % des mesures sont inférieures
+/// ou égales
+///
+/// Renvoie la valeur par défaut du type pour un échantillon vide.
+pub fn percentile<T: Copy + Default>(sorted: &[T], p: f64) -> T {
+    if sorted.is_empty() {
+        return T::default();
+    }
+    let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Comptabilisation des allocations par l'allocateur global (fonctionnalité `alloc-stats`)
+#[cfg(feature = "alloc-stats")]
+mod allocation {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    
+    /// Octets actuellement alloués
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+    /// Plus grande valeur atteinte par `ALLOCATED` depuis la dernière remise à zéro
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    
+    /// Allocateur système comptabilisant les octets alloués
+    struct CountingAllocator;
+    
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+    
+    impl CountingAllocator {
+        fn record_alloc(size: usize) {
+            let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        
+        fn record_dealloc(size: usize) {
+            ALLOCATED.fetch_sub(size, Ordering::Relaxed);
+        }
+    }
+    
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                Self::record_alloc(layout.size());
+            }
+            ptr
+        }
+        
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                Self::record_alloc(layout.size());
+            }
+            ptr
+        }
+        
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            Self::record_dealloc(layout.size());
+        }
+        
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                Self::record_dealloc(layout.size());
+                Self::record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+    
+    /// Ramène le pic à la mémoire actuellement allouée et renvoie celle-ci
+    pub fn reset_peak() -> Option<usize> {
+        let allocated = ALLOCATED.load(Ordering::Relaxed);
+        PEAK.store(allocated, Ordering::Relaxed);
+        Some(allocated)
+    }
+    
+    /// Pic de mémoire allouée depuis le dernier appel à `reset_peak`
+    pub fn peak_bytes() -> Option<usize> {
+        Some(PEAK.load(Ordering::Relaxed))
+    }
+}
+
+/// Sans la fonctionnalité `alloc-stats`, la mémoire n'est pas mesurée
+#[cfg(not(feature = "alloc-stats"))]
+mod allocation {
+    pub fn reset_peak() -> Option<usize> {
+        None
+    }
+    
+    pub fn peak_bytes() -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::hint::black_box;
+    
+    #[test]
+    fn test_benchmark_phases_and_memory_measurement() {
+        const ALLOCATION_SIZE: usize = 8 * 1024 * 1024;
+        
+        let mut calls = 0;
+        let result = Benchmark::new("Allocation")
+            .warmup(2)
+            .iterations(3)
+            .with_metadata("taille", "8 Mo")
+            .run(|| {
+                calls += 1;
+                black_box(vec![1u8; ALLOCATION_SIZE]);
+            })
+            .unwrap();
+        
+        assert_eq!(calls, 5);
+        assert_eq!(result.warmup, 2);
+        assert_eq!(result.iterations, 3);
+        assert!(result.min_duration_us <= result.avg_duration_us);
+        assert!(result.avg_duration_us <= result.max_duration_us);
+        assert!(result.min_duration_us <= result.p50_duration_us);
+        assert!(result.p50_duration_us <= result.p95_duration_us);
+        assert!(result.p95_duration_us <= result.p99_duration_us);
+        assert!(result.p99_duration_us <= result.max_duration_us);
+        assert_eq!(result.metadata["taille"], "8 Mo");
+        
+        // L'allocation de la phase mesurée apparaît dans le pic, au lieu d'une estimation fixe
+        if cfg!(feature = "alloc-stats") {
+            let memory_usage_kb = result.memory_usage_kb.unwrap();
+            assert!(memory_usage_kb >= (ALLOCATION_SIZE / 1024) as f64, "{} Ko", memory_usage_kb);
+            assert_ne!(memory_usage_kb, 1024.0);
+        } else {
+            assert!(result.memory_usage_kb.is_none());
+        }
+        
+        assert!(Benchmark::new("Vide").iterations(0).run(|| {}).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! # Bus d'événements
+//! 
+//! `EventBus` diffuse les événements produits par les modules à tous leurs abonnés, sans
+//! câblage explicite entre producteurs et consommateurs :
+//! 
+//! - le NeuroFireWall publie ses `DetectionEvent` (voir `NeuroFireWall::set_event_bus`) ;
+//! - WarpShield publie ses `AttackEvent` (voir `WarpShield::set_event_bus`) ;
+//! - le dashboard s'abonne via `relay_to_dashboard`, tout autre consommateur via
+//!   `EventBus::subscribe`.
+//! 
+//! AEGIS n'est pas abonné : les attaques lui sont transmises par le `Pipeline`.
+//! 
+//! ```ignore
+//! let bus = EventBus::new();
+//! firewall.set_event_bus(bus.clone());
+//! let events = bus.subscribe();
+//! ```
+//! 
+//! Chaque abonné reçoit sa propre copie des événements, dans l'ordre de publication, via une
+//! file bornée : un abonné lent ou bloqué dont la file est pleine perd les événements suivants
+//! au lieu de les accumuler sans limite. Un abonné dont le récepteur a été détruit est retiré
+//! à la publication suivante.
+
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::dashboard::{Dashboard, LiveEvent};
+use crate::neurofirewall::DetectionEvent;
+use crate::warpshield::AttackEvent;
+
+/// Événement diffusé sur le bus
+#[derive(Debug, Clone)]
+pub enum BusEvent {
+    /// Détection du NeuroFireWall
+    Detection(DetectionEvent),
+    /// Attaque observée dans un environnement WarpShield
+    Attack(AttackEvent),
+}
+
+/// Bus de diffusion des événements entre modules
+///
+/// Les clones partagent les mêmes abonnés : un module peut publier sur un clone tandis
+/// qu'un autre s'abonne sur l'original.
+#[derive(Clone)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<SyncSender<BusEvent>>>>,
+    /// Nombre d'événements en attente par abonné
+    capacity: usize,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl EventBus {
+    /// Nombre d'événements en attente par abonné par défaut
+    pub const DEFAULT_CAPACITY: usize = 1024;
+    
+    /// Crée un bus sans abonné
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Crée un bus sans abonné dont chaque abonné conserve au plus `capacity` événements en attente
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            capacity: capacity.max(1),
+        }
+    }
+    
+    /// Abonne un consommateur ; l'abonnement prend fin à la destruction du récepteur
+    pub fn subscribe(&self) -> Receiver<BusEvent> {
+        let (sender, receiver) = mpsc::sync_channel(self.capacity);
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+    
+    /// Diffuse un événement à tous les abonnés sans bloquer et renvoie le nombre de destinataires
+    ///
+    /// Les abonnés dont la file est pleine ne reçoivent pas l'événement mais restent abonnés.
+    pub fn publish(&self, event: BusEvent) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        let mut delivered = 0;
+        subscribers.retain(|subscriber| match subscriber.try_send(event.clone()) {
+            Ok(()) => {
+                delivered += 1;
+                true
+            }
+            Err(TrySendError::Full(_)) => {
+                log::warn!("File d'un abonné du bus pleine, événement abandonné");
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        delivered
+    }
+    
+    /// Nombre d'abonnés enregistrés (y compris ceux détruits depuis la dernière publication)
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().unwrap().len()
+    }
+}
+
+/// Relaie les événements du bus vers le flux en direct du dashboard
+///
+/// Les attaques WarpShield y apparaissent comme des menaces, identifiées par le générateur
+/// d'identifiants du dashboard. Le relais s'arrête lorsque
+/// toutes les copies du bus ont été détruites.
+pub fn relay_to_dashboard(bus: &EventBus, dashboard: Arc<Dashboard>) -> JoinHandle<()> {
+    let events = bus.subscribe();
+    let ids = dashboard.id_generator();
+    thread::spawn(move || {
+        for event in events {
+            let live_event = match event {
+                BusEvent::Detection(detection) => LiveEvent::Detection(detection),
+                BusEvent::Attack(attack) => LiveEvent::Threat(attack.to_threat_event(ids.as_ref())),
+            };
+            dashboard.publish_event(live_event);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime};
+    
+    use crate::dashboard::DashboardConfig;
+    use crate::neurofirewall::{FirewallDecision, NetworkPacket, NeuroFireWall, NeuroFireWallConfig, TrafficType};
+    use crate::warpshield::{AttackSignature, VirtualEnvironmentType, WarpShield, WarpShieldConfig};
+    
+    #[test]
+    fn test_slow_subscriber_drops_events_without_blocking() {
+        let bus = EventBus::with_capacity(2);
+        let slow = bus.subscribe();
+        let fast = bus.subscribe();
+        let detection = |id: &str| BusEvent::Detection(DetectionEvent {
+            id: id.to_string(),
+            timestamp: SystemTime::now(),
+            anomaly_score: 0.9,
+            decision: FirewallDecision::Block,
+            related_packets: Vec::new(),
+            trigger_features: Vec::new(),
+            feature_scores: Vec::new(),
+            description: String::from("Anomalie détectée"),
+        });
+        let id_of = |event: BusEvent| match event {
+            BusEvent::Detection(detection) => detection.id,
+            other => panic!("Événement inattendu: {:?}", other),
+        };
+        
+        let mut received = Vec::new();
+        for (index, expected_recipients) in [2, 2, 1, 1].into_iter().enumerate() {
+            assert_eq!(bus.publish(detection(&format!("event-{}", index))), expected_recipients);
+            received.push(id_of(fast.try_recv().unwrap()));
+        }
+        assert_eq!(received, vec!["event-0", "event-1", "event-2", "event-3"]);
+        
+        // L'abonné lent n'a conservé que les premiers événements et reste abonné
+        let kept: Vec<String> = slow.try_iter().map(id_of).collect();
+        assert_eq!(kept, vec!["event-0", "event-1"]);
+        assert_eq!(bus.publish(detection("event-4")), 2);
+        assert_eq!(id_of(slow.try_recv().unwrap()), "event-4");
+    }
+    
+    #[test]
+    fn test_detection_reaches_subscribers_through_bus() {
+        let bus = EventBus::new();
+        
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.initialize().unwrap();
+        firewall.set_event_bus(bus.clone());
+        firewall.load_signatures(vec![AttackSignature {
+            id: "sig-bus".to_string(),
+            name: "Webshell".to_string(),
+            description: "Téléversement de webshell".to_string(),
+            patterns: vec!["payload:cmd.php".to_string()],
+            confidence: 0.9,
+            created_at: SystemTime::now(),
+            related_attack_events: vec![],
+            recommended_countermeasures: vec![],
+        }]);
+        
+        let subscriber = bus.subscribe();
+        let dropped = bus.subscribe();
+        drop(dropped);
+        
+        let mut dashboard = Dashboard::new(DashboardConfig::default());
+        dashboard.start().unwrap();
+        let dashboard = Arc::new(dashboard);
+        let mut live_events = dashboard.subscribe_events().unwrap();
+        relay_to_dashboard(&bus, Arc::clone(&dashboard));
+        
+        let packet = NetworkPacket {
+            id: "packet-bus".to_string(),
+            source_ip: "198.51.100.23".to_string(),
+            destination_ip: "192.168.1.1".to_string(),
+            source_port: 40000,
+            destination_port: 80,
+            protocol: "TCP".to_string(),
+            size: 512,
+            timestamp: SystemTime::now(),
+            traffic_type: TrafficType::Web,
+            payload_sample: b"POST /upload/cmd.php".to_vec(),
+            metadata: HashMap::new(),
+        };
+        let (decision, event) = firewall.analyze_packet(packet).unwrap();
+        assert_eq!(decision, FirewallDecision::Block);
+        let event = event.unwrap();
+        
+        match subscriber.recv_timeout(Duration::from_secs(1)).unwrap() {
+            BusEvent::Detection(received) => assert_eq!(received.id, event.id),
+            other => panic!("Événement inattendu: {:?}", other),
+        }
+        // Le récepteur détruit a été retiré ; restent l'abonné et le relais du dashboard
+        assert_eq!(bus.subscriber_count(), 2);
+        
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        let relayed = loop {
+            match live_events.try_recv() {
+                Ok(relayed) => break relayed,
+                Err(_) if std::time::Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+                Err(e) => panic!("Détection non relayée au dashboard: {:?}", e),
+            }
+        };
+        assert!(matches!(relayed, LiveEvent::Detection(received) if received.id == event.id));
+        
+        // Les attaques WarpShield empruntent le même bus
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.initialize().unwrap();
+        warpshield.set_event_bus(bus.clone());
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&env.id, "198.51.100.23").unwrap();
+        let attack = warpshield.record_attack_event(&env.id, "sql_injection", HashMap::new()).unwrap();
+        
+        match subscriber.recv_timeout(Duration::from_secs(1)).unwrap() {
+            BusEvent::Attack(received) => assert_eq!(received.id, attack.id),
+            other => panic!("Événement inattendu: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! # Horloge
+//! 
+//! Les modules dont le comportement dépend de l'heure (expiration des sessions WarpShield
+//! et des blocages du NeuroFireWall, apprentissage de la ligne de base, horodatage des plans
+//! AEGIS) lisent le temps via le trait `Clock` plutôt que `SystemTime::now()`. En production,
+//! `SystemClock` est utilisé ; les tests injectent une `MockClock` avancée manuellement, sans
+//! attente réelle.
+//! 
+//! Les mesures de durée d'exécution (temps d'analyse, latence) restent basées sur `Instant`.
+
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Source de temps
+pub trait Clock: Send + Sync {
+    /// Instant présent
+    fn now(&self) -> SystemTime;
+}
+
+/// Horloge partagée entre les composants d'un module
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Horloge système
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Horloge système partagée (valeur par défaut des modules)
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Horloge de test avancée manuellement
+#[cfg(test)]
+#[derive(Debug)]
+pub struct MockClock {
+    now: std::sync::Mutex<SystemTime>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    /// Crée une horloge arrêtée à l'instant donné
+    pub fn new(start: SystemTime) -> Self {
+        Self {
+            now: std::sync::Mutex::new(start),
+        }
+    }
+    
+    /// Avance l'horloge de la durée donnée
+    pub fn advance(&self, duration: std::time::Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::time::Duration;
+    
+    #[test]
+    fn test_mock_clock_advances_without_sleeping() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let clock = MockClock::new(start);
+        assert_eq!(clock.now(), start);
+        
+        clock.advance(Duration::from_secs(3600));
+        assert_eq!(clock.now(), start + Duration::from_secs(3600));
+        
+        assert!(SystemClock.now() > start);
+    }
+}
@@ -0,0 +1,302 @@
+//! # Configuration ICARUS
+//! 
+//! Ce module permet de configurer l'ensemble du système à partir d'un seul fichier
+//! `icarus.toml` (ou JSON). Chaque section correspond à la configuration d'un module ;
+//! les sections et les champs absents conservent leurs valeurs par défaut.
+//! 
+//! ```toml
+//! [neurofirewall]
+//! anomaly_threshold = 0.9
+//! 
+//! [warpshield]
+//! environment_fidelity = 0.8
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::aegis::AegisConfig;
+use crate::crypto::quantum_vault::QuantumVaultConfig;
+use crate::dashboard::{DashboardConfig, WebhookNotifier};
+use crate::neural_net::NeuralNetConfig;
+use crate::neurofirewall::NeuroFireWallConfig;
+use crate::warpshield::WarpShieldConfig;
+
+/// Variable d'environnement désignant le fichier de configuration du binaire
+pub const CONFIG_PATH_ENV: &str = "ICARUS_CONFIG";
+
+/// Fichier de configuration lu en l'absence de `ICARUS_CONFIG`
+pub const DEFAULT_CONFIG_PATH: &str = "icarus.toml";
+
+/// Configuration complète du système ICARUS
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IcarusConfig {
+    /// Configuration d'AEGIS
+    pub aegis: AegisConfig,
+    /// Configuration du NeuroFireWall
+    pub neurofirewall: NeuroFireWallConfig,
+    /// Configuration de WarpShield
+    pub warpshield: WarpShieldConfig,
+    /// Configuration du dashboard
+    pub dashboard: DashboardConfig,
+    /// Configuration de QuantumVault
+    pub quantum_vault: QuantumVaultConfig,
+    /// Configuration du moteur neuronal
+    pub neural_net: NeuralNetConfig,
+}
+
+impl IcarusConfig {
+    /// Charge et valide la configuration depuis un fichier TOML ou JSON
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let config: Self = load_config(path)?;
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// Charge la configuration comme `load`, ou les valeurs par défaut si le fichier n'existe pas
+    ///
+    /// Un fichier présent mais illisible ou invalide reste une erreur.
+    pub fn load_or_default<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        if path.as_ref().exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+    
+    /// Vérifie que les valeurs de configuration sont dans des plages acceptables
+    pub fn validate(&self) -> Result<(), String> {
+        let aegis = &self.aegis;
+        check_unit_interval("aegis.autonomy_level", aegis.autonomy_level)?;
+        check_unit_interval("aegis.auto_response_threshold", aegis.auto_response_threshold)?;
+        check_unit_interval("aegis.low_reputation_threshold", aegis.low_reputation_threshold)?;
+        check_positive("aegis.event_history_size", aegis.event_history_size)?;
+        check_positive("aegis.max_tracked_chains", aegis.max_tracked_chains)?;
+        check_positive("aegis.correlation_half_life_secs", aegis.correlation_half_life_secs as usize)?;
+        check_unit_interval("aegis.min_correlation_weight", aegis.min_correlation_weight)?;
+        check_log_level("aegis.log_level", aegis.log_level)?;
+        
+        let firewall = &self.neurofirewall;
+        check_positive("neurofirewall.buffer_size", firewall.buffer_size)?;
+        check_positive("neurofirewall.max_payload_sample_bytes", firewall.max_payload_sample_bytes)?;
+        check_unit_interval("neurofirewall.anomaly_threshold", firewall.anomaly_threshold)?;
+        check_unit_interval("neurofirewall.sensitivity", firewall.sensitivity)?;
+        if let Some(thresholds) = &firewall.decision_thresholds {
+            thresholds.validate().map_err(|e| format!("neurofirewall.decision_thresholds: {}", e))?;
+        }
+        check_positive("neurofirewall.packets_per_second", firewall.packets_per_second as usize)?;
+        check_positive("neurofirewall.hidden_layer_size", firewall.hidden_layer_size)?;
+        check_positive("neurofirewall.quarantine_size", firewall.quarantine_size)?;
+        check_positive("neurofirewall.block_ttl_secs", firewall.block_ttl_secs as usize)?;
+        check_positive("neurofirewall.block_backoff_factor", firewall.block_backoff_factor as usize)?;
+        check_positive("neurofirewall.max_tracked_offenders", firewall.max_tracked_offenders)?;
+        check_positive("neurofirewall.stats_window_secs", firewall.stats_window_secs as usize)?;
+        check_positive("neurofirewall.max_window_samples", firewall.max_window_samples)?;
+        check_positive("neurofirewall.max_retained_events", firewall.max_retained_events)?;
+        check_positive("neurofirewall.feedback_window_size", firewall.feedback_window_size)?;
+        check_positive("neurofirewall.max_consecutive_errors", firewall.max_consecutive_errors as usize)?;
+        if let Some(invalid) = firewall
+            .trusted_sources
+            .iter()
+            .find(|source| source.parse::<ipnet::IpNet>().is_err() && source.parse::<std::net::IpAddr>().is_err())
+        {
+            return Err(format!("neurofirewall.trusted_sources contient une source invalide: {}", invalid));
+        }
+        check_log_level("neurofirewall.log_level", firewall.log_level)?;
+        if !(firewall.learning_rate > 0.0 && firewall.learning_rate.is_finite()) {
+            return Err(format!(
+                "neurofirewall.learning_rate doit être strictement positif (valeur: {})",
+                firewall.learning_rate
+            ));
+        }
+        
+        let warpshield = &self.warpshield;
+        check_positive("warpshield.max_virtual_environments", warpshield.max_virtual_environments)?;
+        check_positive("warpshield.max_events_per_environment", warpshield.max_events_per_environment)?;
+        check_positive("warpshield.max_signatures", warpshield.max_signatures)?;
+        check_unit_interval("warpshield.environment_fidelity", warpshield.environment_fidelity)?;
+        check_unit_interval("warpshield.max_resource_allocation", warpshield.max_resource_allocation)?;
+        check_log_level("warpshield.log_level", warpshield.log_level)?;
+        
+        let dashboard = &self.dashboard;
+        if !(1..=5).contains(&dashboard.detail_level) {
+            return Err(format!(
+                "dashboard.detail_level doit être compris entre 1 et 5 (valeur: {})",
+                dashboard.detail_level
+            ));
+        }
+        if !["light", "dark", "system"].contains(&dashboard.theme.as_str()) {
+            return Err(format!(
+                "dashboard.theme doit valoir light, dark ou system (valeur: {})",
+                dashboard.theme
+            ));
+        }
+        check_positive("dashboard.refresh_interval_ms", dashboard.refresh_interval_ms as usize)?;
+        check_positive("dashboard.max_event_subscribers", dashboard.max_event_subscribers)?;
+        check_positive("dashboard.notification_queue_size", dashboard.notification_queue_size)?;
+        if let Some(url) = &dashboard.alert_webhook_url {
+            WebhookNotifier::new(url).map_err(|e| format!("dashboard.alert_webhook_url: {}", e))?;
+        }
+        
+        check_positive("quantum_vault.key_rotation_days", self.quantum_vault.key_rotation_days as usize)?;
+        
+        let neural_net = &self.neural_net;
+        check_unit_interval("neural_net.anomaly_threshold", neural_net.anomaly_threshold)?;
+        check_positive("neural_net.attention_heads", neural_net.attention_heads)?;
+        check_positive("neural_net.max_sequence_length", neural_net.max_sequence_length)?;
+        
+        Ok(())
+    }
+}
+
+/// Charge une configuration depuis un fichier, au format déduit de l'extension
+/// (`.toml` ou `.json`)
+pub fn load_config<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, String> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+    
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&content)
+            .map_err(|e| format!("Configuration TOML invalide dans {}: {}", path.display(), e)),
+        Some("json") => serde_json::from_str(&content)
+            .map_err(|e| format!("Configuration JSON invalide dans {}: {}", path.display(), e)),
+        _ => Err(format!(
+            "Format de configuration non supporté pour {} (attendu: .toml ou .json)",
+            path.display()
+        )),
+    }
+}
+
+/// Vérifie qu'une valeur est comprise dans [0, 1]
+fn check_unit_interval(name: &str, value: f32) -> Result<(), String> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("{} doit être compris entre 0.0 et 1.0 (valeur: {})", name, value))
+    }
+}
+
+/// Vérifie qu'une valeur est strictement positive
+fn check_positive(name: &str, value: usize) -> Result<(), String> {
+    if value > 0 {
+        Ok(())
+    } else {
+        Err(format!("{} doit être strictement positif", name))
+    }
+}
+
+/// Vérifie qu'un niveau de journalisation est valide (0 à 4)
+fn check_log_level(name: &str, value: u8) -> Result<(), String> {
+    if value <= 4 {
+        Ok(())
+    } else {
+        Err(format!("{} doit être compris entre 0 et 4 (valeur: {})", name, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use crate::crypto::quantum_vault::PostQuantumAlgorithm;
+    
+    /// Écrit un fichier de configuration temporaire et renvoie son chemin
+    fn write_temp_config(name: &str, content: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("icarus-{}-{}", uuid::Uuid::new_v4(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+    
+    #[test]
+    fn test_load_toml_overrides_defaults() {
+        let path = write_temp_config("icarus.toml", r#"
+            [aegis]
+            autonomy_level = 0.5
+
+            [neurofirewall]
+            anomaly_threshold = 0.9
+            packets_per_second = 250
+
+            [warpshield]
+            environment_fidelity = 0.75
+
+            [dashboard]
+            theme = "light"
+
+            [quantum_vault]
+            encryption_algorithm = "Kyber1024"
+
+            [neural_net]
+            use_gpu_acceleration = false
+        "#);
+        
+        let config = IcarusConfig::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        
+        assert_eq!(config.aegis.autonomy_level, 0.5);
+        assert_eq!(config.neurofirewall.anomaly_threshold, 0.9);
+        assert_eq!(config.neurofirewall.packets_per_second, 250);
+        assert_eq!(config.warpshield.environment_fidelity, 0.75);
+        assert_eq!(config.dashboard.theme, "light");
+        assert_eq!(config.quantum_vault.encryption_algorithm, PostQuantumAlgorithm::Kyber1024);
+        assert!(!config.neural_net.use_gpu_acceleration);
+        
+        // Les valeurs absentes du fichier conservent leurs valeurs par défaut
+        let defaults = IcarusConfig::default();
+        assert_eq!(config.neurofirewall.buffer_size, defaults.neurofirewall.buffer_size);
+        assert_eq!(config.dashboard.server_port, defaults.dashboard.server_port);
+    }
+    
+    #[test]
+    fn test_load_json_module_config() {
+        let path = write_temp_config("warpshield.json", r#"{ "max_virtual_environments": 4 }"#);
+        
+        let config: WarpShieldConfig = load_config(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        
+        assert_eq!(config.max_virtual_environments, 4);
+    }
+    
+    #[test]
+    fn test_load_rejects_out_of_range_values() {
+        let path = write_temp_config("icarus.toml", "[warpshield]\nenvironment_fidelity = 1.5\n");
+        
+        let error = IcarusConfig::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        
+        assert!(error.contains("warpshield.environment_fidelity"));
+    }
+    
+    #[test]
+    fn test_load_rejects_inverted_decision_thresholds() {
+        let path = write_temp_config("icarus.toml", r#"
+            [neurofirewall.decision_thresholds]
+            block = 0.6
+            quarantine = 0.9
+            alert = 0.5
+        "#);
+        
+        let error = IcarusConfig::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        
+        assert!(error.contains("neurofirewall.decision_thresholds"));
+    }
+    
+    #[test]
+    fn test_load_or_default_falls_back_only_when_missing() {
+        let missing = std::env::temp_dir().join(format!("icarus-{}-absent.toml", uuid::Uuid::new_v4()));
+        let config = IcarusConfig::load_or_default(&missing).unwrap();
+        assert_eq!(config.neurofirewall.anomaly_threshold, NeuroFireWallConfig::default().anomaly_threshold);
+        
+        let path = write_temp_config("icarus.toml", "[warpshield]\nenvironment_fidelity = 1.5\n");
+        let error = IcarusConfig::load_or_default(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        
+        assert!(error.contains("warpshield.environment_fidelity"));
+    }
+}
@@ -0,0 +1,10 @@
+//! # Module Crypto
+//!
+//! Primitives cryptographiques du système ICARUS. Le coffre [`quantum_vault`] regroupe
+//! le chiffrement hybride, les signatures et la gestion des clés post-quantiques.
+//!
+//! `sphincs.rs` n'est pas compilé : il dépend d'une interface de signature
+//! (`DigitalSignature`, `SigningKey`, `CryptoError`) absente du crate. SPHINCS+ reste
+//! disponible via [`quantum_vault::PostQuantumAlgorithm`].
+
+pub mod quantum_vault;
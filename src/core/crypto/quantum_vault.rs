@@ -10,14 +10,61 @@
 //! - Gestion sécurisée des clés avec protection matérielle
 //! - Chiffrement hybride classique/post-quantique
 //! - Protocoles d'établissement de clés résistants aux attaques quantiques
+//!
+//! ## Comparaisons en temps constant
+//!
+//! Toute comparaison d'une valeur secrète ou authentifiante (signature, empreinte, étiquette
+//! d'authentification) doit passer par [`constant_time_eq`] : une comparaison `==` s'arrête
+//! au premier octet différent et révèle, par son temps d'exécution, la longueur du préfixe
+//! correct. Les garanties de sécurité du module reposent sur cette règle. Les étiquettes
+//! AES-GCM sont vérifiées en temps constant par le crate `aes-gcm`.
+//!
+//! ## Simulation et implémentations réelles
+//!
+//! Par défaut, les primitives post-quantiques (génération de clés, encapsulation, signature)
+//! sont **simulées** : elles n'offrent aucune sécurité réelle et servent au développement.
+//! La fonctionnalité cargo `real-pqcrypto` les remplace par les implémentations PQClean des
+//! crates `pqcrypto-*`. [`QuantumVault::is_simulation`] indique le mode actif, et un
+//! avertissement est journalisé à la création d'un coffre en mode simulation.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::io::{self, Read, Write};
 use std::fs::File;
-use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Taille des clés et des secrets X25519 (en octets)
+const X25519_KEY_SIZE: usize = 32;
+/// Taille du nonce AES-GCM (en octets)
+const HYBRID_NONCE_SIZE: usize = 12;
+/// Contexte de dérivation de la clé hybride
+const HYBRID_KDF_INFO: &[u8] = b"ICARUS QuantumVault hybride v1";
+/// Taille des blocs clairs du chiffrement par flux (en octets)
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+/// Taille du préfixe aléatoire des nonces de flux (en octets)
+const STREAM_NONCE_PREFIX_SIZE: usize = 7;
+/// Taille de l'étiquette d'authentification AES-GCM (en octets)
+const STREAM_TAG_SIZE: usize = 16;
+/// Taille de l'empreinte SHA-256 des fichiers signés (en octets)
+const FILE_DIGEST_SIZE: usize = 32;
+/// Erreur renvoyée lorsqu'une clé Kyber est utilisée pour signer ou vérifier
+const KEM_CANNOT_SIGN: &str = "Kyber is a KEM and cannot sign";
+/// Erreur renvoyée lorsque la clé publique distante implique une autre variante de Kyber
+/// que celle attendue par la configuration
+const ALGORITHM_DOWNGRADE: &str = "Kyber parameter set mismatch (possible downgrade)";
+/// Taille des données chiffrées à chaque itération de `benchmark_algorithm` (en octets)
+const BENCHMARK_PAYLOAD_SIZE: usize = 1024;
 
 /// Types d'algorithmes post-quantiques supportés
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PostQuantumAlgorithm {
     /// Kyber - Algorithme de chiffrement à clé publique basé sur les réseaux
     Kyber512,
@@ -39,8 +86,60 @@ pub enum PostQuantumAlgorithm {
     Falcon1024,
 }
 
+impl PostQuantumAlgorithm {
+    /// Tailles (clé publique, clé privée) en octets
+    pub fn key_sizes(&self) -> (usize, usize) {
+        match self {
+            PostQuantumAlgorithm::Kyber512 => (800, 1632),
+            PostQuantumAlgorithm::Kyber768 => (1184, 2400),
+            PostQuantumAlgorithm::Kyber1024 => (1568, 3168),
+            PostQuantumAlgorithm::Dilithium2 => (1312, 2528),
+            PostQuantumAlgorithm::Dilithium3 => (1952, 4000),
+            PostQuantumAlgorithm::Dilithium5 => (2592, 4864),
+            PostQuantumAlgorithm::SphincsSha2128f => (32, 64),
+            PostQuantumAlgorithm::SphincsSha2192f => (48, 96),
+            PostQuantumAlgorithm::SphincsSha2256f => (64, 128),
+            PostQuantumAlgorithm::Falcon512 => (897, 1281),
+            PostQuantumAlgorithm::Falcon1024 => (1793, 2305),
+        }
+    }
+    
+    /// Indique si l'algorithme est un mécanisme d'encapsulation de clés (Kyber)
+    pub fn is_kem(&self) -> bool {
+        matches!(
+            self,
+            PostQuantumAlgorithm::Kyber512 | PostQuantumAlgorithm::Kyber768 | PostQuantumAlgorithm::Kyber1024
+        )
+    }
+    
+    /// Variante de Kyber impliquée par la taille d'une clé publique post-quantique
+    pub fn kem_from_public_key_size(size: usize) -> Option<Self> {
+        [PostQuantumAlgorithm::Kyber512, PostQuantumAlgorithm::Kyber768, PostQuantumAlgorithm::Kyber1024]
+            .into_iter()
+            .find(|algorithm| algorithm.key_sizes().0 == size)
+    }
+    
+    /// Taille des signatures produites, `None` pour les algorithmes de chiffrement
+    pub fn signature_size(&self) -> Option<usize> {
+        match self {
+            PostQuantumAlgorithm::Dilithium2 => Some(2420),
+            PostQuantumAlgorithm::Dilithium3 => Some(3293),
+            PostQuantumAlgorithm::Dilithium5 => Some(4595),
+            PostQuantumAlgorithm::SphincsSha2128f => Some(17088),
+            PostQuantumAlgorithm::SphincsSha2192f => Some(35664),
+            PostQuantumAlgorithm::SphincsSha2256f => Some(49856),
+            PostQuantumAlgorithm::Falcon512 => Some(666),
+            PostQuantumAlgorithm::Falcon1024 => Some(1280),
+            PostQuantumAlgorithm::Kyber512
+            | PostQuantumAlgorithm::Kyber768
+            | PostQuantumAlgorithm::Kyber1024 => None,
+        }
+    }
+}
+
 /// Configuration du module QuantumVault
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct QuantumVaultConfig {
     /// Algorithme de chiffrement à utiliser
     pub encryption_algorithm: PostQuantumAlgorithm,
@@ -87,26 +186,7 @@ pub struct PostQuantumKeyPair {
 impl PostQuantumKeyPair {
     /// Crée une nouvelle paire de clés pour l'algorithme spécifié
     pub fn new(algorithm: PostQuantumAlgorithm) -> Result<Self, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle génère des clés fictives
-        
-        let (public_key_size, private_key_size) = match algorithm {
-            PostQuantumAlgorithm::Kyber512 => (800, 1632),
-            PostQuantumAlgorithm::Kyber768 => (1184, 2400),
-            PostQuantumAlgorithm::Kyber1024 => (1568, 3168),
-            PostQuantumAlgorithm::Dilithium2 => (1312, 2528),
-            PostQuantumAlgorithm::Dilithium3 => (1952, 4000),
-            PostQuantumAlgorithm::Dilithium5 => (2592, 4864),
-            PostQuantumAlgorithm::SphincsSha2128f => (32, 64),
-            PostQuantumAlgorithm::SphincsSha2192f => (48, 96),
-            PostQuantumAlgorithm::SphincsSha2256f => (64, 128),
-            PostQuantumAlgorithm::Falcon512 => (897, 1281),
-            PostQuantumAlgorithm::Falcon1024 => (1793, 2305),
-        };
-        
-        // Génération de clés fictives
-        let public_key = vec![0u8; public_key_size];
-        let private_key = vec![0u8; private_key_size];
+        let (public_key, private_key) = backend::generate_keypair(algorithm)?;
         
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -125,6 +205,27 @@ impl PostQuantumKeyPair {
         })
     }
     
+    /// Crée une paire de clés hybride : clés post-quantiques suivies d'une clé X25519
+    ///
+    /// Les 32 derniers octets des clés publique et privée contiennent la partie classique.
+    pub fn new_hybrid(algorithm: PostQuantumAlgorithm) -> Result<Self, String> {
+        let mut keypair = Self::new(algorithm)?;
+        
+        let classical_secret = StaticSecret::from(rand::random::<[u8; X25519_KEY_SIZE]>());
+        let classical_public = PublicKey::from(&classical_secret);
+        
+        keypair.public_key.extend_from_slice(classical_public.as_bytes());
+        keypair.private_key.extend_from_slice(&classical_secret.to_bytes());
+        
+        Ok(keypair)
+    }
+    
+    /// Partie de la clé privée utilisée par le déchiffrement simulé (miroir de la clé publique)
+    fn kem_secret(&self) -> &[u8] {
+        let public_key_size = self.algorithm.key_sizes().0.min(self.private_key.len());
+        &self.private_key[..public_key_size]
+    }
+    
     /// Sauvegarde la paire de clés dans des fichiers
     pub fn save_to_files<P: AsRef<Path>>(&self, public_key_path: P, private_key_path: P) -> io::Result<()> {
         let mut public_file = File::create(public_key_path)?;
@@ -177,6 +278,15 @@ pub struct EncryptionResult {
     pub is_hybrid: bool,
 }
 
+/// Résultat d'un échange de clés
+#[derive(Debug)]
+pub struct KeyExchangeResult {
+    /// Clé partagée
+    pub shared_key: Vec<u8>,
+    /// Algorithme convenu, vérifié contre la clé publique distante
+    pub algorithm: PostQuantumAlgorithm,
+}
+
 /// Résultat d'une opération de signature
 #[derive(Debug)]
 pub struct SignatureResult {
@@ -188,26 +298,152 @@ pub struct SignatureResult {
     pub timestamp: u64,
 }
 
+/// Temps médians (en microsecondes) mesurés par `QuantumVault::benchmark_algorithm`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlgoBenchResult {
+    /// Génération d'une paire de clés
+    pub keygen_us: f64,
+    /// Chiffrement de `BENCHMARK_PAYLOAD_SIZE` octets
+    pub encrypt_us: f64,
+    /// Déchiffrement des mêmes données
+    pub decrypt_us: f64,
+}
+
+/// Gestionnaire de clés nommées conservées en mémoire
+///
+/// Les clés expirées sont évincées lors de leur accès.
+#[derive(Debug, Default)]
+pub struct KeyManager {
+    keys: HashMap<String, PostQuantumKeyPair>,
+}
+
+impl KeyManager {
+    /// Crée un gestionnaire de clés vide
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Enregistre une paire de clés sous un nom, en renvoyant celle qu'elle remplace
+    pub fn insert(&mut self, name: &str, keypair: PostQuantumKeyPair) -> Option<PostQuantumKeyPair> {
+        self.keys.insert(name.to_string(), keypair)
+    }
+    
+    /// Obtient une paire de clés par son nom ; une clé expirée est évincée et `None` est renvoyé
+    pub fn get(&mut self, name: &str) -> Option<&PostQuantumKeyPair> {
+        let now = unix_timestamp();
+        if self.keys.get(name).is_some_and(|keypair| keypair.expires_at <= now) {
+            self.keys.remove(name);
+            return None;
+        }
+        
+        self.keys.get(name)
+    }
+    
+    /// Retire une paire de clés
+    pub fn remove(&mut self, name: &str) -> Option<PostQuantumKeyPair> {
+        self.keys.remove(name)
+    }
+    
+    /// Évince toutes les clés expirées et renvoie leur nombre
+    pub fn evict_expired(&mut self) -> usize {
+        let now = unix_timestamp();
+        let before = self.keys.len();
+        self.keys.retain(|_, keypair| keypair.expires_at > now);
+        before - self.keys.len()
+    }
+    
+    /// Nombre de clés conservées
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+    
+    /// Indique si aucune clé n'est conservée
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
 /// Module principal QuantumVault
 pub struct QuantumVault {
     config: QuantumVaultConfig,
+    /// Gestionnaire de clés nommées (optionnel)
+    key_manager: Option<Mutex<KeyManager>>,
     // Les champs suivants seront implémentés dans les versions futures
-    // key_manager: KeyManager,
     // hardware_security: Option<HardwareSecurity>,
 }
 
 impl QuantumVault {
     /// Crée une nouvelle instance de QuantumVault
     pub fn new(config: QuantumVaultConfig) -> Self {
+        if backend::IS_SIMULATION {
+            log::warn!(
+                "QuantumVault en mode simulation : aucune sécurité post-quantique réelle \
+                 (compiler avec la fonctionnalité real-pqcrypto)"
+            );
+        }
+        
         Self {
             config,
+            key_manager: None,
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
     
+    /// Indique si les primitives post-quantiques sont simulées (fonctionnalité
+    /// `real-pqcrypto` désactivée)
+    pub fn is_simulation(&self) -> bool {
+        backend::IS_SIMULATION
+    }
+    
+    /// Obtient la configuration du coffre
+    pub fn get_config(&self) -> &QuantumVaultConfig {
+        &self.config
+    }
+    
+    /// Associe un gestionnaire de clés nommées au coffre
+    pub fn set_key_manager(&mut self, key_manager: KeyManager) {
+        self.key_manager = Some(Mutex::new(key_manager));
+    }
+    
+    /// Enregistre une paire de clés nommée dans le gestionnaire de clés
+    pub fn store_key(&self, name: &str, keypair: PostQuantumKeyPair) -> Result<(), String> {
+        let key_manager = self.key_manager.as_ref().ok_or("Aucun gestionnaire de clés configuré")?;
+        key_manager.lock().unwrap().insert(name, keypair);
+        Ok(())
+    }
+    
+    /// Chiffre des données avec la clé publique de la paire nommée
+    pub fn encrypt_with(&self, name: &str, plaintext: &[u8]) -> Result<EncryptionResult, String> {
+        self.with_named_key(name, |keypair| self.encrypt(plaintext, &keypair.public_key))
+    }
+    
+    /// Signe des données avec la paire nommée
+    pub fn sign_with(&self, name: &str, data: &[u8]) -> Result<SignatureResult, String> {
+        self.with_named_key(name, |keypair| self.sign(data, keypair))
+    }
+    
+    /// Exécute une opération avec une paire de clés du gestionnaire
+    fn with_named_key<T>(
+        &self,
+        name: &str,
+        operation: impl FnOnce(&PostQuantumKeyPair) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let key_manager = self.key_manager.as_ref().ok_or("Aucun gestionnaire de clés configuré")?;
+        let mut key_manager = key_manager.lock().unwrap();
+        let keypair = key_manager
+            .get(name)
+            .ok_or(format!("Clé inconnue ou expirée: {}", name))?;
+        
+        operation(keypair)
+    }
+    
     /// Génère une nouvelle paire de clés pour le chiffrement
     pub fn generate_encryption_keypair(&self) -> Result<PostQuantumKeyPair, String> {
-        PostQuantumKeyPair::new(self.config.encryption_algorithm)
+        if self.config.use_hybrid_encryption {
+            PostQuantumKeyPair::new_hybrid(self.config.encryption_algorithm)
+        } else {
+            PostQuantumKeyPair::new(self.config.encryption_algorithm)
+        }
     }
     
     /// Génère une nouvelle paire de clés pour la signature
@@ -215,20 +451,116 @@ impl QuantumVault {
         PostQuantumKeyPair::new(self.config.signature_algorithm)
     }
     
+    /// Génère `count` paires de clés de chiffrement, réparties sur plusieurs threads
+    pub fn generate_encryption_keypairs(&self, count: usize) -> Result<Vec<PostQuantumKeyPair>, String> {
+        self.generate_keypairs(count, Self::generate_encryption_keypair)
+    }
+    
+    /// Génère `count` paires de clés de signature, réparties sur plusieurs threads
+    pub fn generate_signature_keypairs(&self, count: usize) -> Result<Vec<PostQuantumKeyPair>, String> {
+        self.generate_keypairs(count, Self::generate_signature_keypair)
+    }
+    
+    /// Génère un lot de paires de clés en parallèle
+    ///
+    /// Chaque thread tire ses octets de son propre générateur cryptographique (`thread_rng`),
+    /// initialisé depuis l'entropie du système.
+    fn generate_keypairs(
+        &self,
+        count: usize,
+        generate: fn(&Self) -> Result<PostQuantumKeyPair, String>,
+    ) -> Result<Vec<PostQuantumKeyPair>, String> {
+        let threads = std::thread::available_parallelism()
+            .map_or(1, |threads| threads.get())
+            .min(count.max(1));
+        let per_thread = count.div_ceil(threads);
+        
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|index| {
+                    let batch = per_thread.min(count.saturating_sub(index * per_thread));
+                    scope.spawn(move || (0..batch).map(|_| generate(self)).collect::<Result<Vec<_>, _>>())
+                })
+                .collect();
+            
+            let mut keypairs = Vec::with_capacity(count);
+            for worker in workers {
+                let batch = worker.join().map_err(|_| "Échec d'un thread de génération de clés".to_string())??;
+                keypairs.extend(batch);
+            }
+            Ok(keypairs)
+        })
+    }
+    
+    /// Mesure les performances d'un algorithme de chiffrement sur `iterations` cycles
+    /// génération de clés / chiffrement / déchiffrement
+    ///
+    /// Le mode hybride de la configuration est conservé. Les médianes sont peu sensibles aux
+    /// interruptions ponctuelles ; seules les comparaisons relatives entre algorithmes sont
+    /// significatives d'une machine à l'autre.
+    pub fn benchmark_algorithm(&self, algorithm: PostQuantumAlgorithm, iterations: usize) -> Result<AlgoBenchResult, String> {
+        if !algorithm.is_kem() {
+            return Err(format!("{:?} n'est pas un algorithme de chiffrement", algorithm));
+        }
+        if iterations == 0 {
+            return Err("Le nombre d'itérations doit être strictement positif".to_string());
+        }
+        
+        let vault = QuantumVault {
+            config: QuantumVaultConfig { encryption_algorithm: algorithm, ..self.config.clone() },
+            key_manager: None,
+        };
+        let plaintext = vec![0x5a; BENCHMARK_PAYLOAD_SIZE];
+        let elapsed_us = |start: Instant| start.elapsed().as_secs_f64() * 1e6;
+        
+        let mut keygen = Vec::with_capacity(iterations);
+        let mut encrypt = Vec::with_capacity(iterations);
+        let mut decrypt = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let keypair = vault.generate_encryption_keypair()?;
+            keygen.push(elapsed_us(start));
+            
+            let start = Instant::now();
+            let encrypted = vault.encrypt(&plaintext, &keypair.public_key)?;
+            encrypt.push(elapsed_us(start));
+            
+            let start = Instant::now();
+            let decrypted = vault.decrypt(&encrypted.ciphertext, &encrypted.nonce, &keypair)?;
+            decrypt.push(elapsed_us(start));
+            
+            if decrypted != plaintext {
+                return Err(format!("Aller-retour de chiffrement incorrect pour {:?}", algorithm));
+            }
+        }
+        
+        Ok(AlgoBenchResult {
+            keygen_us: median(&mut keygen),
+            encrypt_us: median(&mut encrypt),
+            decrypt_us: median(&mut decrypt),
+        })
+    }
+    
     /// Chiffre des données avec une clé publique
+    ///
+    /// En mode hybride, la clé publique doit provenir d'une paire hybride et le chiffré
+    /// est structuré ainsi : clé publique X25519 éphémère, secret post-quantique encapsulé,
+    /// puis données scellées par AES-256-GCM. Avec les primitives réelles, le mode non hybride
+    /// scelle de même les données sous le seul secret post-quantique encapsulé.
     pub fn encrypt(&self, plaintext: &[u8], public_key: &[u8]) -> Result<EncryptionResult, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle simule un chiffrement
+        if self.config.use_hybrid_encryption || !self.is_simulation() {
+            return self.encrypt_sealed(plaintext, public_key);
+        }
+        
+        // Simulation d'un chiffrement non hybride
+        
+        self.negotiate_algorithm(public_key)?;
         
         // Génération d'un nonce aléatoire
         let nonce = vec![0u8; 24]; // Taille typique pour un nonce
         
         // Simulation de chiffrement (simple XOR avec la première partie de la clé publique)
-        let mut ciphertext = Vec::with_capacity(plaintext.len());
-        for (i, &byte) in plaintext.iter().enumerate() {
-            let key_byte = if i < public_key.len() { public_key[i] } else { 0 };
-            ciphertext.push(byte ^ key_byte);
-        }
+        let ciphertext = xor_with_key(plaintext, public_key);
         
         Ok(EncryptionResult {
             ciphertext,
@@ -240,9 +572,6 @@ impl QuantumVault {
     
     /// Déchiffre des données avec une clé privée
     pub fn decrypt(&self, ciphertext: &[u8], nonce: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle simule un déchiffrement
-        
         // Vérification de l'algorithme
         if keypair.algorithm != self.config.encryption_algorithm {
             return Err(format!(
@@ -251,22 +580,280 @@ impl QuantumVault {
             ));
         }
         
+        if self.config.use_hybrid_encryption || !self.is_simulation() {
+            return self.decrypt_sealed(ciphertext, nonce, keypair);
+        }
+        
         // Simulation de déchiffrement (simple XOR avec la première partie de la clé privée)
-        let mut plaintext = Vec::with_capacity(ciphertext.len());
-        for (i, &byte) in ciphertext.iter().enumerate() {
-            let key_byte = if i < keypair.private_key.len() { keypair.private_key[i] } else { 0 };
-            plaintext.push(byte ^ key_byte);
+        Ok(xor_with_key(ciphertext, keypair.kem_secret()))
+    }
+    
+    /// Chiffrement scellé : AES-256-GCM sous une clé dérivée du secret post-quantique
+    /// encapsulé et, en mode hybride, du secret X25519
+    ///
+    /// En mode hybride, la clé symétrique reste sûre tant que l'un des deux échanges de clés
+    /// n'est pas compromis.
+    fn encrypt_sealed(&self, plaintext: &[u8], public_key: &[u8]) -> Result<EncryptionResult, String> {
+        let (header, key_material) = self.encapsulate_session(public_key)?;
+        let nonce = rand::random::<[u8; HYBRID_NONCE_SIZE]>();
+        let key = derive_session_key(&key_material, &nonce)?;
+        
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| format!("Clé de session invalide: {}", e))?;
+        let sealed = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| "Échec du chiffrement scellé".to_string())?;
+        
+        let mut ciphertext = header;
+        ciphertext.extend_from_slice(&sealed);
+        
+        Ok(EncryptionResult {
+            ciphertext,
+            nonce: nonce.to_vec(),
+            algorithm: self.config.encryption_algorithm,
+            is_hybrid: self.config.use_hybrid_encryption,
+        })
+    }
+    
+    /// Déchiffrement scellé : décapsulation de la clé de session puis ouverture AES-256-GCM
+    fn decrypt_sealed(&self, ciphertext: &[u8], nonce: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        if nonce.len() != HYBRID_NONCE_SIZE {
+            return Err(format!(
+                "Nonce invalide: {} octets attendus, {} reçus",
+                HYBRID_NONCE_SIZE,
+                nonce.len()
+            ));
+        }
+        if ciphertext.len() < self.session_header_size() {
+            return Err("Chiffré tronqué".to_string());
+        }
+        
+        let (header, sealed) = ciphertext.split_at(self.session_header_size());
+        let key_material = self.decapsulate_session(header, keypair)?;
+        
+        let key = derive_session_key(&key_material, nonce)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| format!("Clé de session invalide: {}", e))?;
+        cipher
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .map_err(|_| "Échec du déchiffrement: données altérées ou clé incorrecte".to_string())
+    }
+    
+    /// Chiffre un flux par blocs de taille fixe, sans charger les données en mémoire
+    ///
+    /// Format produit : préfixe de nonce, en-tête d'encapsulation de la clé de session, puis
+    /// une suite de blocs `[dernier: u8][longueur: u32][chiffré AES-256-GCM]`. Le nonce de
+    /// chaque bloc combine le préfixe, un compteur et l'indicateur de dernier bloc, et l'en-tête
+    /// est authentifié avec chaque bloc : toute réorganisation, troncature ou substitution de
+    /// blocs est détectée au déchiffrement.
+    ///
+    /// Renvoie le nombre d'octets clairs chiffrés.
+    pub fn encrypt_stream<R: Read, W: Write>(&self, mut reader: R, mut writer: W, public_key: &[u8]) -> Result<u64, String> {
+        let nonce_prefix = rand::random::<[u8; STREAM_NONCE_PREFIX_SIZE]>();
+        let (session_header, key_material) = self.encapsulate_session(public_key)?;
+        
+        let mut header = nonce_prefix.to_vec();
+        header.extend_from_slice(&session_header);
+        writer.write_all(&header)
+            .map_err(|e| format!("Échec de l'écriture de l'en-tête du flux: {}", e))?;
+        
+        let key = derive_session_key(&key_material, &nonce_prefix)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| format!("Clé de flux invalide: {}", e))?;
+        
+        let mut chunk = vec![0u8; STREAM_CHUNK_SIZE];
+        let mut counter: u32 = 0;
+        let mut total: u64 = 0;
+        
+        loop {
+            let length = read_chunk(&mut reader, &mut chunk)
+                .map_err(|e| format!("Échec de la lecture du flux: {}", e))?;
+            let last = length < STREAM_CHUNK_SIZE;
+            
+            let nonce = stream_nonce(&nonce_prefix, counter, last);
+            let sealed = cipher
+                .encrypt(Nonce::from_slice(&nonce), Payload { msg: &chunk[..length], aad: &header })
+                .map_err(|_| "Échec du chiffrement d'un bloc du flux".to_string())?;
+            
+            writer.write_all(&[last as u8])
+                .and_then(|_| writer.write_all(&(sealed.len() as u32).to_be_bytes()))
+                .and_then(|_| writer.write_all(&sealed))
+                .map_err(|e| format!("Échec de l'écriture du flux: {}", e))?;
+            
+            total += length as u64;
+            if last {
+                break;
+            }
+            counter = counter.checked_add(1).ok_or("Flux trop volumineux")?;
         }
         
-        Ok(plaintext)
+        writer.flush().map_err(|e| format!("Échec de l'écriture du flux: {}", e))?;
+        
+        Ok(total)
+    }
+    
+    /// Déchiffre un flux produit par `encrypt_stream`
+    ///
+    /// Les blocs sont écrits au fur et à mesure de leur authentification ; en cas d'erreur,
+    /// les données déjà écrites doivent être écartées. Renvoie le nombre d'octets clairs.
+    pub fn decrypt_stream<R: Read, W: Write>(&self, mut reader: R, mut writer: W, keypair: &PostQuantumKeyPair) -> Result<u64, String> {
+        if keypair.algorithm != self.config.encryption_algorithm {
+            return Err(format!(
+                "Algorithme de clé incompatible: attendu {:?}, reçu {:?}",
+                self.config.encryption_algorithm, keypair.algorithm
+            ));
+        }
+        
+        let mut header = vec![0u8; STREAM_NONCE_PREFIX_SIZE + self.session_header_size()];
+        reader.read_exact(&mut header)
+            .map_err(|e| format!("En-tête du flux illisible: {}", e))?;
+        
+        let (nonce_prefix, session_header) = header.split_at(STREAM_NONCE_PREFIX_SIZE);
+        let key_material = self.decapsulate_session(session_header, keypair)?;
+        let key = derive_session_key(&key_material, nonce_prefix)?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|e| format!("Clé de flux invalide: {}", e))?;
+        
+        let mut sealed = Vec::with_capacity(STREAM_CHUNK_SIZE + STREAM_TAG_SIZE);
+        let mut counter: u32 = 0;
+        let mut total: u64 = 0;
+        
+        loop {
+            let mut frame = [0u8; 5];
+            reader.read_exact(&mut frame)
+                .map_err(|_| "Flux chiffré tronqué: dernier bloc absent".to_string())?;
+            
+            let last = match frame[0] {
+                0 => false,
+                1 => true,
+                flag => return Err(format!("Indicateur de bloc invalide: {}", flag)),
+            };
+            let length = u32::from_be_bytes([frame[1], frame[2], frame[3], frame[4]]) as usize;
+            if !(STREAM_TAG_SIZE..=STREAM_CHUNK_SIZE + STREAM_TAG_SIZE).contains(&length) {
+                return Err(format!("Taille de bloc invalide: {}", length));
+            }
+            
+            sealed.resize(length, 0);
+            reader.read_exact(&mut sealed)
+                .map_err(|_| "Flux chiffré tronqué au milieu d'un bloc".to_string())?;
+            
+            let nonce = stream_nonce(nonce_prefix.try_into().unwrap(), counter, last);
+            let plaintext = cipher
+                .decrypt(Nonce::from_slice(&nonce), Payload { msg: &sealed, aad: &header })
+                .map_err(|_| format!("Échec de l'authentification du bloc {}", counter))?;
+            
+            writer.write_all(&plaintext)
+                .map_err(|e| format!("Échec de l'écriture du flux: {}", e))?;
+            total += plaintext.len() as u64;
+            
+            if last {
+                break;
+            }
+            counter = counter.checked_add(1).ok_or("Flux trop volumineux")?;
+        }
+        
+        // Aucune donnée ne doit suivre le dernier bloc
+        if reader.read(&mut [0u8; 1]).map_err(|e| format!("Échec de la lecture du flux: {}", e))? != 0 {
+            return Err("Données inattendues après le dernier bloc du flux".to_string());
+        }
+        
+        writer.flush().map_err(|e| format!("Échec de l'écriture du flux: {}", e))?;
+        
+        Ok(total)
+    }
+    
+    /// Taille de l'en-tête d'encapsulation de la clé de session
+    fn session_header_size(&self) -> usize {
+        let encapsulated_size = backend::encapsulated_size(self.config.encryption_algorithm);
+        if self.config.use_hybrid_encryption {
+            X25519_KEY_SIZE + encapsulated_size
+        } else {
+            encapsulated_size
+        }
+    }
+    
+    /// Établit un secret de session pour le destinataire
+    ///
+    /// Renvoie l'en-tête à transmettre (clé X25519 éphémère en mode hybride, puis secret
+    /// post-quantique encapsulé) et le matériel de clé combiné à dériver.
+    fn encapsulate_session(&self, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let mut header = Vec::with_capacity(self.session_header_size());
+        let mut classical_secret = Vec::new();
+        
+        let pq_public = if self.config.use_hybrid_encryption {
+            let (pq_public, classical_public) = split_hybrid_key(public_key)?;
+            
+            // Échange de clés classique avec une clé éphémère
+            let ephemeral_secret = StaticSecret::from(rand::random::<[u8; X25519_KEY_SIZE]>());
+            let classical_shared = ephemeral_secret.diffie_hellman(&PublicKey::from(classical_public));
+            header.extend_from_slice(PublicKey::from(&ephemeral_secret).as_bytes());
+            classical_secret.extend_from_slice(classical_shared.as_bytes());
+            
+            pq_public
+        } else {
+            public_key
+        };
+        
+        // Encapsulation du secret post-quantique
+        let algorithm = self.negotiate_algorithm(pq_public)?;
+        let (mut key_material, encapsulated) = backend::encapsulate(algorithm, pq_public)?;
+        header.extend_from_slice(&encapsulated);
+        key_material.extend_from_slice(&classical_secret);
+        
+        Ok((header, key_material))
+    }
+    
+    /// Vérifie que la clé publique post-quantique distante correspond à l'algorithme configuré
+    ///
+    /// La variante de Kyber est déduite de la taille de la clé : un intermédiaire qui
+    /// substituerait une clé d'une variante plus faible provoque une erreur de rétrogradation.
+    fn negotiate_algorithm(&self, remote_pq_public_key: &[u8]) -> Result<PostQuantumAlgorithm, String> {
+        let expected = self.config.encryption_algorithm;
+        match PostQuantumAlgorithm::kem_from_public_key_size(remote_pq_public_key.len()) {
+            Some(implied) if implied == expected => Ok(implied),
+            Some(implied) => Err(format!(
+                "{}: {:?} attendu, la clé publique distante implique {:?}",
+                ALGORITHM_DOWNGRADE, expected, implied
+            )),
+            None => Err(format!(
+                "Clé publique distante invalide pour {:?}: {} octets",
+                expected,
+                remote_pq_public_key.len()
+            )),
+        }
+    }
+    
+    /// Retrouve le matériel de clé de session à partir de l'en-tête d'encapsulation
+    fn decapsulate_session(&self, header: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        if header.len() != self.session_header_size() {
+            return Err("En-tête d'encapsulation invalide".to_string());
+        }
+        
+        if self.config.use_hybrid_encryption {
+            let (_, classical_private) = split_hybrid_key(&keypair.private_key)?;
+            let (ephemeral_public, encapsulated) = header.split_at(X25519_KEY_SIZE);
+            
+            let ephemeral_public: [u8; X25519_KEY_SIZE] = ephemeral_public.try_into().unwrap();
+            let classical_shared = StaticSecret::from(classical_private)
+                .diffie_hellman(&PublicKey::from(ephemeral_public));
+            
+            let mut key_material = backend::decapsulate(encapsulated, keypair)?;
+            key_material.extend_from_slice(classical_shared.as_bytes());
+            Ok(key_material)
+        } else {
+            backend::decapsulate(header, keypair)
+        }
     }
     
     /// Signe des données avec une clé privée
+    ///
+    /// La signature simulée est déterministe : elle est dérivée de l'empreinte des données
+    /// et de la clé publique issue de la clé privée.
     pub fn sign(&self, data: &[u8], keypair: &PostQuantumKeyPair) -> Result<SignatureResult, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle simule une signature
-        
         // Vérification de l'algorithme
+        if keypair.algorithm.is_kem() {
+            return Err(KEM_CANNOT_SIGN.to_string());
+        }
         if keypair.algorithm != self.config.signature_algorithm {
             return Err(format!(
                 "Algorithme de clé incompatible: attendu {:?}, reçu {:?}",
@@ -274,21 +861,7 @@ impl QuantumVault {
             ));
         }
         
-        // Taille de signature typique pour l'algorithme
-        let signature_size = match keypair.algorithm {
-            PostQuantumAlgorithm::Dilithium2 => 2420,
-            PostQuantumAlgorithm::Dilithium3 => 3293,
-            PostQuantumAlgorithm::Dilithium5 => 4595,
-            PostQuantumAlgorithm::SphincsSha2128f => 17088,
-            PostQuantumAlgorithm::SphincsSha2192f => 35664,
-            PostQuantumAlgorithm::SphincsSha2256f => 49856,
-            PostQuantumAlgorithm::Falcon512 => 666,
-            PostQuantumAlgorithm::Falcon1024 => 1280,
-            _ => return Err("Algorithme non supporté pour la signature".to_string()),
-        };
-        
-        // Génération d'une signature fictive
-        let signature = vec![0u8; signature_size];
+        let signature = backend::sign(data, keypair)?;
         
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -303,41 +876,72 @@ impl QuantumVault {
     }
     
     /// Vérifie une signature avec une clé publique
+    ///
+    /// Renvoie `false` si les données, la signature ou la clé publique ne correspondent pas.
     pub fn verify(&self, data: &[u8], signature: &[u8], public_key: &[u8], algorithm: PostQuantumAlgorithm) -> Result<bool, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle renvoie toujours vrai
+        if algorithm.is_kem() {
+            return Err(KEM_CANNOT_SIGN.to_string());
+        }
         
-        // Vérification de l'algorithme
-        if !matches!(algorithm, 
-            PostQuantumAlgorithm::Dilithium2 | 
-            PostQuantumAlgorithm::Dilithium3 | 
-            PostQuantumAlgorithm::Dilithium5 | 
-            PostQuantumAlgorithm::SphincsSha2128f | 
-            PostQuantumAlgorithm::SphincsSha2192f | 
-            PostQuantumAlgorithm::SphincsSha2256f |
-            PostQuantumAlgorithm::Falcon512 |
-            PostQuantumAlgorithm::Falcon1024
-        ) {
-            return Err("Algorithme non supporté pour la vérification de signature".to_string());
+        backend::verify(data, signature, public_key, algorithm)
+    }
+    
+    /// Produit une signature détachée d'un fichier sans le charger entièrement en mémoire
+    ///
+    /// Le fichier est haché (SHA-256) par blocs puis l'empreinte est signée. La signature
+    /// détachée contient l'empreinte suivie de la signature de celle-ci.
+    pub fn sign_file<P: AsRef<Path>>(&self, path: P, keypair: &PostQuantumKeyPair) -> Result<SignatureResult, String> {
+        let digest = hash_file(path.as_ref())?;
+        
+        let mut result = self.sign(&digest, keypair)?;
+        let mut signature = digest.to_vec();
+        signature.append(&mut result.signature);
+        result.signature = signature;
+        
+        Ok(result)
+    }
+    
+    /// Vérifie une signature détachée produite par `sign_file`
+    ///
+    /// Renvoie `false` si le fichier a été modifié depuis la signature.
+    pub fn verify_file<P: AsRef<Path>>(&self, path: P, signature: &[u8], public_key: &[u8], algorithm: PostQuantumAlgorithm) -> Result<bool, String> {
+        if signature.len() < FILE_DIGEST_SIZE {
+            return Err("Signature de fichier invalide: empreinte absente".to_string());
+        }
+        
+        let (signed_digest, digest_signature) = signature.split_at(FILE_DIGEST_SIZE);
+        let digest = hash_file(path.as_ref())?;
+        if !constant_time_eq(&digest, signed_digest) {
+            return Ok(false);
         }
         
-        // Simulation de vérification
-        Ok(true)
+        self.verify(&digest, digest_signature, public_key, algorithm)
     }
     
     /// Établit une clé partagée entre deux parties
-    pub fn key_exchange(&self, local_keypair: &PostQuantumKeyPair, remote_public_key: &[u8]) -> Result<Vec<u8>, String> {
+    ///
+    /// L'algorithme convenu est celui de la configuration ; la paire locale et la clé
+    /// publique distante doivent toutes deux lui correspondre.
+    pub fn key_exchange(&self, local_keypair: &PostQuantumKeyPair, remote_public_key: &[u8]) -> Result<KeyExchangeResult, String> {
         // Cette fonction sera implémentée dans les versions futures
         // Pour l'instant, elle simule un échange de clés
         
         // Vérification de l'algorithme
-        if !matches!(local_keypair.algorithm, 
-            PostQuantumAlgorithm::Kyber512 | 
-            PostQuantumAlgorithm::Kyber768 | 
-            PostQuantumAlgorithm::Kyber1024
-        ) {
+        if !local_keypair.algorithm.is_kem() {
             return Err("Algorithme non supporté pour l'échange de clés".to_string());
         }
+        if local_keypair.algorithm != self.config.encryption_algorithm {
+            return Err(format!(
+                "Algorithme de clé incompatible: attendu {:?}, reçu {:?}",
+                self.config.encryption_algorithm, local_keypair.algorithm
+            ));
+        }
+        let remote_pq_public_key = if self.config.use_hybrid_encryption {
+            split_hybrid_key(remote_public_key)?.0
+        } else {
+            remote_public_key
+        };
+        let algorithm = self.negotiate_algorithm(remote_pq_public_key)?;
         
         // Taille de clé partagée typique
         let shared_key_size = 32;
@@ -345,7 +949,320 @@ impl QuantumVault {
         // Génération d'une clé partagée fictive
         let shared_key = vec![0u8; shared_key_size];
         
-        Ok(shared_key)
+        Ok(KeyExchangeResult { shared_key, algorithm })
+    }
+}
+
+/// Applique un XOR octet par octet avec la clé (octets nuls au-delà de sa longueur)
+fn xor_with_key(data: &[u8], key: &[u8]) -> Vec<u8> {
+    data.iter()
+        .enumerate()
+        .map(|(i, &byte)| byte ^ key.get(i).copied().unwrap_or(0))
+        .collect()
+}
+
+/// Médiane (haute pour un nombre pair de valeurs) d'une série de mesures non vide
+fn median(samples: &mut [f64]) -> f64 {
+    samples.sort_by(f64::total_cmp);
+    samples[samples.len() / 2]
+}
+
+/// Remplit le tampon depuis le lecteur ; renvoie moins que sa taille uniquement en fin de flux
+fn read_chunk<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Compare deux tranches d'octets en temps constant vis-à-vis de leur contenu
+///
+/// Seule la longueur (publique) peut provoquer un retour anticipé ; à longueur égale,
+/// tous les octets sont examinés quel que soit l'emplacement de la première différence.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    
+    let difference = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(difference) == 0
+}
+
+/// Horodatage UNIX courant (en secondes)
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Calcule l'empreinte SHA-256 d'un fichier en le lisant par blocs
+fn hash_file(path: &Path) -> Result<[u8; FILE_DIGEST_SIZE], String> {
+    let mut file = File::open(path)
+        .map_err(|e| format!("Impossible d'ouvrir {}: {}", path.display(), e))?;
+    
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; STREAM_CHUNK_SIZE];
+    loop {
+        let read = read_chunk(&mut file, &mut buffer)
+            .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+        hasher.update(&buffer[..read]);
+        if read < buffer.len() {
+            break;
+        }
+    }
+    
+    Ok(hasher.finalize().into())
+}
+
+/// Construit le nonce d'un bloc de flux : préfixe, compteur et indicateur de dernier bloc
+fn stream_nonce(prefix: &[u8; STREAM_NONCE_PREFIX_SIZE], counter: u32, last: bool) -> [u8; HYBRID_NONCE_SIZE] {
+    let mut nonce = [0u8; HYBRID_NONCE_SIZE];
+    nonce[..STREAM_NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[STREAM_NONCE_PREFIX_SIZE..HYBRID_NONCE_SIZE - 1].copy_from_slice(&counter.to_be_bytes());
+    nonce[HYBRID_NONCE_SIZE - 1] = last as u8;
+    nonce
+}
+
+/// Sépare une clé hybride en partie post-quantique et clé X25519 finale
+fn split_hybrid_key(key: &[u8]) -> Result<(&[u8], [u8; X25519_KEY_SIZE]), String> {
+    if key.len() <= X25519_KEY_SIZE {
+        return Err("Clé hybride invalide: partie classique X25519 absente".to_string());
+    }
+    
+    let (pq_key, classical_key) = key.split_at(key.len() - X25519_KEY_SIZE);
+    Ok((pq_key, classical_key.try_into().unwrap()))
+}
+
+/// Dérive la clé symétrique de session du matériel de clé combiné (HKDF-SHA256)
+fn derive_session_key(key_material: &[u8], salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(salt), key_material)
+        .expand(HYBRID_KDF_INFO, &mut key)
+        .map_err(|e| format!("Échec de la dérivation de la clé hybride: {}", e))?;
+    
+    Ok(key)
+}
+
+/// Primitives post-quantiques simulées (mode par défaut)
+///
+/// Les clés sont des octets aléatoires, l'encapsulation un XOR avec la clé publique et la
+/// signature une empreinte SHA-256 étendue : aucune sécurité post-quantique réelle.
+#[cfg(not(feature = "real-pqcrypto"))]
+mod backend {
+    use rand::Rng;
+    
+    use super::*;
+    
+    /// Les primitives sont simulées
+    pub const IS_SIMULATION: bool = true;
+    /// Taille du secret partagé encapsulé (en octets)
+    const PQ_SHARED_SECRET_SIZE: usize = 32;
+    
+    /// Génère une paire (clé publique, clé privée) fictive
+    ///
+    /// Les clés sont tirées d'octets aléatoires indépendants :
+    /// - signatures : clé publique dérivée de la clé privée
+    /// - chiffrement : clé privée commençant par la clé publique (comme pour Kyber)
+    pub fn generate_keypair(algorithm: PostQuantumAlgorithm) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let (public_key_size, private_key_size) = algorithm.key_sizes();
+        
+        if algorithm.signature_size().is_some() {
+            let private_key = random_bytes(private_key_size);
+            Ok((derive_signature_public_key(&private_key, public_key_size), private_key))
+        } else {
+            let public_key = random_bytes(public_key_size);
+            let mut private_key = public_key.clone();
+            private_key.extend(random_bytes(private_key_size - public_key_size));
+            Ok((public_key, private_key))
+        }
+    }
+    
+    /// Taille du secret encapsulé
+    pub fn encapsulated_size(_algorithm: PostQuantumAlgorithm) -> usize {
+        PQ_SHARED_SECRET_SIZE
+    }
+    
+    /// Tire un secret partagé et l'encapsule pour la clé publique ; renvoie (secret, encapsulé)
+    pub fn encapsulate(_algorithm: PostQuantumAlgorithm, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let secret = rand::random::<[u8; PQ_SHARED_SECRET_SIZE]>();
+        let encapsulated = xor_with_key(&secret, public_key);
+        Ok((secret.to_vec(), encapsulated))
+    }
+    
+    /// Retrouve le secret partagé encapsulé
+    pub fn decapsulate(encapsulated: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        Ok(xor_with_key(encapsulated, keypair.kem_secret()))
+    }
+    
+    /// Signe l'empreinte des données
+    pub fn sign(data: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        let signature_size = keypair.algorithm.signature_size()
+            .ok_or("Algorithme non supporté pour la signature")?;
+        
+        let public_key = derive_signature_public_key(&keypair.private_key, keypair.public_key.len());
+        Ok(compute_signature(data, &public_key, signature_size))
+    }
+    
+    /// Vérifie une signature en comparant en temps constant la signature recalculée
+    pub fn verify(data: &[u8], signature: &[u8], public_key: &[u8], algorithm: PostQuantumAlgorithm) -> Result<bool, String> {
+        let signature_size = algorithm.signature_size()
+            .ok_or("Algorithme non supporté pour la vérification de signature")?;
+        
+        let expected = compute_signature(data, public_key, signature_size);
+        Ok(constant_time_eq(&expected, signature))
+    }
+    
+    /// Tire des octets aléatoires du générateur cryptographique du thread courant
+    fn random_bytes(length: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; length];
+        rand::thread_rng().fill(bytes.as_mut_slice());
+        bytes
+    }
+    
+    /// Étend une empreinte SHA-256 en mode compteur jusqu'à la longueur demandée
+    fn expand_digest(label: &[u8], inputs: &[&[u8]], length: usize) -> Vec<u8> {
+        let mut output = Vec::with_capacity(length);
+        let mut counter: u32 = 0;
+        
+        while output.len() < length {
+            let mut hasher = Sha256::new();
+            hasher.update(label);
+            hasher.update(counter.to_be_bytes());
+            for input in inputs {
+                hasher.update(input);
+            }
+            output.extend_from_slice(&hasher.finalize());
+            counter += 1;
+        }
+        
+        output.truncate(length);
+        output
+    }
+    
+    /// Dérive la clé publique de signature (simulée) d'une clé privée
+    fn derive_signature_public_key(private_key: &[u8], length: usize) -> Vec<u8> {
+        expand_digest(b"ICARUS QuantumVault cle publique", &[private_key], length)
+    }
+    
+    /// Calcule la signature (simulée) des données pour une clé publique
+    fn compute_signature(data: &[u8], public_key: &[u8], length: usize) -> Vec<u8> {
+        let data_digest = Sha256::digest(data);
+        let key_digest = Sha256::digest(public_key);
+        expand_digest(b"ICARUS QuantumVault signature", &[&key_digest, &data_digest], length)
+    }
+}
+
+/// Primitives post-quantiques réelles (implémentations PQClean des crates `pqcrypto-*`)
+#[cfg(feature = "real-pqcrypto")]
+mod backend {
+    use pqcrypto_traits::kem::{Ciphertext as _, PublicKey as _, SecretKey as _, SharedSecret as _};
+    use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+    
+    use super::{PostQuantumAlgorithm, PostQuantumKeyPair};
+    
+    /// Les primitives sont réelles
+    pub const IS_SIMULATION: bool = false;
+    
+    /// Évalue `$body` avec `$scheme` désignant le module Kyber de l'algorithme
+    macro_rules! with_kem {
+        ($algorithm:expr, $scheme:ident => $body:expr) => {
+            match $algorithm {
+                PostQuantumAlgorithm::Kyber512 => { use pqcrypto_kyber::kyber512 as $scheme; $body }
+                PostQuantumAlgorithm::Kyber768 => { use pqcrypto_kyber::kyber768 as $scheme; $body }
+                PostQuantumAlgorithm::Kyber1024 => { use pqcrypto_kyber::kyber1024 as $scheme; $body }
+                algorithm => Err(format!("{:?} n'est pas un mécanisme d'encapsulation de clés", algorithm)),
+            }
+        };
+    }
+    
+    /// Évalue `$body` avec `$scheme` désignant le module de signature de l'algorithme
+    macro_rules! with_signature {
+        ($algorithm:expr, $error:expr, $scheme:ident => $body:expr) => {
+            match $algorithm {
+                PostQuantumAlgorithm::Dilithium2 => { use pqcrypto_dilithium::dilithium2 as $scheme; $body }
+                PostQuantumAlgorithm::Dilithium3 => { use pqcrypto_dilithium::dilithium3 as $scheme; $body }
+                PostQuantumAlgorithm::Dilithium5 => { use pqcrypto_dilithium::dilithium5 as $scheme; $body }
+                PostQuantumAlgorithm::SphincsSha2128f => { use pqcrypto_sphincsplus::sphincssha2128fsimple as $scheme; $body }
+                PostQuantumAlgorithm::SphincsSha2192f => { use pqcrypto_sphincsplus::sphincssha2192fsimple as $scheme; $body }
+                PostQuantumAlgorithm::SphincsSha2256f => { use pqcrypto_sphincsplus::sphincssha2256fsimple as $scheme; $body }
+                PostQuantumAlgorithm::Falcon512 => { use pqcrypto_falcon::falcon512 as $scheme; $body }
+                PostQuantumAlgorithm::Falcon1024 => { use pqcrypto_falcon::falcon1024 as $scheme; $body }
+                PostQuantumAlgorithm::Kyber512
+                | PostQuantumAlgorithm::Kyber768
+                | PostQuantumAlgorithm::Kyber1024 => Err($error.to_string()),
+            }
+        };
+    }
+    
+    /// Génère une paire (clé publique, clé privée)
+    pub fn generate_keypair(algorithm: PostQuantumAlgorithm) -> Result<(Vec<u8>, Vec<u8>), String> {
+        if algorithm.signature_size().is_some() {
+            with_signature!(algorithm, "Algorithme non supporté pour la signature", scheme => {
+                let (public_key, secret_key) = scheme::keypair();
+                Ok((public_key.as_bytes().to_vec(), secret_key.as_bytes().to_vec()))
+            })
+        } else {
+            with_kem!(algorithm, scheme => {
+                let (public_key, secret_key) = scheme::keypair();
+                Ok((public_key.as_bytes().to_vec(), secret_key.as_bytes().to_vec()))
+            })
+        }
+    }
+    
+    /// Taille du chiffré d'encapsulation (0 pour un algorithme qui n'est pas un KEM)
+    pub fn encapsulated_size(algorithm: PostQuantumAlgorithm) -> usize {
+        with_kem!(algorithm, scheme => Ok(scheme::ciphertext_bytes())).unwrap_or(0)
+    }
+    
+    /// Encapsule un secret partagé pour la clé publique ; renvoie (secret, encapsulé)
+    pub fn encapsulate(algorithm: PostQuantumAlgorithm, public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        with_kem!(algorithm, scheme => {
+            let public_key = scheme::PublicKey::from_bytes(public_key)
+                .map_err(|_| format!("Clé publique {:?} invalide", algorithm))?;
+            let (secret, encapsulated) = scheme::encapsulate(&public_key);
+            Ok((secret.as_bytes().to_vec(), encapsulated.as_bytes().to_vec()))
+        })
+    }
+    
+    /// Décapsule le secret partagé avec la partie post-quantique de la clé privée
+    pub fn decapsulate(encapsulated: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        let algorithm = keypair.algorithm;
+        with_kem!(algorithm, scheme => {
+            // Une clé privée hybride est suivie de la clé X25519
+            let secret_key = keypair.private_key.get(..scheme::secret_key_bytes())
+                .and_then(|bytes| scheme::SecretKey::from_bytes(bytes).ok())
+                .ok_or(format!("Clé privée {:?} invalide", algorithm))?;
+            let encapsulated = scheme::Ciphertext::from_bytes(encapsulated)
+                .map_err(|_| "En-tête d'encapsulation invalide".to_string())?;
+            Ok(scheme::decapsulate(&encapsulated, &secret_key).as_bytes().to_vec())
+        })
+    }
+    
+    /// Produit une signature détachée
+    pub fn sign(data: &[u8], keypair: &PostQuantumKeyPair) -> Result<Vec<u8>, String> {
+        let algorithm = keypair.algorithm;
+        with_signature!(algorithm, "Algorithme non supporté pour la signature", scheme => {
+            let secret_key = scheme::SecretKey::from_bytes(&keypair.private_key)
+                .map_err(|_| format!("Clé privée {:?} invalide", algorithm))?;
+            Ok(scheme::detached_sign(data, &secret_key).as_bytes().to_vec())
+        })
+    }
+    
+    /// Vérifie une signature détachée ; une signature mal formée est simplement invalide
+    pub fn verify(data: &[u8], signature: &[u8], public_key: &[u8], algorithm: PostQuantumAlgorithm) -> Result<bool, String> {
+        with_signature!(algorithm, "Algorithme non supporté pour la vérification de signature", scheme => {
+            let public_key = scheme::PublicKey::from_bytes(public_key)
+                .map_err(|_| format!("Clé publique {:?} invalide", algorithm))?;
+            Ok(scheme::DetachedSignature::from_bytes(signature)
+                .is_ok_and(|signature| scheme::verify_detached_signature(&signature, data, &public_key).is_ok()))
+        })
     }
 }
 
@@ -390,7 +1307,7 @@ mod tests {
         
         let keypair = vault.generate_signature_keypair().unwrap();
         
-        let data = b"Données à signer pour le test";
+        let data = "Données à signer pour le test".as_bytes();
         
         let signature_result = vault.sign(data, &keypair).unwrap();
         assert!(!signature_result.signature.is_empty());
@@ -398,4 +1315,286 @@ mod tests {
         let verification_result = vault.verify(data, &signature_result.signature, &keypair.public_key, keypair.algorithm).unwrap();
         assert!(verification_result);
     }
+    
+    #[test]
+    fn test_hybrid_encryption_toggle() {
+        let plaintext = b"Message secret pour le test";
+        
+        for use_hybrid_encryption in [true, false] {
+            let config = QuantumVaultConfig { use_hybrid_encryption, ..Default::default() };
+            let vault = QuantumVault::new(config);
+            let keypair = vault.generate_encryption_keypair().unwrap();
+            
+            let encryption_result = vault.encrypt(plaintext, &keypair.public_key).unwrap();
+            assert_eq!(encryption_result.is_hybrid, use_hybrid_encryption);
+            
+            if use_hybrid_encryption {
+                // La couche AES-GCM masque le message clair
+                assert!(!encryption_result.ciphertext.windows(plaintext.len()).any(|window| window == plaintext));
+                
+                let mut tampered = encryption_result.ciphertext.clone();
+                *tampered.last_mut().unwrap() ^= 0x01;
+                assert!(vault.decrypt(&tampered, &encryption_result.nonce, &keypair).is_err());
+            }
+            
+            let decrypted = vault.decrypt(&encryption_result.ciphertext, &encryption_result.nonce, &keypair).unwrap();
+            assert_eq!(decrypted, plaintext);
+        }
+    }
+    
+    /// Écrivain mémorisant la plus grande écriture reçue
+    struct TrackingWriter {
+        data: Vec<u8>,
+        max_write: usize,
+    }
+    
+    impl Write for TrackingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.max_write = self.max_write.max(buf.len());
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_stream_encrypt_decrypt() {
+        let config = QuantumVaultConfig::default();
+        let vault = QuantumVault::new(config);
+        let keypair = vault.generate_encryption_keypair().unwrap();
+        
+        let data: Vec<u8> = (0..10 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        
+        let mut encrypted = TrackingWriter { data: Vec::new(), max_write: 0 };
+        let written = vault.encrypt_stream(io::Cursor::new(&data), &mut encrypted, &keypair.public_key).unwrap();
+        assert_eq!(written, data.len() as u64);
+        // Les données sont traitées bloc par bloc
+        assert!(encrypted.max_write <= STREAM_CHUNK_SIZE + STREAM_TAG_SIZE);
+        
+        let mut decrypted = TrackingWriter { data: Vec::new(), max_write: 0 };
+        let read = vault.decrypt_stream(io::Cursor::new(&encrypted.data), &mut decrypted, &keypair).unwrap();
+        assert_eq!(read, data.len() as u64);
+        assert!(decrypted.max_write <= STREAM_CHUNK_SIZE);
+        assert!(decrypted.data == data);
+        
+        // Un flux tronqué est rejeté
+        let truncated = &encrypted.data[..encrypted.data.len() - 1];
+        assert!(vault.decrypt_stream(truncated, io::sink(), &keypair).is_err());
+    }
+    
+    #[test]
+    fn test_sign_verify_file() {
+        let config = QuantumVaultConfig::default();
+        let vault = QuantumVault::new(config);
+        let keypair = vault.generate_signature_keypair().unwrap();
+        
+        let path = std::env::temp_dir().join(format!("icarus-{}-model.bin", uuid::Uuid::new_v4()));
+        std::fs::write(&path, vec![42u8; 200 * 1024]).unwrap();
+        
+        let signature_result = vault.sign_file(&path, &keypair).unwrap();
+        assert!(vault.verify_file(&path, &signature_result.signature, &keypair.public_key, keypair.algorithm).unwrap());
+        
+        // Un octet ajouté après la signature invalide la vérification
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[0]).unwrap();
+        drop(file);
+        
+        let verified = vault.verify_file(&path, &signature_result.signature, &keypair.public_key, keypair.algorithm).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!verified);
+    }
+    
+    #[test]
+    fn test_verify_rejects_tampered_data_and_wrong_key() {
+        let config = QuantumVaultConfig::default();
+        let vault = QuantumVault::new(config);
+        
+        let keypair = vault.generate_signature_keypair().unwrap();
+        let other_keypair = vault.generate_signature_keypair().unwrap();
+        
+        let data = b"Rapport d'incident";
+        let signature_result = vault.sign(data, &keypair).unwrap();
+        
+        // La signature est déterministe
+        assert_eq!(vault.sign(data, &keypair).unwrap().signature, signature_result.signature);
+        
+        assert!(vault.verify(data, &signature_result.signature, &keypair.public_key, keypair.algorithm).unwrap());
+        assert!(!vault.verify(b"Rapport d'incident modifie", &signature_result.signature, &keypair.public_key, keypair.algorithm).unwrap());
+        assert!(!vault.verify(data, &signature_result.signature, &other_keypair.public_key, keypair.algorithm).unwrap());
+    }
+    
+    #[test]
+    fn test_batch_keypair_generation() {
+        let config = QuantumVaultConfig { use_hybrid_encryption: false, ..Default::default() };
+        let vault = QuantumVault::new(config);
+        
+        let keypairs = vault.generate_encryption_keypairs(100).unwrap();
+        assert_eq!(keypairs.len(), 100);
+        
+        let (public_key_size, _) = PostQuantumAlgorithm::Kyber1024.key_sizes();
+        assert!(keypairs.iter().all(|keypair| keypair.public_key.len() == public_key_size));
+        
+        let distinct: std::collections::HashSet<&Vec<u8>> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        assert_eq!(distinct.len(), 100);
+        
+        let signature_keypairs = vault.generate_signature_keypairs(10).unwrap();
+        assert_eq!(signature_keypairs.len(), 10);
+        assert!(signature_keypairs.iter().all(|keypair| keypair.algorithm == PostQuantumAlgorithm::Dilithium5));
+    }
+    
+    #[test]
+    fn test_key_manager_named_keys() {
+        let config = QuantumVaultConfig::default();
+        let mut vault = QuantumVault::new(config);
+        
+        let keypair = vault.generate_encryption_keypair().unwrap();
+        let mut expired = vault.generate_encryption_keypair().unwrap();
+        expired.expires_at = 0;
+        
+        let mut key_manager = KeyManager::new();
+        key_manager.insert("session", keypair);
+        key_manager.insert("ancienne", expired);
+        assert_eq!(key_manager.len(), 2);
+        vault.set_key_manager(key_manager);
+        
+        let encryption_result = vault.encrypt_with("session", b"Message secret").unwrap();
+        assert!(!encryption_result.ciphertext.is_empty());
+        
+        // La clé expirée est évincée lors de l'accès
+        let error = vault.encrypt_with("ancienne", b"Message secret").unwrap_err();
+        assert!(error.contains("ancienne"));
+        assert_eq!(vault.key_manager.as_ref().unwrap().lock().unwrap().len(), 1);
+        
+        assert!(vault.sign_with("inconnue", b"Message").is_err());
+    }
+    
+    #[test]
+    fn test_round_trip_all_algorithms() {
+        let vault = QuantumVault::new(QuantumVaultConfig::default());
+        assert_eq!(vault.is_simulation(), !cfg!(feature = "real-pqcrypto"));
+        
+        let plaintext = b"Message secret pour le test";
+        for encryption_algorithm in [
+            PostQuantumAlgorithm::Kyber512,
+            PostQuantumAlgorithm::Kyber768,
+            PostQuantumAlgorithm::Kyber1024,
+        ] {
+            for use_hybrid_encryption in [true, false] {
+                let config = QuantumVaultConfig { encryption_algorithm, use_hybrid_encryption, ..Default::default() };
+                let vault = QuantumVault::new(config);
+                let keypair = vault.generate_encryption_keypair().unwrap();
+                let other_keypair = vault.generate_encryption_keypair().unwrap();
+                
+                let encryption_result = vault.encrypt(plaintext, &keypair.public_key).unwrap();
+                let decrypted = vault.decrypt(&encryption_result.ciphertext, &encryption_result.nonce, &keypair).unwrap();
+                assert_eq!(decrypted, plaintext);
+                
+                // Avec les primitives réelles, une autre clé privée ne déchiffre jamais
+                if !vault.is_simulation() || use_hybrid_encryption {
+                    assert!(vault.decrypt(&encryption_result.ciphertext, &encryption_result.nonce, &other_keypair).is_err());
+                }
+            }
+        }
+        
+        let data = b"Rapport d'incident";
+        for signature_algorithm in [
+            PostQuantumAlgorithm::Dilithium2,
+            PostQuantumAlgorithm::Dilithium3,
+            PostQuantumAlgorithm::Dilithium5,
+            PostQuantumAlgorithm::SphincsSha2128f,
+            PostQuantumAlgorithm::SphincsSha2192f,
+            PostQuantumAlgorithm::SphincsSha2256f,
+            PostQuantumAlgorithm::Falcon512,
+            PostQuantumAlgorithm::Falcon1024,
+        ] {
+            let config = QuantumVaultConfig { signature_algorithm, ..Default::default() };
+            let vault = QuantumVault::new(config);
+            let keypair = vault.generate_signature_keypair().unwrap();
+            
+            let signature_result = vault.sign(data, &keypair).unwrap();
+            assert!(signature_result.signature.len() <= signature_algorithm.signature_size().unwrap());
+            assert!(vault.verify(data, &signature_result.signature, &keypair.public_key, signature_algorithm).unwrap());
+            assert!(!vault.verify(b"Rapport modifie", &signature_result.signature, &keypair.public_key, signature_algorithm).unwrap());
+        }
+    }
+    
+    #[test]
+    fn test_kyber_keypair_cannot_sign() {
+        let config = QuantumVaultConfig { use_hybrid_encryption: false, ..Default::default() };
+        let vault = QuantumVault::new(config);
+        
+        let keypair = PostQuantumKeyPair::new(PostQuantumAlgorithm::Kyber1024).unwrap();
+        assert_eq!(vault.sign(b"Message", &keypair).unwrap_err(), "Kyber is a KEM and cannot sign");
+        assert_eq!(
+            vault.verify(b"Message", &[0u8; 64], &keypair.public_key, PostQuantumAlgorithm::Kyber1024).unwrap_err(),
+            "Kyber is a KEM and cannot sign"
+        );
+        
+        // Les autres incompatibilités conservent leur propre message
+        let falcon_keypair = PostQuantumKeyPair::new(PostQuantumAlgorithm::Falcon512).unwrap();
+        let error = vault.sign(b"Message", &falcon_keypair).unwrap_err();
+        assert!(error.contains("incompatible"));
+    }
+    
+    #[test]
+    fn test_benchmark_kyber_variants() {
+        let config = QuantumVaultConfig { use_hybrid_encryption: false, ..Default::default() };
+        let vault = QuantumVault::new(config);
+        
+        let results: Vec<AlgoBenchResult> = [
+            PostQuantumAlgorithm::Kyber512,
+            PostQuantumAlgorithm::Kyber768,
+            PostQuantumAlgorithm::Kyber1024,
+        ]
+        .into_iter()
+        .map(|algorithm| vault.benchmark_algorithm(algorithm, 200).unwrap())
+        .collect();
+        for result in &results {
+            assert!(result.keygen_us > 0.0 && result.encrypt_us > 0.0 && result.decrypt_us > 0.0, "{:?}", result);
+        }
+        
+        // Comparaison relative, seulement avec les implémentations réelles : en simulation la
+        // génération se limite à des octets aléatoires et les écarts ne sont que du bruit.
+        // La marge absorbe la variabilité des tests exécutés en parallèle.
+        if cfg!(feature = "real-pqcrypto") {
+            let keygen: Vec<f64> = results.iter().map(|result| result.keygen_us).collect();
+            assert!(keygen[0] * 1.2 < keygen[2], "{:?}", keygen);
+        }
+        
+        assert!(vault.benchmark_algorithm(PostQuantumAlgorithm::Dilithium5, 10).is_err());
+        assert!(vault.benchmark_algorithm(PostQuantumAlgorithm::Kyber512, 0).is_err());
+    }
+    
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"signature", b"signature"));
+        assert!(!constant_time_eq(b"signature", b"signatures"));
+        assert!(!constant_time_eq(b"signature", b"signaturE"));
+        assert!(!constant_time_eq(b"Signature", b"signature"));
+    }
+    
+    #[test]
+    fn test_key_exchange_rejects_downgraded_public_key() {
+        let config = QuantumVaultConfig { encryption_algorithm: PostQuantumAlgorithm::Kyber1024, ..Default::default() };
+        let vault = QuantumVault::new(config);
+        let local_keypair = vault.generate_encryption_keypair().unwrap();
+        
+        // Clé Kyber512 substituée par un intermédiaire
+        let weak_keypair = PostQuantumKeyPair::new_hybrid(PostQuantumAlgorithm::Kyber512).unwrap();
+        let error = vault.key_exchange(&local_keypair, &weak_keypair.public_key).unwrap_err();
+        assert!(error.starts_with(ALGORITHM_DOWNGRADE), "{}", error);
+        assert!(error.contains("Kyber512"));
+        let error = vault.encrypt(b"Message secret", &weak_keypair.public_key).unwrap_err();
+        assert!(error.starts_with(ALGORITHM_DOWNGRADE), "{}", error);
+        
+        let remote_keypair = vault.generate_encryption_keypair().unwrap();
+        let exchange = vault.key_exchange(&local_keypair, &remote_keypair.public_key).unwrap();
+        assert_eq!(exchange.algorithm, PostQuantumAlgorithm::Kyber1024);
+        assert!(!exchange.shared_key.is_empty());
+        assert!(vault.key_exchange(&local_keypair, &[0u8; 100]).is_err());
+    }
 }
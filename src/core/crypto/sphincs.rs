@@ -8,8 +8,20 @@
 //! - SPHINCS+128s: NIST Level 1 (small signatures)
 //! - SPHINCS+192s: NIST Level 3 (small signatures)
 //! - SPHINCS+256s: NIST Level 5 (small signatures)
+//!
+//! ## Constant-time verification
+//! Verification must compare recomputed roots and signature components with
+//! [`quantum_vault::constant_time_eq`](super::quantum_vault::constant_time_eq),
+//! never with `==`, to avoid leaking timing information.
+//!
+//! ## Simplified scheme
+//! Until the hypertree is implemented, a signature starts with an `n`-byte tag
+//! `H(public key || H(message))` followed by filler up to the parameter set's
+//! signature size. Verification recomputes the tag, so a signature only verifies
+//! for the message and key it was produced for.
 
 use super::*;
+use super::quantum_vault::constant_time_eq;
 
 /// SPHINCS+ parameter sets
 #[derive(Debug, Clone)]
@@ -95,11 +107,67 @@ impl SphincsParams {
             security_level: 5,
         }
     }
+    
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        // Simplified hash function (would use SHAKE-256 in production)
+        let mut hash = vec![0u8; self.n];
+        let mut state = 0x123456789abcdefu64;
+        
+        for &byte in input {
+            state = state.wrapping_mul(31).wrapping_add(byte as u64);
+        }
+        
+        for byte in hash.iter_mut() {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            *byte = (state >> 24) as u8;
+        }
+        
+        hash
+    }
+    
+    /// Public key (hypertree root followed by the public seed) matching a private key
+    /// made of the secret seed followed by the public seed
+    fn derive_public_key(&self, private_key: &[u8]) -> Vec<u8> {
+        let pk_seed = &private_key[self.n..2 * self.n];
+        
+        // Generate root of hypertree (simplified)
+        let mut public_key = self.hash(&private_key[..2 * self.n]);
+        public_key.extend_from_slice(pk_seed);
+        public_key
+    }
+    
+    /// Tag binding a signature to the message and the signer's public key
+    fn signature_tag(&self, public_key: &[u8], message: &[u8]) -> Vec<u8> {
+        let message_hash = self.hash(message);
+        self.hash(&[public_key, message_hash.as_slice()].concat())
+    }
+    
+    /// Signature data: tag followed by `filler`, truncated or padded to the signature size
+    fn sign(&self, private_key: &[u8], message: &[u8], filler: Vec<u8>) -> Result<Vec<u8>, CryptoError> {
+        if private_key.len() < 2 * self.n {
+            return Err(CryptoError::InvalidKey("Private key too short".to_string()));
+        }
+        
+        let mut data = self.signature_tag(&self.derive_public_key(private_key), message);
+        data.extend(filler);
+        data.resize(self.signature_size, 0);
+        Ok(data)
+    }
+    
+    /// Recomputes the tag and compares it in constant time
+    fn verify(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        if public_key.len() != self.public_key_size || signature.len() != self.signature_size {
+            return false;
+        }
+        
+        let expected = self.signature_tag(public_key, message);
+        constant_time_eq(&signature[..self.n], &expected)
+    }
 }
 
 impl SphincsPlus128s {
     pub fn new() -> Self {
-        println!("🌲 Initializing SPHINCS+128s (Hash-based signatures)");
+        log::debug!("Initializing SPHINCS+128s (hash-based signatures)");
         Self {
             params: SphincsParams::sphincs_128s(),
             rng_state: std::sync::Mutex::new(42),
@@ -117,43 +185,17 @@ impl SphincsPlus128s {
         
         bytes
     }
-    
-    fn hash_function(&self, input: &[u8]) -> Vec<u8> {
-        // Simplified hash function (would use SHAKE-256 in production)
-        let mut hash = vec![0u8; self.params.n];
-        let mut state = 0x123456789abcdefu64;
-        
-        for &byte in input {
-            state = state.wrapping_mul(31).wrapping_add(byte as u64);
-        }
-        
-        for i in 0..self.params.n {
-            state = state.wrapping_mul(1103515245).wrapping_add(12345);
-            hash[i] = (state >> 24) as u8;
-        }
-        
-        hash
-    }
 }
 
 impl DigitalSignature for SphincsPlus128s {
     fn generate_keypair(&self) -> Result<(SigningKey, VerifyingKey), CryptoError> {
-        println!("🔑 Generating SPHINCS+128s keypair...");
-        
-        // Generate random seed
-        let seed = self.generate_random_bytes(self.params.n);
-        let pk_seed = self.generate_random_bytes(self.params.n);
-        
-        // Generate root of hypertree (simplified)
-        let root = self.hash_function(&[seed.clone(), pk_seed.clone()].concat());
+        log::debug!("Generating SPHINCS+128s keypair");
         
-        // Private key contains seed and public key seed
-        let mut private_key_data = seed;
-        private_key_data.extend_from_slice(&pk_seed);
+        // Private key contains random seed and public key seed
+        let private_key_data = self.generate_random_bytes(2 * self.params.n);
         
         // Public key contains root and public key seed
-        let mut public_key_data = root;
-        public_key_data.extend_from_slice(&pk_seed);
+        let public_key_data = self.params.derive_public_key(&private_key_data);
         
         Ok((
             SigningKey {
@@ -174,13 +216,11 @@ impl DigitalSignature for SphincsPlus128s {
             return Err(CryptoError::InvalidKey("Wrong algorithm for SPHINCS+128s".to_string()));
         }
         
-        println!("✍️ Signing with SPHINCS+128s (hash-based)...");
-        
-        // Hash message
-        let message_hash = self.hash_function(message);
+        log::debug!("Signing with SPHINCS+128s");
         
         // Generate signature (simplified implementation)
-        let signature_data = self.generate_random_bytes(self.params.signature_size);
+        let filler = self.generate_random_bytes(self.params.signature_size - self.params.n);
+        let signature_data = self.params.sign(&signing_key.key_data, message, filler)?;
         
         Ok(Signature {
             data: signature_data,
@@ -194,12 +234,10 @@ impl DigitalSignature for SphincsPlus128s {
             return Err(CryptoError::InvalidKey("Algorithm mismatch".to_string()));
         }
         
-        println!("✅ Verifying with SPHINCS+128s...");
-        
-        let _message_hash = self.hash_function(message);
+        log::debug!("Verifying with SPHINCS+128s");
         
-        // Simplified verification - always returns true for demo
-        Ok(true)
+        // Simplified verification: recompute the tag instead of the hypertree root
+        Ok(self.params.verify(&verifying_key.key_data, message, &signature.data))
     }
     
     fn algorithm_name(&self) -> &str {
@@ -213,7 +251,7 @@ impl DigitalSignature for SphincsPlus128s {
 
 impl SphincsPlus192s {
     pub fn new() -> Self {
-        println!("🌲 Initializing SPHINCS+192s (NIST Level 3)");
+        log::debug!("Initializing SPHINCS+192s (NIST level 3)");
         Self {
             params: SphincsParams::sphincs_192s(),
             rng_state: std::sync::Mutex::new(12345),
@@ -223,32 +261,43 @@ impl SphincsPlus192s {
 
 impl DigitalSignature for SphincsPlus192s {
     fn generate_keypair(&self) -> Result<(SigningKey, VerifyingKey), CryptoError> {
-        println!("🔑 Generating SPHINCS+192s keypair...");
+        log::debug!("Generating SPHINCS+192s keypair");
+        
+        let private_key_data = vec![0u8; self.params.private_key_size];
+        let public_key_data = self.params.derive_public_key(&private_key_data);
         
         Ok((
             SigningKey {
                 algorithm: "SPHINCS+192s".to_string(),
-                key_data: vec![0u8; self.params.private_key_size],
+                key_data: private_key_data,
                 created_at: chrono::Utc::now(),
             },
             VerifyingKey {
                 algorithm: "SPHINCS+192s".to_string(),
-                key_data: vec![0u8; self.params.public_key_size],
+                key_data: public_key_data,
                 created_at: chrono::Utc::now(),
             },
         ))
     }
     
-    fn sign(&self, _signing_key: &SigningKey, _message: &[u8]) -> Result<Signature, CryptoError> {
+    fn sign(&self, signing_key: &SigningKey, message: &[u8]) -> Result<Signature, CryptoError> {
+        if signing_key.algorithm != "SPHINCS+192s" {
+            return Err(CryptoError::InvalidKey("Wrong algorithm for SPHINCS+192s".to_string()));
+        }
+        
         Ok(Signature {
-            data: vec![0u8; self.params.signature_size],
+            data: self.params.sign(&signing_key.key_data, message, Vec::new())?,
             algorithm: "SPHINCS+192s".to_string(),
             created_at: chrono::Utc::now(),
         })
     }
     
-    fn verify(&self, _verifying_key: &VerifyingKey, _message: &[u8], _signature: &Signature) -> Result<bool, CryptoError> {
-        Ok(true)
+    fn verify(&self, verifying_key: &VerifyingKey, message: &[u8], signature: &Signature) -> Result<bool, CryptoError> {
+        if verifying_key.algorithm != "SPHINCS+192s" || signature.algorithm != "SPHINCS+192s" {
+            return Err(CryptoError::InvalidKey("Algorithm mismatch".to_string()));
+        }
+        
+        Ok(self.params.verify(&verifying_key.key_data, message, &signature.data))
     }
     
     fn algorithm_name(&self) -> &str {
@@ -262,7 +311,7 @@ impl DigitalSignature for SphincsPlus192s {
 
 impl SphincsPlus256s {
     pub fn new() -> Self {
-        println!("🌲 Initializing SPHINCS+256s (NIST Level 5)");
+        log::debug!("Initializing SPHINCS+256s (NIST level 5)");
         Self {
             params: SphincsParams::sphincs_256s(),
             rng_state: std::sync::Mutex::new(54321),
@@ -272,32 +321,43 @@ impl SphincsPlus256s {
 
 impl DigitalSignature for SphincsPlus256s {
     fn generate_keypair(&self) -> Result<(SigningKey, VerifyingKey), CryptoError> {
-        println!("🔑 Generating SPHINCS+256s keypair...");
+        log::debug!("Generating SPHINCS+256s keypair");
+        
+        let private_key_data = vec![0u8; self.params.private_key_size];
+        let public_key_data = self.params.derive_public_key(&private_key_data);
         
         Ok((
             SigningKey {
                 algorithm: "SPHINCS+256s".to_string(),
-                key_data: vec![0u8; self.params.private_key_size],
+                key_data: private_key_data,
                 created_at: chrono::Utc::now(),
             },
             VerifyingKey {
                 algorithm: "SPHINCS+256s".to_string(),
-                key_data: vec![0u8; self.params.public_key_size],
+                key_data: public_key_data,
                 created_at: chrono::Utc::now(),
             },
         ))
     }
     
-    fn sign(&self, _signing_key: &SigningKey, _message: &[u8]) -> Result<Signature, CryptoError> {
+    fn sign(&self, signing_key: &SigningKey, message: &[u8]) -> Result<Signature, CryptoError> {
+        if signing_key.algorithm != "SPHINCS+256s" {
+            return Err(CryptoError::InvalidKey("Wrong algorithm for SPHINCS+256s".to_string()));
+        }
+        
         Ok(Signature {
-            data: vec![0u8; self.params.signature_size],
+            data: self.params.sign(&signing_key.key_data, message, Vec::new())?,
             algorithm: "SPHINCS+256s".to_string(),
             created_at: chrono::Utc::now(),
         })
     }
     
-    fn verify(&self, _verifying_key: &VerifyingKey, _message: &[u8], _signature: &Signature) -> Result<bool, CryptoError> {
-        Ok(true)
+    fn verify(&self, verifying_key: &VerifyingKey, message: &[u8], signature: &Signature) -> Result<bool, CryptoError> {
+        if verifying_key.algorithm != "SPHINCS+256s" || signature.algorithm != "SPHINCS+256s" {
+            return Err(CryptoError::InvalidKey("Algorithm mismatch".to_string()));
+        }
+        
+        Ok(self.params.verify(&verifying_key.key_data, message, &signature.data))
     }
     
     fn algorithm_name(&self) -> &str {
@@ -337,6 +397,32 @@ mod tests {
         assert!(verification.unwrap());
     }
     
+    fn assert_rejects_tampering<S: DigitalSignature>(sphincs: &S) {
+        let message = b"test message for hash-based signing";
+        let (signing_key, verifying_key) = sphincs.generate_keypair().unwrap();
+        let signature = sphincs.sign(&signing_key, message).unwrap();
+        
+        assert!(sphincs.verify(&verifying_key, message, &signature).unwrap());
+        assert!(!sphincs.verify(&verifying_key, b"test message for hash-based signinG", &signature).unwrap());
+        
+        // A corrupted tag is rejected as well
+        let mut data = signature.data.clone();
+        data[0] ^= 0x01;
+        let corrupted = Signature {
+            data,
+            algorithm: signature.algorithm.clone(),
+            created_at: signature.created_at,
+        };
+        assert!(!sphincs.verify(&verifying_key, message, &corrupted).unwrap());
+    }
+    
+    #[test]
+    fn test_sphincs_rejects_tampered_message() {
+        assert_rejects_tampering(&SphincsPlus128s::new());
+        assert_rejects_tampering(&SphincsPlus192s::new());
+        assert_rejects_tampering(&SphincsPlus256s::new());
+    }
+    
     #[test]
     fn test_all_sphincs_variants() {
         let sphincs128 = SphincsPlus128s::new();
@@ -11,12 +11,31 @@
 //! - Gestion des incidents et des alertes
 //! - Vue centralisée multi-sites et multi-cloud
 
+mod notification;
+
+pub use notification::{LogNotifier, Notifier, WebhookNotifier};
+
+use notification::NotificationQueue;
+
 use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, SystemTime};
+use std::time::{Instant, SystemTime};
+
+use ipnet::IpNet;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::aegis::ThreatEvent;
+use crate::clock::{system_clock, SharedClock};
+use crate::id::{random_ids, SharedIdGenerator};
+use crate::neurofirewall::DetectionEvent;
 
 /// Configuration du dashboard
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DashboardConfig {
     /// Port d'écoute du serveur web
     pub server_port: u16,
@@ -30,7 +49,16 @@ pub struct DashboardConfig {
     pub enable_3d_visualization: bool,
     /// Activer les notifications en temps réel
     pub enable_realtime_notifications: bool,
-    /// Niveau de détail de la visualisation (1-5)
+    /// URL du webhook recevant les alertes (`http://` uniquement) ; sans webhook, les alertes
+    /// sont écrites dans le journal applicatif
+    pub alert_webhook_url: Option<String>,
+    /// Nombre d'alertes en attente de livraison ; au-delà, les nouvelles alertes sont abandonnées
+    pub notification_queue_size: usize,
+    /// Nombre maximal de clients abonnés au flux d'événements en direct
+    pub max_event_subscribers: usize,
+    /// Répertoire des préférences utilisateur (un fichier JSON par utilisateur)
+    pub preferences_dir: String,
+    /// Niveau de détail de la visualisation (1 = menaces critiques uniquement, 5 = tout afficher)
     pub detail_level: u8,
     /// Thème de l'interface (light, dark, system)
     pub theme: String,
@@ -45,6 +73,10 @@ impl Default for DashboardConfig {
             max_events: 1000,
             enable_3d_visualization: true,
             enable_realtime_notifications: true,
+            alert_webhook_url: None,
+            notification_queue_size: 256,
+            max_event_subscribers: 32,
+            preferences_dir: String::from("/var/lib/icarus/dashboard/preferences"),
             detail_level: 3,
             theme: String::from("dark"),
         }
@@ -116,7 +148,7 @@ pub struct UiEvent {
 }
 
 /// État du dashboard
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DashboardState {
     /// Initialisation en cours
     Initializing,
@@ -131,7 +163,7 @@ pub enum DashboardState {
 }
 
 /// Statistiques du dashboard
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DashboardStats {
     /// Nombre d'utilisateurs connectés
     pub connected_users: usize,
@@ -139,6 +171,8 @@ pub struct DashboardStats {
     pub active_views: usize,
     /// Nombre d'événements traités
     pub processed_events: u64,
+    /// Nombre d'alertes abandonnées faute de place dans la file de notification
+    pub dropped_notifications: u64,
     /// Temps de réponse moyen (en millisecondes)
     pub avg_response_time_ms: f64,
     /// Utilisation CPU (pourcentage)
@@ -150,7 +184,7 @@ pub struct DashboardStats {
 }
 
 /// Élément de visualisation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VisualizationElement {
     /// Identifiant unique de l'élément
     pub id: String,
@@ -187,32 +221,99 @@ pub struct VisualizationScene {
     pub description: String,
     /// Éléments de la scène
     pub elements: HashMap<String, VisualizationElement>,
+    /// Informations complémentaires (totaux, éléments masqués selon le niveau de détail)
+    pub metadata: HashMap<String, String>,
     /// Horodatage de création
     pub created_at: SystemTime,
     /// Horodatage de dernière mise à jour
     pub updated_at: SystemTime,
 }
 
+/// Géolocalisation des adresses IP utilisée par la vue `GeoMap`
+///
+/// Injectée par l'appelant (base GeoIP locale, service externe, table statique) afin que le
+/// dashboard ne dépende d'aucun accès réseau.
+pub trait GeoLocator {
+    /// Latitude et longitude (en degrés) de l'adresse, ou `None` si elle est inconnue
+    fn locate(&self, ip: &str) -> Option<(f32, f32)>;
+}
+
+/// Événement diffusé en direct aux clients du dashboard
+///
+/// Sérialisé en JSON sous la forme `{"kind": "Detection" | "Threat", "event": {...}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "event")]
+pub enum LiveEvent {
+    /// Détection du NeuroFireWall
+    Detection(DetectionEvent),
+    /// Menace traitée par AEGIS
+    Threat(ThreatEvent),
+}
+
+/// Différences entre deux états d'une scène, applicables incrémentalement par le frontend
+///
+/// Les listes sont triées par identifiant d'élément.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneDiff {
+    /// Éléments présents uniquement dans la nouvelle scène
+    pub added: Vec<VisualizationElement>,
+    /// Identifiants des éléments absents de la nouvelle scène
+    pub removed: Vec<String>,
+    /// Nouvelle version des éléments dont au moins un champ a changé
+    pub modified: Vec<VisualizationElement>,
+}
+
+impl SceneDiff {
+    /// Indique si les deux scènes ont des éléments identiques
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
 /// Dashboard principal
 pub struct Dashboard {
     config: DashboardConfig,
     state: Arc<Mutex<DashboardState>>,
+    /// Instant de passage à l'état opérationnel (None si arrêté)
+    started_at: Arc<Mutex<Option<Instant>>>,
     stats: Arc<Mutex<DashboardStats>>,
+    /// Canal de diffusion des événements en direct ; le verrou sérialise les abonnements
+    /// pour respecter `max_event_subscribers`
+    live_events: Arc<Mutex<broadcast::Sender<LiveEvent>>>,
+    /// Utilisateurs connectés, indexés par identifiant
+    users: Arc<Mutex<HashMap<String, DashboardUser>>>,
+    /// File de livraison des alertes (détections du NeuroFireWall) vers le notificateur
+    notifications: Option<NotificationQueue>,
+    /// Générateur des identifiants de scènes et d'éléments
+    ids: SharedIdGenerator,
+    /// Source de temps (horodatage des scènes et des connexions)
+    clock: SharedClock,
     // Les champs suivants seront implémentés dans les versions futures
     // server: Option<WebServer>,
     // visualization_engine: VisualizationEngine,
     // event_processor: EventProcessor,
     // user_manager: UserManager,
-    // notification_system: NotificationSystem,
 }
 
 impl Dashboard {
+    /// Largeur de l'axe temporel d'une frise chronologique
+    const TIMELINE_WIDTH: f32 = 100.0;
+    /// Écart vertical entre deux cibles d'une visualisation de menace
+    const TARGET_SPACING: f32 = 2.0;
+    /// Préfixe des sous-réseaux IPv4 regroupés en une seule cible
+    const CLUSTER_PREFIX_V4: u8 = 24;
+    /// Préfixe des sous-réseaux IPv6 regroupés en une seule cible
+    const CLUSTER_PREFIX_V6: u8 = 64;
+    /// Nombre d'événements en attente par abonné ; un client plus lent perd les plus anciens
+    const LIVE_EVENT_CAPACITY: usize = 256;
+    
     /// Crée une nouvelle instance du dashboard
     pub fn new(config: DashboardConfig) -> Self {
         let stats = DashboardStats {
             connected_users: 0,
             active_views: 0,
             processed_events: 0,
+            dropped_notifications: 0,
             avg_response_time_ms: 0.0,
             cpu_usage: 0.0,
             memory_usage: 0.0,
@@ -222,18 +323,36 @@ impl Dashboard {
         Self {
             config,
             state: Arc::new(Mutex::new(DashboardState::Initializing)),
+            started_at: Arc::new(Mutex::new(None)),
             stats: Arc::new(Mutex::new(stats)),
+            live_events: Arc::new(Mutex::new(broadcast::channel(Self::LIVE_EVENT_CAPACITY).0)),
+            users: Arc::new(Mutex::new(HashMap::new())),
+            notifications: None,
+            ids: random_ids(),
+            clock: system_clock(),
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
     
     /// Démarre le dashboard
+    ///
+    /// Sans notificateur défini, les alertes sont livrées au webhook configuré ou, à défaut,
+    /// au journal applicatif.
     pub fn start(&mut self) -> Result<(), String> {
         // Cette fonction sera implémentée dans les versions futures
         // Pour l'instant, elle change simplement l'état
         
+        if self.notifications.is_none() {
+            let notifier: Box<dyn Notifier> = match &self.config.alert_webhook_url {
+                Some(url) => Box::new(WebhookNotifier::new(url)?),
+                None => Box::new(LogNotifier),
+            };
+            self.set_notifier(notifier);
+        }
+        
         let mut state = self.state.lock().unwrap();
         *state = DashboardState::Running;
+        *self.started_at.lock().unwrap() = Some(Instant::now());
         
         Ok(())
     }
@@ -245,6 +364,7 @@ impl Dashboard {
         
         let mut state = self.state.lock().unwrap();
         *state = DashboardState::Stopped;
+        *self.started_at.lock().unwrap() = None;
         
         Ok(())
     }
@@ -254,9 +374,171 @@ impl Dashboard {
         self.state.lock().unwrap().clone()
     }
     
+    /// Obtient la configuration du dashboard
+    pub fn get_config(&self) -> &DashboardConfig {
+        &self.config
+    }
+    
     /// Obtient les statistiques actuelles du dashboard
     pub fn get_stats(&self) -> DashboardStats {
-        self.stats.lock().unwrap().clone()
+        let mut stats = self.stats.lock().unwrap().clone();
+        stats.uptime_seconds = self.uptime_seconds();
+        stats
+    }
+    
+    /// Temps écoulé depuis le dernier démarrage (en secondes)
+    fn uptime_seconds(&self) -> u64 {
+        self.started_at.lock().unwrap().map_or(0, |started_at| started_at.elapsed().as_secs())
+    }
+    
+    /// Remet à zéro les compteurs statistiques sans modifier l'état opérationnel
+    ///
+    /// Peut être appelé sans risque entre deux phases d'un benchmark (échauffement, mesure).
+    ///
+    /// Les mesures instantanées (utilisateurs connectés, vues actives, ressources) sont conservées.
+    pub fn reset_stats(&self) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.processed_events = 0;
+        stats.dropped_notifications = 0;
+        stats.avg_response_time_ms = 0.0;
+    }
+    
+    /// Connecte un utilisateur et complète ses préférences avec celles enregistrées
+    ///
+    /// Les préférences fournies à la connexion priment sur les préférences enregistrées.
+    pub fn connect_user(&self, mut user: DashboardUser) -> Result<(), String> {
+        let mut preferences = self.load_preferences(&user.id)?;
+        preferences.extend(user.preferences.drain());
+        user.preferences = preferences;
+        user.last_login = Some(self.clock.now());
+        
+        let mut users = self.users.lock().unwrap();
+        users.insert(user.id.clone(), user);
+        self.stats.lock().unwrap().connected_users = users.len();
+        
+        Ok(())
+    }
+    
+    /// Déconnecte un utilisateur ; renvoie `false` s'il n'était pas connecté
+    pub fn disconnect_user(&self, user_id: &str) -> bool {
+        let mut users = self.users.lock().unwrap();
+        let removed = users.remove(user_id).is_some();
+        self.stats.lock().unwrap().connected_users = users.len();
+        removed
+    }
+    
+    /// Utilisateur connecté
+    pub fn get_user(&self, user_id: &str) -> Option<DashboardUser> {
+        self.users.lock().unwrap().get(user_id).cloned()
+    }
+    
+    /// Modifie une préférence d'un utilisateur connecté (sans l'enregistrer)
+    pub fn set_user_preference(&self, user_id: &str, key: &str, value: &str) -> Result<(), String> {
+        let mut users = self.users.lock().unwrap();
+        let user = users.get_mut(user_id).ok_or(format!("Utilisateur non connecté: {}", user_id))?;
+        user.preferences.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+    
+    /// Enregistre les préférences d'un utilisateur connecté dans `preferences_dir`
+    pub fn save_preferences(&self, user_id: &str) -> Result<(), String> {
+        let preferences = self
+            .get_user(user_id)
+            .ok_or(format!("Utilisateur non connecté: {}", user_id))?
+            .preferences;
+        let path = self.preferences_path(user_id)?;
+        
+        fs::create_dir_all(&self.config.preferences_dir)
+            .map_err(|e| format!("Impossible de créer {}: {}", self.config.preferences_dir, e))?;
+        let content = serde_json::to_string_pretty(&preferences)
+            .map_err(|e| format!("Sérialisation des préférences impossible: {}", e))?;
+        
+        // Écriture dans un fichier temporaire puis renommage : un arrêt brutal ne laisse
+        // jamais de fichier tronqué
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, content).map_err(|e| format!("Impossible d'écrire {}: {}", temporary.display(), e))?;
+        fs::rename(&temporary, &path).map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))
+    }
+    
+    /// Charge les préférences enregistrées d'un utilisateur (vides si aucune)
+    pub fn load_preferences(&self, user_id: &str) -> Result<HashMap<String, String>, String> {
+        let path = self.preferences_path(user_id)?;
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+        
+        let content = fs::read_to_string(&path).map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Préférences invalides dans {}: {}", path.display(), e))
+    }
+    
+    /// Chemin du fichier de préférences ; l'identifiant ne peut pas sortir du répertoire
+    fn preferences_path(&self, user_id: &str) -> Result<PathBuf, String> {
+        let valid = !user_id.is_empty()
+            && user_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(format!("Identifiant d'utilisateur invalide: {:?}", user_id));
+        }
+        
+        Ok(PathBuf::from(&self.config.preferences_dir).join(format!("{}.json", user_id)))
+    }
+    
+    /// Abonne un client au flux d'événements en direct
+    ///
+    /// Échoue si les notifications en temps réel sont désactivées ou si `max_event_subscribers`
+    /// clients sont déjà abonnés. L'abonnement prend fin à la destruction du récepteur.
+    pub fn subscribe_events(&self) -> Result<broadcast::Receiver<LiveEvent>, String> {
+        if !self.config.enable_realtime_notifications {
+            return Err("Les notifications en temps réel sont désactivées".to_string());
+        }
+        
+        let sender = self.live_events.lock().unwrap();
+        if sender.receiver_count() >= self.config.max_event_subscribers {
+            return Err(format!(
+                "Nombre maximal d'abonnés au flux d'événements atteint ({})",
+                self.config.max_event_subscribers
+            ));
+        }
+        
+        Ok(sender.subscribe())
+    }
+    
+    /// Définit le canal de livraison des alertes
+    ///
+    /// Les alertes sont livrées par un thread dédié ; le notificateur précédent s'arrête après
+    /// avoir livré les alertes déjà en attente.
+    pub fn set_notifier(&mut self, notifier: Box<dyn Notifier>) {
+        self.notifications = Some(NotificationQueue::spawn(notifier, self.config.notification_queue_size));
+    }
+    
+    /// Remplace la source de temps (horloge simulée dans les tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+    
+    /// Remplace le générateur d'identifiants (identifiants séquentiels dans les tests)
+    pub fn set_id_generator(&mut self, ids: SharedIdGenerator) {
+        self.ids = ids;
+    }
+    
+    /// Générateur d'identifiants du dashboard (menaces dérivées des attaques relayées)
+    pub fn id_generator(&self) -> SharedIdGenerator {
+        self.ids.clone()
+    }
+    
+    /// Diffuse un événement aux clients abonnés et renvoie le nombre de destinataires
+    ///
+    /// Les détections sont également mises en file pour le notificateur si les notifications en
+    /// temps réel sont activées, sans attendre leur livraison : un échec de livraison est
+    /// journalisé sans être propagé, et une alerte ne trouvant pas de place dans la file est
+    /// abandonnée (`DashboardStats::dropped_notifications`).
+    pub fn publish_event(&self, event: LiveEvent) -> usize {
+        if let (LiveEvent::Detection(detection), Some(notifications)) = (&event, &self.notifications) {
+            if self.config.enable_realtime_notifications && !notifications.submit(detection) {
+                self.stats.lock().unwrap().dropped_notifications += 1;
+            }
+        }
+        
+        self.live_events.lock().unwrap().send(event).unwrap_or(0)
     }
     
     /// Crée une nouvelle scène de visualisation
@@ -264,14 +546,16 @@ impl Dashboard {
         // Cette fonction sera implémentée dans les versions futures
         // Pour l'instant, elle crée une scène vide
         
+        let now = self.clock.now();
         let scene = VisualizationScene {
-            id: format!("scene-{}", uuid::Uuid::new_v4()),
+            id: self.ids.next_id("scene"),
             visualization_type,
             title: title.to_string(),
             description: description.to_string(),
             elements: HashMap::new(),
-            created_at: SystemTime::now(),
-            updated_at: SystemTime::now(),
+            metadata: HashMap::new(),
+            created_at: now,
+            updated_at: now,
         };
         
         Ok(scene)
@@ -282,7 +566,7 @@ impl Dashboard {
         // Cette fonction sera implémentée dans les versions futures
         // Pour l'instant, elle ajoute un élément simple
         
-        let element_id = format!("element-{}", uuid::Uuid::new_v4());
+        let element_id = self.ids.next_id("element");
         
         let element = VisualizationElement {
             id: element_id.clone(),
@@ -298,40 +582,282 @@ impl Dashboard {
         };
         
         scene.elements.insert(element_id.clone(), element);
-        scene.updated_at = SystemTime::now();
+        scene.updated_at = self.clock.now();
         
         Ok(element_id)
     }
     
+    /// Calcule les éléments ajoutés, retirés et modifiés entre deux états d'une scène
+    pub fn diff_scenes(old: &VisualizationScene, new: &VisualizationScene) -> SceneDiff {
+        let mut diff = SceneDiff::default();
+        
+        for (id, element) in &new.elements {
+            match old.elements.get(id) {
+                None => diff.added.push(element.clone()),
+                Some(previous) if previous != element => diff.modified.push(element.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .elements
+            .keys()
+            .filter(|id| !new.elements.contains_key(*id))
+            .cloned()
+            .collect();
+        
+        diff.added.sort_by(|a, b| a.id.cmp(&b.id));
+        diff.modified.sort_by(|a, b| a.id.cmp(&b.id));
+        diff.removed.sort();
+        
+        diff
+    }
+    
+    /// Construit une carte géographique des sources de menaces
+    ///
+    /// Chaque adresse localisée donne un élément placé en `(longitude, latitude, 0)`, coloré
+    /// selon la gravité la plus élevée observée (0 = info à 4 = critique). Les événements d'une
+    /// même adresse sont regroupés ; les adresses non localisées sont ignorées.
+    ///
+    /// Les adresses dont la gravité est inférieure au minimum du niveau de détail sont masquées ;
+    /// les métadonnées `total_events`, `suppressed_events` et `suppressed_elements` en rendent compte.
+    ///
+    /// L'identifiant d'un élément (`source-<adresse>`) est stable d'une reconstruction à l'autre,
+    /// ce qui permet à `diff_scenes` de ne signaler que les sources réellement modifiées.
+    pub fn build_geo_map(&self, events: &[(String, u8)], geo: &dyn GeoLocator) -> VisualizationScene {
+        let now = self.clock.now();
+        let mut scene = VisualizationScene {
+            id: self.ids.next_id("scene"),
+            visualization_type: VisualizationType::GeoMap,
+            title: "Carte des menaces".to_string(),
+            description: format!("Origine géographique de {} événement(s)", events.len()),
+            elements: HashMap::new(),
+            metadata: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        
+        // Gravité maximale et nombre d'événements par adresse, dans l'ordre de première apparition
+        let mut sources: Vec<(&str, u8, usize)> = Vec::new();
+        for (ip, severity) in events {
+            match sources.iter_mut().find(|(source, _, _)| source == ip) {
+                Some(source) => {
+                    source.1 = source.1.max(*severity);
+                    source.2 += 1;
+                }
+                None => sources.push((ip, *severity, 1)),
+            }
+        }
+        
+        let min_severity = self.min_visible_severity();
+        let mut suppressed_events = 0;
+        let mut suppressed_elements = 0;
+        
+        for (ip, severity, count) in sources {
+            if severity < min_severity {
+                suppressed_events += count;
+                suppressed_elements += 1;
+                continue;
+            }
+            let Some((latitude, longitude)) = geo.locate(ip) else { continue };
+            
+            let element_id = format!("source-{}", ip);
+            let mut data = HashMap::new();
+            data.insert("address".to_string(), ip.to_string());
+            data.insert("severity".to_string(), severity.to_string());
+            data.insert("event_count".to_string(), count.to_string());
+            
+            scene.elements.insert(element_id.clone(), VisualizationElement {
+                id: element_id,
+                element_type: "source".to_string(),
+                position_x: longitude,
+                position_y: latitude,
+                position_z: 0.0,
+                color: Self::severity_color(severity).to_string(),
+                size: 1.0,
+                opacity: 1.0,
+                data,
+                children: Vec::new(),
+            });
+        }
+        
+        scene.metadata.insert("total_events".to_string(), events.len().to_string());
+        scene.metadata.insert("suppressed_events".to_string(), suppressed_events.to_string());
+        scene.metadata.insert("suppressed_elements".to_string(), suppressed_elements.to_string());
+        
+        scene
+    }
+    
+    /// Construit une frise chronologique d'événements `(horodatage, libellé, gravité)`
+    ///
+    /// Les éléments sont placés sur l'axe X selon leur horodatage, ramené à `[0, TIMELINE_WIDTH]`
+    /// entre le premier et le dernier événement, et sur l'axe Y selon leur gravité. Comme pour
+    /// `build_geo_map`, les événements sous le minimum du niveau de détail sont masqués et
+    /// comptabilisés dans les métadonnées.
+    ///
+    /// L'identifiant d'un élément est dérivé de son horodatage et de son libellé
+    /// (`event-<secondes>.<nanosecondes>-<libellé>`, suffixé d'un rang pour les doublons) afin
+    /// de rester stable d'une reconstruction à l'autre.
+    pub fn build_timeline(&self, events: &[(SystemTime, String, u8)]) -> VisualizationScene {
+        let now = self.clock.now();
+        let mut scene = VisualizationScene {
+            id: self.ids.next_id("scene"),
+            visualization_type: VisualizationType::Timeline,
+            title: "Chronologie des menaces".to_string(),
+            description: format!("Progression de {} événement(s)", events.len()),
+            elements: HashMap::new(),
+            metadata: HashMap::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        
+        let mut ordered: Vec<&(SystemTime, String, u8)> = events.iter().collect();
+        ordered.sort_by_key(|(timestamp, _, _)| *timestamp);
+        
+        // Un seul événement (ou des horodatages identiques) : tous placés à l'origine
+        let start = ordered.first().map(|(timestamp, _, _)| *timestamp);
+        let span = match (start, ordered.last()) {
+            (Some(start), Some((end, _, _))) => end.duration_since(start).unwrap_or_default().as_secs_f32(),
+            _ => 0.0,
+        };
+        
+        let min_severity = self.min_visible_severity();
+        let mut suppressed_events = 0;
+        
+        for (index, (timestamp, label, severity)) in ordered.into_iter().enumerate() {
+            if *severity < min_severity {
+                suppressed_events += 1;
+                continue;
+            }
+            
+            let offset = start
+                .and_then(|start| timestamp.duration_since(start).ok())
+                .map_or(0.0, |offset| offset.as_secs_f32());
+            let position_x = if span > 0.0 { offset / span * Self::TIMELINE_WIDTH } else { 0.0 };
+            
+            let since_epoch = timestamp.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+            let base_id = format!("event-{}.{:09}-{}", since_epoch.as_secs(), since_epoch.subsec_nanos(), label);
+            let mut element_id = base_id.clone();
+            for rank in 1.. {
+                if !scene.elements.contains_key(&element_id) {
+                    break;
+                }
+                element_id = format!("{}-{}", base_id, rank);
+            }
+            let mut data = HashMap::new();
+            data.insert("label".to_string(), label.clone());
+            data.insert("severity".to_string(), severity.to_string());
+            data.insert("order".to_string(), index.to_string());
+            if let Ok(since_epoch) = timestamp.duration_since(SystemTime::UNIX_EPOCH) {
+                data.insert("timestamp".to_string(), since_epoch.as_secs().to_string());
+            }
+            
+            scene.elements.insert(element_id.clone(), VisualizationElement {
+                id: element_id,
+                element_type: "event".to_string(),
+                position_x,
+                position_y: *severity as f32,
+                position_z: 0.0,
+                color: Self::severity_color(*severity).to_string(),
+                size: 1.0,
+                opacity: 1.0,
+                data,
+                children: Vec::new(),
+            });
+        }
+        
+        scene.metadata.insert("total_events".to_string(), events.len().to_string());
+        scene.metadata.insert("suppressed_events".to_string(), suppressed_events.to_string());
+        
+        scene
+    }
+    
+    /// Gravité minimale affichée selon `detail_level` : 4 (critique) au niveau 1, 0 (tout) au niveau 5
+    fn min_visible_severity(&self) -> u8 {
+        5 - self.config.detail_level.clamp(1, 5)
+    }
+    
+    /// Couleur associée à une gravité (0 = info à 4 = critique)
+    fn severity_color(severity: u8) -> &'static str {
+        match severity {
+            0 => "#00AAFF",
+            1 => "#2ECC71",
+            2 => "#F1C40F",
+            3 => "#E67E22",
+            _ => "#E74C3C",
+        }
+    }
+    
     /// Traite un événement de menace pour visualisation
     pub fn process_threat_for_visualization(&self, threat_id: &str, threat_type: &str, severity: u8, source: &str, target: &str) -> Result<VisualizationScene, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle crée une scène de visualisation simple
-        
+        self.process_threat_targets_for_visualization(threat_id, threat_type, severity, source, &[target], false)
+    }
+    
+    /// Traite un événement de menace visant plusieurs cibles pour visualisation
+    ///
+    /// Avec `group_by_subnet`, les cibles d'un même sous-réseau (/24 en IPv4, /64 en IPv6) sont
+    /// regroupées en un seul élément `cluster` portant le nombre de membres (`member_count`) ;
+    /// une cible seule dans son sous-réseau ou dont l'adresse est illisible reste un nœud.
+    ///
+    /// Une menace moins grave que le minimum affiché selon `detail_level` est réduite à son
+    /// seul élément `threat` : la source et les cibles omises sont comptées dans la métadonnée
+    /// `suppressed_elements` et le nombre de cibles est conservé dans `target_count`.
+    pub fn process_threat_targets_for_visualization(
+        &self,
+        threat_id: &str,
+        threat_type: &str,
+        severity: u8,
+        source: &str,
+        targets: &[&str],
+        group_by_subnet: bool,
+    ) -> Result<VisualizationScene, String> {
         let mut scene = self.create_visualization_scene(
             VisualizationType::Network3D,
             &format!("Menace: {}", threat_id),
             &format!("Visualisation de la menace de type {}", threat_type),
         )?;
         
+        let groups = Self::group_targets(targets, group_by_subnet);
+        let detailed = severity >= self.min_visible_severity();
+        let suppressed_elements = if detailed { 0 } else { 1 + groups.len() };
+        
         // Ajouter l'élément source
-        let mut source_data = HashMap::new();
-        source_data.insert("type".to_string(), "source".to_string());
-        source_data.insert("address".to_string(), source.to_string());
-        self.add_element_to_scene(&mut scene, "node", (-5.0, 0.0, 0.0), source_data)?;
+        if detailed {
+            let mut source_data = HashMap::new();
+            source_data.insert("type".to_string(), "source".to_string());
+            source_data.insert("address".to_string(), source.to_string());
+            self.add_element_to_scene(&mut scene, "node", (-5.0, 0.0, 0.0), source_data)?;
+        }
         
-        // Ajouter l'élément cible
-        let mut target_data = HashMap::new();
-        target_data.insert("type".to_string(), "target".to_string());
-        target_data.insert("address".to_string(), target.to_string());
-        self.add_element_to_scene(&mut scene, "node", (5.0, 0.0, 0.0), target_data)?;
+        // Ajouter les cibles, empilées verticalement et centrées sur l'axe de la source
+        let shown_groups = if detailed { groups.as_slice() } else { &[] };
+        for (index, (subnet, members)) in shown_groups.iter().enumerate() {
+            let position = (5.0, (index as f32 - (groups.len() - 1) as f32 / 2.0) * Self::TARGET_SPACING, 0.0);
+            
+            let mut target_data = HashMap::new();
+            match (subnet, members.as_slice()) {
+                (Some(subnet), [_, _, ..]) => {
+                    target_data.insert("type".to_string(), "target_cluster".to_string());
+                    target_data.insert("subnet".to_string(), subnet.to_string());
+                    target_data.insert("member_count".to_string(), members.len().to_string());
+                    target_data.insert("members".to_string(), members.join(","));
+                    self.add_element_to_scene(&mut scene, "cluster", position, target_data)?;
+                }
+                _ => {
+                    target_data.insert("type".to_string(), "target".to_string());
+                    target_data.insert("address".to_string(), members[0].to_string());
+                    self.add_element_to_scene(&mut scene, "node", position, target_data)?;
+                }
+            }
+        }
         
         // Ajouter l'élément de menace
         let mut threat_data = HashMap::new();
         threat_data.insert("id".to_string(), threat_id.to_string());
         threat_data.insert("type".to_string(), threat_type.to_string());
         threat_data.insert("severity".to_string(), severity.to_string());
+        threat_data.insert("target_count".to_string(), targets.len().to_string());
         self.add_element_to_scene(&mut scene, "threat", (0.0, 0.0, 0.0), threat_data)?;
+        scene.metadata.insert("suppressed_elements".to_string(), suppressed_elements.to_string());
         
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
@@ -340,6 +866,27 @@ impl Dashboard {
         Ok(scene)
     }
     
+    /// Regroupe les cibles par sous-réseau, dans l'ordre de première apparition
+    ///
+    /// Sans regroupement, ou pour une adresse illisible, chaque cible forme son propre groupe.
+    fn group_targets<'a>(targets: &[&'a str], group_by_subnet: bool) -> Vec<(Option<IpNet>, Vec<&'a str>)> {
+        let mut groups: Vec<(Option<IpNet>, Vec<&'a str>)> = Vec::new();
+        
+        for &target in targets {
+            let subnet = target.parse::<IpAddr>().ok().filter(|_| group_by_subnet).and_then(|address| {
+                let prefix = if address.is_ipv4() { Self::CLUSTER_PREFIX_V4 } else { Self::CLUSTER_PREFIX_V6 };
+                IpNet::new(address, prefix).ok().map(|network| network.trunc())
+            });
+            
+            match groups.iter_mut().find(|(group_subnet, _)| subnet.is_some() && *group_subnet == subnet) {
+                Some((_, members)) => members.push(target),
+                None => groups.push((subnet, vec![target])),
+            }
+        }
+        
+        groups
+    }
+    
     /// Génère une URL pour accéder au dashboard
     pub fn get_dashboard_url(&self) -> String {
         format!("https://{}:{}/dashboard", self.config.server_address, self.config.server_port)
@@ -350,6 +897,11 @@ impl Dashboard {
 mod tests {
     use super::*;
     
+    use std::sync::mpsc;
+    use std::time::Duration;
+    
+    use crate::clock::MockClock;
+    
     #[test]
     fn test_dashboard_creation() {
         let config = DashboardConfig::default();
@@ -392,6 +944,32 @@ mod tests {
         assert!(scene.elements.is_empty());
     }
     
+    #[test]
+    fn test_timestamps_come_from_injected_clock() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let clock = Arc::new(MockClock::new(start));
+        let mut dashboard = Dashboard::new(DashboardConfig::default());
+        dashboard.set_clock(clock.clone());
+        
+        let mut scene = dashboard.create_visualization_scene(VisualizationType::Network3D, "Scène", "").unwrap();
+        assert_eq!((scene.created_at, scene.updated_at), (start, start));
+        
+        clock.advance(Duration::from_secs(60));
+        dashboard.add_element_to_scene(&mut scene, "node", (0.0, 0.0, 0.0), HashMap::new()).unwrap();
+        assert_eq!(scene.created_at, start);
+        assert_eq!(scene.updated_at, start + Duration::from_secs(60));
+        assert_eq!(dashboard.build_timeline(&[]).created_at, start + Duration::from_secs(60));
+        
+        dashboard.connect_user(DashboardUser {
+            id: "analyst-1".to_string(),
+            username: "analyste".to_string(),
+            access_level: AccessLevel::Analyst,
+            last_login: None,
+            preferences: HashMap::new(),
+        }).unwrap();
+        assert_eq!(dashboard.get_user("analyst-1").unwrap().last_login, Some(start + Duration::from_secs(60)));
+    }
+    
     #[test]
     fn test_add_element_to_scene() {
         let config = DashboardConfig::default();
@@ -420,4 +998,392 @@ mod tests {
         assert_eq!(element.position_z, 3.0);
         assert_eq!(element.data.get("key1").unwrap(), "value1");
     }
+    
+    #[test]
+    fn test_diff_scenes() {
+        let config = DashboardConfig::default();
+        let dashboard = Dashboard::new(config);
+        
+        let before = dashboard
+            .process_threat_for_visualization("threat-1", "PortScan", 2, "192.168.1.100", "192.168.1.1")
+            .unwrap();
+        assert!(Dashboard::diff_scenes(&before, &before).is_empty());
+        
+        let mut after = before.clone();
+        let added_id = dashboard.add_element_to_scene(&mut after, "node", (0.0, 5.0, 0.0), HashMap::new()).unwrap();
+        let moved_id = before.elements.keys().next().unwrap().clone();
+        after.elements.get_mut(&moved_id).unwrap().position_y = 3.0;
+        
+        let diff = Dashboard::diff_scenes(&before, &after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, added_id);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].id, moved_id);
+        assert_eq!(diff.modified[0].position_y, 3.0);
+        assert!(diff.removed.is_empty());
+        
+        // La suppression est détectée dans l'autre sens
+        let reverse = Dashboard::diff_scenes(&after, &before);
+        assert_eq!(reverse.removed, vec![added_id]);
+        
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["added"].as_array().unwrap().len(), 1);
+        assert_eq!(json["modified"][0]["position_y"], 3.0);
+    }
+    
+    #[test]
+    fn test_live_event_subscribers_are_capped() {
+        let config = DashboardConfig { max_event_subscribers: 2, ..Default::default() };
+        let dashboard = Dashboard::new(config);
+        
+        let event = LiveEvent::Threat(ThreatEvent {
+            id: "threat-live".to_string(),
+            threat_type: crate::aegis::ThreatType::PortScan,
+            severity: crate::aegis::ThreatSeverity::Medium,
+            confidence: 0.85,
+            source: "192.168.1.100".to_string(),
+            target: "192.168.1.1".to_string(),
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        });
+        assert_eq!(dashboard.publish_event(event.clone()), 0);
+        
+        let mut first = dashboard.subscribe_events().unwrap();
+        let second = dashboard.subscribe_events().unwrap();
+        assert!(dashboard.subscribe_events().is_err());
+        
+        // La déconnexion d'un client libère une place
+        drop(second);
+        let _third = dashboard.subscribe_events().unwrap();
+        
+        assert_eq!(dashboard.publish_event(event), 2);
+        assert!(matches!(first.try_recv().unwrap(), LiveEvent::Threat(threat) if threat.id == "threat-live"));
+        
+        let config = DashboardConfig { enable_realtime_notifications: false, ..Default::default() };
+        assert!(Dashboard::new(config).subscribe_events().is_err());
+    }
+    
+    /// Localisation à partir d'une table fixe
+    struct MockLocator(HashMap<&'static str, (f32, f32)>);
+    
+    impl GeoLocator for MockLocator {
+        fn locate(&self, ip: &str) -> Option<(f32, f32)> {
+            self.0.get(ip).copied()
+        }
+    }
+    
+    #[test]
+    fn test_build_geo_map() {
+        let config = DashboardConfig::default();
+        let dashboard = Dashboard::new(config);
+        let locator = MockLocator(HashMap::from([
+            ("203.0.113.7", (48.85, 2.35)),
+            ("198.51.100.23", (-33.87, 151.21)),
+        ]));
+        
+        let events = vec![
+            ("203.0.113.7".to_string(), 1),
+            ("198.51.100.23".to_string(), 4),
+            ("203.0.113.7".to_string(), 3),
+            ("192.0.2.1".to_string(), 2),
+        ];
+        let scene = dashboard.build_geo_map(&events, &locator);
+        assert_eq!(scene.visualization_type, VisualizationType::GeoMap);
+        assert_eq!(scene.elements.len(), 2);
+        
+        let element = |ip: &str| scene.elements.values().find(|e| e.data.get("address").unwrap() == ip).unwrap();
+        
+        let paris = element("203.0.113.7");
+        assert_eq!((paris.position_x, paris.position_y, paris.position_z), (2.35, 48.85, 0.0));
+        assert_eq!(paris.data.get("severity").unwrap(), "3");
+        assert_eq!(paris.data.get("event_count").unwrap(), "2");
+        assert_eq!(paris.color, "#E67E22");
+        
+        let sydney = element("198.51.100.23");
+        assert_eq!((sydney.position_x, sydney.position_y), (151.21, -33.87));
+        assert_eq!(sydney.color, "#E74C3C");
+    }
+    
+    #[test]
+    fn test_detail_level_suppresses_low_severity_sources() {
+        let locator = MockLocator(HashMap::from([
+            ("203.0.113.7", (48.85, 2.35)),
+            ("198.51.100.23", (-33.87, 151.21)),
+            ("192.0.2.1", (40.71, -74.01)),
+        ]));
+        let events = vec![
+            ("203.0.113.7".to_string(), 1),
+            ("203.0.113.7".to_string(), 2),
+            ("198.51.100.23".to_string(), 4),
+            ("192.0.2.1".to_string(), 0),
+        ];
+        let build = |detail_level: u8| {
+            let config = DashboardConfig { detail_level, ..Default::default() };
+            Dashboard::new(config).build_geo_map(&events, &locator)
+        };
+        
+        let full = build(5);
+        assert_eq!(full.elements.len(), 3);
+        assert_eq!(full.metadata.get("suppressed_events").unwrap(), "0");
+        
+        let minimal = build(1);
+        assert_eq!(minimal.elements.len(), 1);
+        assert_eq!(minimal.elements.values().next().unwrap().data.get("address").unwrap(), "198.51.100.23");
+        assert_eq!(minimal.metadata.get("total_events").unwrap(), "4");
+        assert_eq!(minimal.metadata.get("suppressed_events").unwrap(), "3");
+        assert_eq!(minimal.metadata.get("suppressed_elements").unwrap(), "2");
+    }
+    
+    #[test]
+    fn test_build_timeline() {
+        let config = DashboardConfig { detail_level: 5, ..Default::default() };
+        let dashboard = Dashboard::new(config);
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        
+        // Étapes d'une attaque APT, fournies dans le désordre
+        let events = vec![
+            (start + Duration::from_secs(3600), "exfiltration".to_string(), 4),
+            (start, "reconnaissance".to_string(), 1),
+            (start + Duration::from_secs(600), "mouvement latéral".to_string(), 3),
+        ];
+        let scene = dashboard.build_timeline(&events);
+        assert_eq!(scene.visualization_type, VisualizationType::Timeline);
+        
+        let mut elements: Vec<&VisualizationElement> = scene.elements.values().collect();
+        elements.sort_by_key(|element| element.data.get("order").unwrap().parse::<usize>().unwrap());
+        let labels: Vec<&str> = elements.iter().map(|e| e.data.get("label").unwrap().as_str()).collect();
+        assert_eq!(labels, vec!["reconnaissance", "mouvement latéral", "exfiltration"]);
+        assert!(elements.windows(2).all(|pair| pair[0].position_x < pair[1].position_x));
+        assert_eq!(elements[0].position_x, 0.0);
+        assert_eq!(elements[2].position_x, Dashboard::TIMELINE_WIDTH);
+        assert_eq!(elements[2].position_y, 4.0);
+        
+        // Un événement unique est placé à l'origine sans division par zéro
+        let single = dashboard.build_timeline(&events[..1]);
+        let element = single.elements.values().next().unwrap();
+        assert_eq!(element.position_x, 0.0);
+        assert!(dashboard.build_timeline(&[]).elements.is_empty());
+    }
+    
+    #[test]
+    fn test_rebuilt_scenes_keep_element_ids() {
+        let config = DashboardConfig { detail_level: 5, ..Default::default() };
+        let dashboard = Dashboard::new(config);
+        let locator = MockLocator(HashMap::from([
+            ("203.0.113.7", (48.85, 2.35)),
+            ("198.51.100.23", (-33.87, 151.21)),
+        ]));
+        
+        // Une reconstruction à l'identique ne produit aucune différence
+        let mut events = vec![("203.0.113.7".to_string(), 1)];
+        let before = dashboard.build_geo_map(&events, &locator);
+        assert!(Dashboard::diff_scenes(&before, &dashboard.build_geo_map(&events, &locator)).is_empty());
+        
+        // Seule la nouvelle source apparaît
+        events.push(("198.51.100.23".to_string(), 4));
+        let diff = Dashboard::diff_scenes(&before, &dashboard.build_geo_map(&events, &locator));
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, "source-198.51.100.23");
+        assert!(diff.removed.is_empty() && diff.modified.is_empty());
+        
+        // Frise : les doublons (même horodatage et libellé) restent distincts et stables
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut timeline = vec![
+            (start, "reconnaissance".to_string(), 1),
+            (start, "reconnaissance".to_string(), 1),
+        ];
+        let before = dashboard.build_timeline(&timeline);
+        assert_eq!(before.elements.len(), 2);
+        assert!(Dashboard::diff_scenes(&before, &dashboard.build_timeline(&timeline)).is_empty());
+        
+        // Un événement ajouté en fin de frise déplace les autres sans les remplacer
+        timeline.push((start + Duration::from_secs(600), "exfiltration".to_string(), 4));
+        let diff = Dashboard::diff_scenes(&before, &dashboard.build_timeline(&timeline));
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].data.get("label").unwrap(), "exfiltration");
+        assert!(diff.removed.is_empty());
+    }
+    
+    #[test]
+    fn test_preferences_persist_across_restarts() {
+        let directory = std::env::temp_dir().join(format!("icarus-preferences-{}", uuid::Uuid::new_v4()));
+        let config = DashboardConfig {
+            preferences_dir: directory.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let user = |preferences: HashMap<String, String>| DashboardUser {
+            id: "analyst-1".to_string(),
+            username: "analyste".to_string(),
+            access_level: AccessLevel::Analyst,
+            last_login: None,
+            preferences,
+        };
+        
+        let dashboard = Dashboard::new(config.clone());
+        dashboard.connect_user(user(HashMap::new())).unwrap();
+        dashboard.set_user_preference("analyst-1", "theme", "light").unwrap();
+        dashboard.set_user_preference("analyst-1", "layout", "grid").unwrap();
+        dashboard.save_preferences("analyst-1").unwrap();
+        drop(dashboard);
+        
+        let dashboard = Dashboard::new(config);
+        assert_eq!(dashboard.load_preferences("analyst-1").unwrap().get("theme").unwrap(), "light");
+        
+        // Les préférences fournies à la connexion priment sur celles enregistrées
+        dashboard.connect_user(user(HashMap::from([("layout".to_string(), "list".to_string())]))).unwrap();
+        let preferences = dashboard.get_user("analyst-1").unwrap().preferences;
+        assert_eq!(preferences.get("theme").unwrap(), "light");
+        assert_eq!(preferences.get("layout").unwrap(), "list");
+        assert_eq!(dashboard.get_stats().connected_users, 1);
+        
+        assert!(dashboard.load_preferences("../analyst-1").is_err());
+        assert!(dashboard.load_preferences("unknown").unwrap().is_empty());
+        
+        fs::remove_dir_all(&directory).unwrap();
+    }
+    
+    #[test]
+    fn test_reset_stats_keeps_state() {
+        let config = DashboardConfig::default();
+        let mut dashboard = Dashboard::new(config);
+        dashboard.start().unwrap();
+        
+        dashboard
+            .process_threat_for_visualization("threat-1", "PortScan", 2, "192.168.1.100", "192.168.1.1")
+            .unwrap();
+        assert_eq!(dashboard.get_stats().processed_events, 1);
+        
+        dashboard.reset_stats();
+        
+        assert_eq!(dashboard.get_stats().processed_events, 0);
+        assert_eq!(dashboard.get_state(), DashboardState::Running);
+    }
+    
+    #[test]
+    fn test_uptime_restarts_with_dashboard() {
+        let config = DashboardConfig::default();
+        let mut dashboard = Dashboard::new(config);
+        
+        dashboard.start().unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(dashboard.get_stats().uptime_seconds >= 1);
+        
+        // Un redémarrage remet l'horloge à zéro
+        dashboard.stop().unwrap();
+        assert_eq!(dashboard.get_stats().uptime_seconds, 0);
+        dashboard.start().unwrap();
+        assert_eq!(dashboard.get_stats().uptime_seconds, 0);
+    }
+    
+    /// Notificateur de test : transmet les identifiants des détections reçues
+    struct RecordingNotifier {
+        received: mpsc::Sender<String>,
+    }
+    
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, event: &DetectionEvent) -> Result<(), String> {
+            self.received.send(event.id.clone()).map_err(|e| e.to_string())
+        }
+    }
+    
+    #[test]
+    fn test_detections_are_notified() {
+        let detection = |id: &str| LiveEvent::Detection(DetectionEvent {
+            id: id.to_string(),
+            timestamp: SystemTime::now(),
+            anomaly_score: 0.92,
+            decision: crate::neurofirewall::FirewallDecision::Quarantine,
+            related_packets: Vec::new(),
+            trigger_features: Vec::new(),
+            feature_scores: Vec::new(),
+            description: String::from("Anomalie détectée"),
+        });
+        
+        let (sender, received) = mpsc::channel();
+        let next = || received.recv_timeout(Duration::from_secs(5)).unwrap();
+        let mut dashboard = Dashboard::new(DashboardConfig::default());
+        dashboard.set_notifier(Box::new(RecordingNotifier { received: sender.clone() }));
+        
+        dashboard.publish_event(detection("event-1"));
+        dashboard.publish_event(detection("event-2"));
+        assert_eq!((next(), next()), ("event-1".to_string(), "event-2".to_string()));
+        
+        // Sans notifications en temps réel, rien n'est transmis
+        let config = DashboardConfig { enable_realtime_notifications: false, ..Default::default() };
+        let mut silent = Dashboard::new(config);
+        silent.set_notifier(Box::new(RecordingNotifier { received: sender }));
+        silent.publish_event(detection("event-3"));
+        dashboard.publish_event(detection("event-4"));
+        assert_eq!(next(), "event-4");
+        assert!(received.recv_timeout(Duration::from_millis(100)).is_err());
+        assert_eq!(dashboard.get_stats().dropped_notifications, 0);
+        
+        // Un webhook invalide empêche le démarrage
+        let config = DashboardConfig {
+            alert_webhook_url: Some("https://alerts.example.com/hook".to_string()),
+            ..Default::default()
+        };
+        assert!(Dashboard::new(config).start().is_err());
+    }
+    
+    #[test]
+    fn test_targets_grouped_by_subnet() {
+        let dashboard = Dashboard::new(DashboardConfig::default());
+        let targets = ["192.168.1.10", "192.168.1.20", "192.168.1.30", "10.0.0.5", "serveur-interne"];
+        
+        let targets_of = |scene: &VisualizationScene| -> Vec<VisualizationElement> {
+            scene
+                .elements
+                .values()
+                .filter(|element| element.data.get("type").is_some_and(|kind| kind.starts_with("target")))
+                .cloned()
+                .collect()
+        };
+        
+        let scene = dashboard
+            .process_threat_targets_for_visualization("threat-subnet", "PortScan", 3, "203.0.113.9", &targets, true)
+            .unwrap();
+        let grouped = targets_of(&scene);
+        assert_eq!(grouped.len(), 3);
+        
+        let clusters: Vec<&VisualizationElement> = grouped.iter().filter(|element| element.element_type == "cluster").collect();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].data["subnet"], "192.168.1.0/24");
+        assert_eq!(clusters[0].data["member_count"], "3");
+        assert_eq!(clusters[0].data["members"], "192.168.1.10,192.168.1.20,192.168.1.30");
+        
+        // Sans regroupement, chaque cible a son propre nœud
+        let scene = dashboard
+            .process_threat_targets_for_visualization("threat-subnet", "PortScan", 3, "203.0.113.9", &targets, false)
+            .unwrap();
+        let ungrouped = targets_of(&scene);
+        assert_eq!(ungrouped.len(), targets.len());
+        assert!(ungrouped.iter().all(|element| element.element_type == "node"));
+    }
+    
+    #[test]
+    fn test_detail_level_reduces_threat_scene() {
+        let targets = ["192.168.1.10", "192.168.1.20", "10.0.0.5"];
+        let build = |detail_level: u8, severity: u8| {
+            let config = DashboardConfig { detail_level, ..Default::default() };
+            Dashboard::new(config)
+                .process_threat_targets_for_visualization("threat-detail", "PortScan", severity, "203.0.113.9", &targets, false)
+                .unwrap()
+        };
+        
+        let full = build(5, 1);
+        assert_eq!(full.elements.len(), 5);
+        assert_eq!(full.metadata.get("suppressed_elements").unwrap(), "0");
+        
+        // Au niveau 1, une menace non critique est réduite à son élément de menace
+        let minimal = build(1, 1);
+        assert_eq!(minimal.elements.len(), 1);
+        let threat = minimal.elements.values().next().unwrap();
+        assert_eq!(threat.element_type, "threat");
+        assert_eq!(threat.data.get("target_count").unwrap(), "3");
+        assert_eq!(minimal.metadata.get("suppressed_elements").unwrap(), "4");
+        
+        // Une menace critique reste détaillée
+        assert_eq!(build(1, 4).elements.len(), 5);
+    }
 }
@@ -0,0 +1,283 @@
+//! # Notifications d'alerte
+//!
+//! Livraison des détections du NeuroFireWall hors du dashboard (journal, webhook), lorsque
+//! les notifications en temps réel sont activées.
+//!
+//! Les livraisons passent par une `NotificationQueue` bornée, vidée par un thread dédié : un
+//! webhook lent ou injoignable ne ralentit pas la diffusion des événements, et les alertes
+//! arrivant alors que la file est pleine sont abandonnées.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+use crate::neurofirewall::DetectionEvent;
+
+/// Canal de livraison des alertes
+pub trait Notifier: Send + Sync {
+    /// Transmet une détection ; une erreur n'interrompt pas la diffusion des événements
+    fn notify(&self, event: &DetectionEvent) -> Result<(), String>;
+}
+
+/// File de livraison des alertes vers un notificateur, vidée par un thread dédié
+///
+/// Le thread s'arrête lorsque la file est détruite.
+pub struct NotificationQueue {
+    sender: SyncSender<DetectionEvent>,
+}
+
+impl NotificationQueue {
+    /// Démarre le thread de livraison d'une file de `capacity` alertes en attente
+    pub fn spawn(notifier: Box<dyn Notifier>, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<DetectionEvent>(capacity);
+        thread::spawn(move || {
+            for event in receiver {
+                if let Err(e) = notifier.notify(&event) {
+                    log::warn!("Échec de la notification de l'alerte {}: {}", event.id, e);
+                }
+            }
+        });
+        
+        Self { sender }
+    }
+    
+    /// Met une alerte en attente de livraison sans bloquer
+    ///
+    /// Renvoie `false` si l'alerte est abandonnée (file pleine ou thread de livraison arrêté).
+    pub fn submit(&self, event: &DetectionEvent) -> bool {
+        match self.sender.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log::warn!("File de notification pleine, alerte {} abandonnée", event.id);
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                log::error!("Thread de notification arrêté, alerte {} abandonnée", event.id);
+                false
+            }
+        }
+    }
+}
+
+/// Notification par le journal applicatif (crate `log`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn notify(&self, event: &DetectionEvent) -> Result<(), String> {
+        log::warn!(
+            "Alerte {}: {:?} (score {:.2}) - {}",
+            event.id,
+            event.decision,
+            event.anomaly_score,
+            event.description
+        );
+        Ok(())
+    }
+}
+
+/// Notification par webhook : POST HTTP de l'événement sérialisé en JSON
+///
+/// Seules les URL `http://hôte[:port][/chemin]` sont prises en charge ; un webhook HTTPS
+/// doit passer par un relais local.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    host: String,
+    port: u16,
+    path: String,
+    timeout: Duration,
+}
+
+impl WebhookNotifier {
+    /// Délai par défaut de connexion, d'écriture et de lecture de la réponse
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+    
+    /// Crée un notificateur pour l'URL donnée
+    pub fn new(url: &str) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("URL de webhook non supportée (http:// attendu): {}", url))?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| format!("Port de webhook invalide: {}", url))?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(format!("Hôte de webhook manquant: {}", url));
+        }
+        
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        })
+    }
+    
+    /// Remplace le délai de connexion, d'écriture et de lecture
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, event: &DetectionEvent) -> Result<(), String> {
+        let body = serde_json::to_vec(event).map_err(|e| format!("Sérialisation de l'alerte impossible: {}", e))?;
+        
+        let address = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addresses| addresses.next())
+            .ok_or_else(|| format!("Adresse du webhook introuvable: {}:{}", self.host, self.port))?;
+        let mut stream = TcpStream::connect_timeout(&address, self.timeout)
+            .map_err(|e| format!("Connexion au webhook {} impossible: {}", address, e))?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(|e| e.to_string())?;
+        stream.set_write_timeout(Some(self.timeout)).map_err(|e| e.to_string())?;
+        
+        let header = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            self.host,
+            body.len()
+        );
+        stream
+            .write_all(header.as_bytes())
+            .and_then(|_| stream.write_all(&body))
+            .map_err(|e| format!("Envoi de l'alerte au webhook impossible: {}", e))?;
+        
+        let mut status_line = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut status_line)
+            .map_err(|e| format!("Réponse du webhook illisible: {}", e))?;
+        let status: u16 = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| format!("Réponse du webhook invalide: {:?}", status_line.trim_end()))?;
+        
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(format!("Le webhook a refusé l'alerte (statut {})", status))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::sync::mpsc::{Receiver, Sender};
+    use std::sync::Mutex;
+    use std::time::SystemTime;
+    
+    use crate::neurofirewall::FirewallDecision;
+    
+    fn detection(id: &str) -> DetectionEvent {
+        DetectionEvent {
+            id: id.to_string(),
+            timestamp: SystemTime::now(),
+            anomaly_score: 0.97,
+            decision: FirewallDecision::Block,
+            related_packets: vec!["packet-1".to_string()],
+            trigger_features: vec!["payload_entropy".to_string()],
+            feature_scores: vec![0.8],
+            description: "Anomalie détectée avec un score de 0.97".to_string(),
+        }
+    }
+    
+    /// Notificateur signalant chaque alerte prise en charge, puis bloqué jusqu'à ce que le test
+    /// autorise sa livraison
+    struct GatedNotifier {
+        started: Sender<String>,
+        gate: Mutex<Receiver<()>>,
+    }
+    
+    impl Notifier for GatedNotifier {
+        fn notify(&self, event: &DetectionEvent) -> Result<(), String> {
+            self.started.send(event.id.clone()).map_err(|e| e.to_string())?;
+            self.gate.lock().unwrap().recv().map_err(|e| e.to_string())
+        }
+    }
+    
+    #[test]
+    fn test_slow_notifier_does_not_block_submissions() {
+        let (started_tx, started) = mpsc::channel();
+        let (gate, gate_rx) = mpsc::channel();
+        let queue = NotificationQueue::spawn(
+            Box::new(GatedNotifier { started: started_tx, gate: Mutex::new(gate_rx) }),
+            2,
+        );
+        let next_started = || started.recv_timeout(Duration::from_secs(5)).unwrap();
+        
+        // Le thread de livraison reste bloqué sur la première alerte : la file en accepte deux
+        // de plus, la suivante est abandonnée sans attendre
+        assert!(queue.submit(&detection("event-0")));
+        assert_eq!(next_started(), "event-0");
+        assert!(queue.submit(&detection("event-1")));
+        assert!(queue.submit(&detection("event-2")));
+        assert!(!queue.submit(&detection("event-3")));
+        
+        gate.send(()).unwrap();
+        assert_eq!(next_started(), "event-1");
+        gate.send(()).unwrap();
+        assert_eq!(next_started(), "event-2");
+        gate.send(()).unwrap();
+        
+        // Une place s'est libérée
+        assert!(queue.submit(&detection("event-4")));
+        assert_eq!(next_started(), "event-4");
+        gate.send(()).unwrap();
+    }
+    
+    #[test]
+    fn test_webhook_posts_serialized_event() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(b"HTTP/1.1 204 No Content\r\n\r\n").unwrap();
+            
+            (request_line, body)
+        });
+        
+        let event = detection("event-webhook");
+        
+        let notifier = WebhookNotifier::new(&format!("http://127.0.0.1:{}/alerts", port)).unwrap();
+        notifier.notify(&event).unwrap();
+        
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /alerts HTTP/1.1"));
+        let received: DetectionEvent = serde_json::from_slice(&body).unwrap();
+        assert_eq!(received.id, event.id);
+        
+        assert!(WebhookNotifier::new("https://example.com/alerts").is_err());
+        assert!(WebhookNotifier::new("http://:8080/").is_err());
+    }
+}
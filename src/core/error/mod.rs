@@ -0,0 +1,162 @@
+//! # Erreurs ICARUS
+//! 
+//! Les modules renvoient leurs erreurs sous forme de `String` ; `IcarusError` les
+//! rattache au sous-système d'origine afin qu'une fonction enchaînant plusieurs
+//! modules puisse propager les échecs avec `?` :
+//! 
+//! ```ignore
+//! let (decision, _) = firewall.analyze_packet(packet).map_err(IcarusError::Firewall)?;
+//! let plan = aegis.process_threat_event(event).map_err(IcarusError::Aegis)?;
+//! ```
+
+use std::fmt;
+use std::io;
+
+/// Erreur unifiée des modules ICARUS
+#[derive(Debug)]
+pub enum IcarusError {
+    /// Erreur de l'orchestrateur AEGIS
+    Aegis(String),
+    /// Erreur cryptographique (QuantumVault)
+    Crypto(String),
+    /// Erreur du NeuroFireWall
+    Firewall(String),
+    /// Erreur de WarpShield
+    WarpShield(String),
+    /// Erreur du dashboard
+    Dashboard(String),
+    /// Erreur du moteur neuronal
+    NeuralNet(String),
+    /// Erreur de configuration
+    Config(String),
+    /// Erreur d'entrée/sortie
+    Io(io::Error),
+}
+
+impl IcarusError {
+    /// Nom du sous-système à l'origine de l'erreur
+    pub fn subsystem(&self) -> &'static str {
+        match self {
+            IcarusError::Aegis(_) => "AEGIS",
+            IcarusError::Crypto(_) => "QuantumVault",
+            IcarusError::Firewall(_) => "NeuroFireWall",
+            IcarusError::WarpShield(_) => "WarpShield",
+            IcarusError::Dashboard(_) => "Dashboard",
+            IcarusError::NeuralNet(_) => "NeuralNet",
+            IcarusError::Config(_) => "Configuration",
+            IcarusError::Io(_) => "E/S",
+        }
+    }
+}
+
+impl fmt::Display for IcarusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcarusError::Aegis(message)
+            | IcarusError::Crypto(message)
+            | IcarusError::Firewall(message)
+            | IcarusError::WarpShield(message)
+            | IcarusError::Dashboard(message)
+            | IcarusError::NeuralNet(message)
+            | IcarusError::Config(message) => write!(f, "{}: {}", self.subsystem(), message),
+            IcarusError::Io(error) => write!(f, "{}: {}", self.subsystem(), error),
+        }
+    }
+}
+
+impl std::error::Error for IcarusError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IcarusError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for IcarusError {
+    fn from(error: io::Error) -> Self {
+        IcarusError::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+    
+    use crate::aegis::{AegisConfig, AegisOrchestrator, ThreatEvent, ThreatSeverity, ThreatType};
+    use crate::neurofirewall::{NetworkPacket, NeuroFireWall, NeuroFireWallConfig, TrafficType};
+    
+    /// Analyse un paquet puis transmet l'alerte correspondante à AEGIS
+    fn escalate_packet(
+        firewall: &NeuroFireWall,
+        aegis: &AegisOrchestrator,
+        packet: NetworkPacket,
+    ) -> Result<(), IcarusError> {
+        let source = packet.source_ip.clone();
+        let target = packet.destination_ip.clone();
+        firewall.analyze_packet(packet).map_err(IcarusError::Firewall)?;
+        
+        let event = ThreatEvent {
+            id: "threat-pipeline".to_string(),
+            threat_type: ThreatType::PortScan,
+            severity: ThreatSeverity::Medium,
+            confidence: 0.8,
+            source,
+            target,
+            timestamp: SystemTime::now(),
+            metadata: HashMap::new(),
+        };
+        aegis.process_threat_event(event).map_err(IcarusError::Aegis)?;
+        
+        Ok(())
+    }
+    
+    fn create_test_packet() -> NetworkPacket {
+        NetworkPacket {
+            id: "packet-pipeline".to_string(),
+            source_ip: "192.168.1.100".to_string(),
+            destination_ip: "192.168.1.1".to_string(),
+            source_port: 12345,
+            destination_port: 80,
+            protocol: "TCP".to_string(),
+            size: 512,
+            timestamp: SystemTime::now(),
+            traffic_type: TrafficType::Web,
+            payload_sample: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[test]
+    fn test_errors_propagate_across_modules() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        
+        // NeuroFireWall non initialisé : l'échec provient du pare-feu
+        let error = escalate_packet(&firewall, &aegis, create_test_packet()).unwrap_err();
+        assert!(matches!(error, IcarusError::Firewall(_)));
+        
+        // AEGIS non initialisé : l'échec provient de l'orchestrateur
+        firewall.initialize().unwrap();
+        let error = escalate_packet(&firewall, &aegis, create_test_packet()).unwrap_err();
+        assert!(matches!(error, IcarusError::Aegis(_)));
+        assert!(error.to_string().starts_with("AEGIS: "));
+        
+        aegis.initialize().unwrap();
+        assert!(escalate_packet(&firewall, &aegis, create_test_packet()).is_ok());
+    }
+    
+    #[test]
+    fn test_io_error_conversion() {
+        fn read_missing() -> Result<String, IcarusError> {
+            Ok(std::fs::read_to_string("/nonexistent/icarus.toml")?)
+        }
+        
+        let error = read_missing().unwrap_err();
+        assert!(matches!(error, IcarusError::Io(_)));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,79 @@
+//! # Identifiants
+//! 
+//! Les modules qui créent des objets identifiés (plans AEGIS, environnements, attaques et
+//! signatures WarpShield, événements de détection du NeuroFireWall, scènes du dashboard)
+//! obtiennent leurs identifiants via le trait `IdGenerator` plutôt que `Uuid::new_v4()`.
+//! En production, `RandomIdGenerator` produit des identifiants `<préfixe>-<uuid>` ; les tests
+//! injectent un `SequentialIdGenerator` pour obtenir des identifiants reproductibles
+//! (`env-0`, `env-1`...).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Source d'identifiants
+pub trait IdGenerator: Send + Sync {
+    /// Nouvel identifiant, de la forme `<prefix>-<suffixe>`
+    fn next_id(&self, prefix: &str) -> String;
+}
+
+/// Générateur partagé entre les composants d'un module
+pub type SharedIdGenerator = Arc<dyn IdGenerator>;
+
+/// Générateur d'identifiants aléatoires (UUID v4)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+    fn next_id(&self, prefix: &str) -> String {
+        format!("{}-{}", prefix, uuid::Uuid::new_v4())
+    }
+}
+
+/// Générateur aléatoire partagé (valeur par défaut des modules)
+pub fn random_ids() -> SharedIdGenerator {
+    Arc::new(RandomIdGenerator)
+}
+
+/// Générateur d'identifiants séquentiels, numérotés à partir de 0 pour chaque préfixe
+///
+/// Les compteurs étant indépendants, la création d'objets d'un autre type ne décale pas la
+/// numérotation : le premier environnement créé est toujours `env-0`.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    counters: Mutex<HashMap<String, u64>>,
+}
+
+impl SequentialIdGenerator {
+    /// Crée un générateur dont tous les compteurs sont à 0
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self, prefix: &str) -> String {
+        let mut counters = self.counters.lock().unwrap();
+        let counter = counters.entry(prefix.to_string()).or_insert(0);
+        let id = format!("{}-{}", prefix, counter);
+        *counter += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sequential_ids_are_numbered_per_prefix() {
+        let ids = SequentialIdGenerator::new();
+        assert_eq!(ids.next_id("env"), "env-0");
+        assert_eq!(ids.next_id("sig"), "sig-0");
+        assert_eq!(ids.next_id("env"), "env-1");
+        
+        let random = RandomIdGenerator;
+        let id = random.next_id("env");
+        assert!(id.starts_with("env-"));
+        assert_ne!(id, random.next_id("env"));
+    }
+}
@@ -0,0 +1,126 @@
+//! # Journalisation ICARUS
+//! 
+//! Les modules émettent leurs événements via le crate `log`, filtrés par le champ
+//! `log_level` de leur configuration :
+//! 
+//! | `log_level` | Niveau         |
+//! |-------------|----------------|
+//! | 0           | aucun          |
+//! | 1           | erreurs        |
+//! | 2           | avertissements |
+//! | 3           | info           |
+//! | 4           | debug          |
+
+use std::fmt;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Convertit un niveau de journalisation numérique en filtre `log`
+pub fn level_filter(level: u8) -> LevelFilter {
+    match level {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        _ => LevelFilter::Debug,
+    }
+}
+
+/// Indique si un enregistrement de niveau `level` doit être émis pour un module
+/// configuré avec `log_level`
+pub fn enabled(log_level: u8, level: Level) -> bool {
+    level <= level_filter(log_level)
+}
+
+/// Émet un enregistrement si le niveau de journalisation du module le permet
+macro_rules! module_log {
+    ($log_level:expr, $level:expr, $($arg:tt)+) => {
+        if $crate::logging::enabled($log_level, $level) {
+            $crate::logging::emit(module_path!(), $level, format_args!($($arg)+));
+        }
+    };
+}
+
+pub(crate) use module_log;
+
+/// Transmet un enregistrement au journal global
+pub fn emit(target: &str, level: Level, args: fmt::Arguments) {
+    #[cfg(test)]
+    capture::record(level, &args);
+    
+    log::log!(target: target, level, "{}", args);
+}
+
+/// Journal écrivant les enregistrements sur la sortie d'erreur
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+    
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+    
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Installe le journal global avec le niveau maximal donné
+///
+/// Si un journal est déjà installé (par Rocket notamment), il est conservé et seul
+/// le niveau maximal est appliqué.
+pub fn init_logging(level: u8) {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(level_filter(level));
+}
+
+/// Capture des enregistrements émis par les modules sur le thread courant
+#[cfg(test)]
+pub mod capture {
+    use std::cell::RefCell;
+    use std::fmt;
+    
+    use log::Level;
+    
+    thread_local! {
+        static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+    
+    /// Enregistre un événement émis sur le thread courant
+    pub(super) fn record(level: Level, args: &fmt::Arguments) {
+        RECORDS.with(|records| records.borrow_mut().push((level, args.to_string())));
+    }
+    
+    /// Vide les enregistrements capturés sur le thread courant
+    pub fn start() {
+        RECORDS.with(|records| records.borrow_mut().clear());
+    }
+    
+    /// Renvoie et vide les enregistrements capturés sur le thread courant
+    pub fn take() -> Vec<(Level, String)> {
+        RECORDS.with(|records| records.borrow_mut().drain(..).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_level_filter_mapping() {
+        assert_eq!(level_filter(0), LevelFilter::Off);
+        assert_eq!(level_filter(1), LevelFilter::Error);
+        assert_eq!(level_filter(2), LevelFilter::Warn);
+        assert_eq!(level_filter(3), LevelFilter::Info);
+        assert_eq!(level_filter(4), LevelFilter::Debug);
+        
+        assert!(!enabled(0, Level::Error));
+        assert!(enabled(3, Level::Info));
+        assert!(!enabled(3, Level::Debug));
+    }
+}
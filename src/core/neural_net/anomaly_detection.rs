@@ -15,6 +15,8 @@
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 
+use crate::clock::{system_clock, SharedClock};
+
 /// Configuration du détecteur d'anomalies
 #[derive(Debug, Clone)]
 pub struct AnomalyDetectionConfig {
@@ -87,22 +89,38 @@ pub struct AnomalyDetector {
     // adaptive_threshold_manager: AdaptiveThresholdManager,
     baseline_established: bool,
     learning_start_time: Option<SystemTime>,
+    /// Décalage du seuil de base appris des retours d'opérateur (seuils adaptatifs)
+    threshold_adjustment: f32,
+    /// Source de temps (période d'apprentissage, horodatage des détections)
+    clock: SharedClock,
 }
 
 impl AnomalyDetector {
+    /// Pas d'ajustement du seuil par appel à `adjust_thresholds`
+    const THRESHOLD_STEP: f32 = 0.01;
+    /// Décalage maximal du seuil, dans un sens comme dans l'autre
+    const MAX_THRESHOLD_ADJUSTMENT: f32 = 0.2;
+    
     /// Crée une nouvelle instance du détecteur d'anomalies
     pub fn new(config: AnomalyDetectionConfig) -> Self {
         Self {
             config,
             baseline_established: false,
             learning_start_time: None,
+            threshold_adjustment: 0.0,
+            clock: system_clock(),
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
     
+    /// Remplace la source de temps (horloge simulée dans les tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+    
     /// Démarre la période d'apprentissage pour établir la ligne de base
     pub fn start_baseline_learning(&mut self) {
-        self.learning_start_time = Some(SystemTime::now());
+        self.learning_start_time = Some(self.clock.now());
         self.baseline_established = false;
         
         // Cette fonction sera implémentée complètement dans les versions futures
@@ -111,7 +129,7 @@ impl AnomalyDetector {
     /// Vérifie si la période d'apprentissage est terminée
     pub fn is_baseline_established(&self) -> bool {
         if let Some(start_time) = self.learning_start_time {
-            if let Ok(elapsed) = start_time.elapsed() {
+            if let Ok(elapsed) = self.clock.now().duration_since(start_time) {
                 return elapsed >= Duration::from_secs(self.config.baseline_learning_period_hours as u64 * 3600);
             }
         }
@@ -164,10 +182,15 @@ impl AnomalyDetector {
             anomaly_type,
             confidence,
             contributing_features,
-            timestamp: SystemTime::now(),
+            timestamp: self.clock.now(),
         }
     }
     
+    /// Seuil appliqué hors contexte, ajustements compris
+    pub fn current_threshold(&self) -> f32 {
+        self.get_current_threshold(None)
+    }
+    
     /// Obtient le seuil actuel pour la détection d'anomalies
     fn get_current_threshold(&self, _context: Option<&HashMap<String, Vec<u8>>>) -> f32 {
         // Cette fonction sera implémentée dans les versions futures
@@ -175,8 +198,7 @@ impl AnomalyDetector {
         
         if self.config.adaptive_thresholds {
             // Dans les versions futures, ce seuil sera ajusté en fonction du contexte
-            // et de l'historique des détections
-            self.config.base_threshold * self.config.sensitivity
+            ((self.config.base_threshold + self.threshold_adjustment) * self.config.sensitivity).clamp(0.0, 1.0)
         } else {
             self.config.base_threshold
         }
@@ -188,10 +210,24 @@ impl AnomalyDetector {
         // Pour l'instant, elle ne fait rien
     }
     
-    /// Ajuste les seuils en fonction des retours (feedback)
-    pub fn adjust_thresholds(&mut self, _false_positive_rate: f32, _false_negative_rate: f32) {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle ne fait rien
+    /// Ajuste les seuils en fonction des taux d'erreur mesurés sur les retours d'opérateur
+    ///
+    /// Le seuil monte d'un pas lorsque les faux positifs dépassent la cible et l'emportent sur
+    /// les faux négatifs, et descend d'un pas lorsque les faux négatifs l'emportent. Sans effet
+    /// si les seuils adaptatifs sont désactivés.
+    pub fn adjust_thresholds(&mut self, false_positive_rate: f32, false_negative_rate: f32) {
+        if !self.config.adaptive_thresholds {
+            return;
+        }
+        
+        if false_positive_rate > self.config.target_false_positive_rate && false_positive_rate >= false_negative_rate {
+            self.threshold_adjustment += Self::THRESHOLD_STEP;
+        } else if false_negative_rate > false_positive_rate {
+            self.threshold_adjustment -= Self::THRESHOLD_STEP;
+        }
+        self.threshold_adjustment = self
+            .threshold_adjustment
+            .clamp(-Self::MAX_THRESHOLD_ADJUSTMENT, Self::MAX_THRESHOLD_ADJUSTMENT);
     }
 }
 
@@ -199,6 +235,10 @@ impl AnomalyDetector {
 mod tests {
     use super::*;
     
+    use std::sync::Arc;
+    
+    use crate::clock::{Clock, MockClock};
+    
     #[test]
     fn test_anomaly_detector_creation() {
         let config = AnomalyDetectionConfig::default();
@@ -236,4 +276,49 @@ mod tests {
         assert!(detector.learning_start_time.is_some());
         assert!(!detector.is_baseline_established());
     }
+    
+    #[test]
+    fn test_baseline_established_after_learning_period() {
+        let clock = Arc::new(MockClock::new(SystemTime::now()));
+        let config = AnomalyDetectionConfig { baseline_learning_period_hours: 2, ..Default::default() };
+        let mut detector = AnomalyDetector::new(config);
+        detector.set_clock(clock.clone());
+        
+        detector.start_baseline_learning();
+        clock.advance(Duration::from_secs(2 * 3600 - 1));
+        assert!(!detector.is_baseline_established());
+        
+        clock.advance(Duration::from_secs(1));
+        assert!(detector.is_baseline_established());
+        assert_eq!(detector.detect_anomalies(&[0.5], None).timestamp, clock.now());
+    }
+    
+    #[test]
+    fn test_adjust_thresholds_from_error_rates() {
+        let mut detector = AnomalyDetector::new(AnomalyDetectionConfig::default());
+        let initial = detector.get_current_threshold(None);
+        
+        // Trop de faux positifs : le seuil monte
+        detector.adjust_thresholds(0.25, 0.05);
+        let raised = detector.get_current_threshold(None);
+        assert!(raised > initial);
+        
+        // Trop de faux négatifs : le seuil redescend
+        detector.adjust_thresholds(0.0, 0.5);
+        detector.adjust_thresholds(0.0, 0.5);
+        assert!(detector.get_current_threshold(None) < initial);
+        
+        // Le décalage est borné
+        for _ in 0..100 {
+            detector.adjust_thresholds(0.0, 1.0);
+        }
+        let floor = detector.get_current_threshold(None);
+        assert!((floor - (0.85 - AnomalyDetector::MAX_THRESHOLD_ADJUSTMENT)).abs() < 1e-5);
+        
+        // Sans seuils adaptatifs, les retours sont ignorés
+        let config = AnomalyDetectionConfig { adaptive_thresholds: false, ..Default::default() };
+        let mut fixed = AnomalyDetector::new(config);
+        fixed.adjust_thresholds(0.5, 0.0);
+        assert_eq!(fixed.get_current_threshold(None), 0.85);
+    }
 }
@@ -79,6 +79,11 @@ impl FeatureExtractor {
         }
     }
     
+    /// Nombre de caractéristiques produites par flux
+    pub fn feature_dimension(&self) -> usize {
+        self.config.feature_dimension
+    }
+    
     /// Extrait les caractéristiques d'un flux réseau
     pub fn extract_features(&self, flow_data: &[u8]) -> Vec<f32> {
         let mut features = Vec::with_capacity(self.config.feature_dimension);
@@ -122,20 +127,7 @@ impl FeatureExtractor {
         
         let std_dev = variance.sqrt();
         
-        // Histogramme simple des valeurs d'octets
-        let mut histogram = [0u32; 256];
-        for &byte in flow_data {
-            histogram[byte as usize] += 1;
-        }
-        
-        // Calcul de l'entropie
-        let mut entropy = 0.0;
-        for &count in &histogram {
-            if count > 0 {
-                let p = count as f32 / flow_data.len() as f32;
-                entropy -= p * p.log2();
-            }
-        }
+        let entropy = shannon_entropy(flow_data);
         
         vec![mean, variance, std_dev, entropy]
     }
@@ -207,6 +199,30 @@ impl FeatureExtractor {
     }
 }
 
+/// Calcule l'entropie de Shannon (en bits par octet, entre 0 et 8) d'une séquence d'octets
+pub fn shannon_entropy(data: &[u8]) -> f32 {
+    if data.is_empty() {
+        return 0.0;
+    }
+    
+    // Histogramme simple des valeurs d'octets
+    let mut histogram = [0u32; 256];
+    for &byte in data {
+        histogram[byte as usize] += 1;
+    }
+    
+    // Calcul de l'entropie
+    let mut entropy = 0.0;
+    for &count in &histogram {
+        if count > 0 {
+            let p = count as f32 / data.len() as f32;
+            entropy -= p * p.log2();
+        }
+    }
+    
+    entropy
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,7 +230,7 @@ mod tests {
     #[test]
     fn test_feature_extractor_creation() {
         let config = FeatureExtractionConfig::default();
-        let extractor = FeatureExtractor::new(config);
+        let extractor = FeatureExtractor::new(config.clone());
         
         assert_eq!(extractor.config.feature_dimension, 128);
     }
@@ -222,7 +238,7 @@ mod tests {
     #[test]
     fn test_extract_features() {
         let config = FeatureExtractionConfig::default();
-        let extractor = FeatureExtractor::new(config);
+        let extractor = FeatureExtractor::new(config.clone());
         
         // Données fictives pour le test
         let flow_data = vec![0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
@@ -236,7 +252,7 @@ mod tests {
     fn test_statistical_features() {
         let mut config = FeatureExtractionConfig::default();
         config.enable_statistical_features = true;
-        let extractor = FeatureExtractor::new(config);
+        let extractor = FeatureExtractor::new(config.clone());
         
         // Données fictives pour le test
         let flow_data = vec![0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
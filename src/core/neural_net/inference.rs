@@ -12,9 +12,18 @@
 //! - Parallélisation des opérations d'inférence
 //! - Quantification des modèles pour performance maximale
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use super::optimization::{prune_weights, quantize_weights};
+use crate::bench::percentile;
+
+/// Taille du vecteur de sortie du modèle
+const OUTPUT_SIZE: usize = 10;
+/// Nombre de latences récentes conservées pour le calcul des centiles
+const LATENCY_WINDOW: usize = 1024;
+
 /// Configuration du moteur d'inférence
 #[derive(Debug, Clone)]
 pub struct InferenceConfig {
@@ -30,6 +39,10 @@ pub struct InferenceConfig {
     pub enable_simd: bool,
     /// Seuil de latence maximale acceptable (en microsecondes)
     pub max_latency_us: u64,
+    /// Exiger également que le 99e centile des latences récentes respecte `max_latency_us`
+    pub enforce_p99_latency: bool,
+    /// Fraction des poids élagués lors de l'optimisation pour les performances (0.0 - 1.0)
+    pub pruning_sparsity: f32,
 }
 
 impl Default for InferenceConfig {
@@ -41,6 +54,8 @@ impl Default for InferenceConfig {
             num_threads: 4,
             enable_simd: true,
             max_latency_us: 200,
+            enforce_p99_latency: false,
+            pruning_sparsity: 0.0,
         }
     }
 }
@@ -54,6 +69,12 @@ pub struct InferenceStats {
     pub min_latency_us: u64,
     /// Latence maximale observée (en microsecondes)
     pub max_latency_us: u64,
+    /// Latence médiane des `LATENCY_WINDOW` dernières inférences (en microsecondes)
+    pub p50_latency_us: u64,
+    /// 95e centile des latences récentes (en microsecondes)
+    pub p95_latency_us: u64,
+    /// 99e centile des latences récentes (en microsecondes)
+    pub p99_latency_us: u64,
     /// Nombre d'inférences effectuées
     pub inference_count: u64,
     /// Taux de succès du cache (pourcentage)
@@ -88,10 +109,36 @@ pub enum InferenceDevice {
     NPU,
 }
 
+/// Détecte l'appareil d'inférence disponible
+///
+/// La détection GPU n'est compilée qu'avec la fonctionnalité `gpu` ; sans elle, le CPU
+/// est toujours renvoyé.
+pub fn detect_device() -> InferenceDevice {
+    #[cfg(feature = "gpu")]
+    {
+        let gpu_nodes = ["/dev/nvidia0", "/dev/dri/renderD128", "/dev/kfd"];
+        if gpu_nodes.iter().any(|node| std::path::Path::new(node).exists()) {
+            return InferenceDevice::GPU;
+        }
+    }
+    
+    InferenceDevice::CPU
+}
+
 /// Moteur d'inférence principal
 pub struct InferenceEngine {
     config: InferenceConfig,
     stats: InferenceStats,
+    /// Latences des dernières inférences (au plus `LATENCY_WINDOW`), de la plus ancienne à la plus récente
+    recent_latencies: VecDeque<u64>,
+    /// Appareil effectivement utilisé pour l'inférence
+    device: InferenceDevice,
+    /// Indique si le GPU demandé était indisponible et que le CPU est utilisé à la place
+    gpu_fallback: bool,
+    /// Poids de sortie en pleine précision (référence pour la restauration)
+    full_precision_weights: Vec<f32>,
+    /// Poids de sortie utilisés pour l'inférence
+    weights: Vec<f32>,
     // Les champs suivants seront implémentés dans les versions futures
     // model_cache: LruCache<Vec<u8>, Vec<f32>>,
     // thread_pool: ThreadPool,
@@ -105,18 +152,45 @@ impl InferenceEngine {
             avg_latency_us: 0.0,
             min_latency_us: u64::MAX,
             max_latency_us: 0,
+            p50_latency_us: 0,
+            p95_latency_us: 0,
+            p99_latency_us: 0,
             inference_count: 0,
             cache_hit_rate: 0.0,
             memory_usage_mb: 0.0,
         };
         
+        let (device, gpu_fallback) = match (config.use_gpu, detect_device()) {
+            (true, InferenceDevice::CPU) => {
+                log::warn!("GPU demandé mais indisponible, inférence sur CPU");
+                (InferenceDevice::CPU, true)
+            }
+            (true, device) => (device, false),
+            (false, _) => (InferenceDevice::CPU, false),
+        };
+        
         Self {
             config,
             stats,
+            recent_latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+            device,
+            gpu_fallback,
+            full_precision_weights: vec![1.0; OUTPUT_SIZE],
+            weights: vec![1.0; OUTPUT_SIZE],
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
     
+    /// Appareil effectivement utilisé pour l'inférence
+    pub fn device(&self) -> &InferenceDevice {
+        &self.device
+    }
+    
+    /// Indique si l'inférence s'exécute sur CPU faute de GPU disponible
+    pub fn gpu_fallback(&self) -> bool {
+        self.gpu_fallback
+    }
+    
     /// Exécute l'inférence sur un vecteur de caractéristiques
     pub fn run_inference(&mut self, features: &[f32]) -> InferenceResult {
         let start_time = Instant::now();
@@ -128,13 +202,12 @@ impl InferenceEngine {
         std::thread::sleep(Duration::from_micros(150));
         
         // Génération d'un résultat fictif
-        let output_size = 10; // Taille arbitraire pour la démonstration
-        let mut output = Vec::with_capacity(output_size);
+        let mut output = Vec::with_capacity(OUTPUT_SIZE);
         
-        for i in 0..output_size {
+        for (i, weight) in self.weights.iter().enumerate() {
             // Valeurs fictives basées sur les caractéristiques d'entrée
             let output_value = if !features.is_empty() {
-                (features[i % features.len()] * 2.0 - 1.0).tanh()
+                (features[i % features.len()] * 2.0 - 1.0).tanh() * weight
             } else {
                 0.0
             };
@@ -150,11 +223,7 @@ impl InferenceEngine {
             output,
             inference_time_us,
             cache_hit: false,
-            device_used: if self.config.use_gpu {
-                InferenceDevice::GPU
-            } else {
-                InferenceDevice::CPU
-            },
+            device_used: self.device.clone(),
         }
     }
     
@@ -170,6 +239,10 @@ impl InferenceEngine {
         if latency_us > self.stats.max_latency_us {
             self.stats.max_latency_us = latency_us;
         }
+        if self.recent_latencies.len() == LATENCY_WINDOW {
+            self.recent_latencies.pop_front();
+        }
+        self.recent_latencies.push_back(latency_us);
         
         // Calcul de la moyenne mobile
         let old_weight = (self.stats.inference_count - 1) as f64 / self.stats.inference_count as f64;
@@ -183,25 +256,44 @@ impl InferenceEngine {
     }
     
     /// Obtient les statistiques actuelles d'inférence
+    ///
+    /// Les centiles sont calculés à la demande sur les `LATENCY_WINDOW` dernières latences.
     pub fn get_stats(&self) -> InferenceStats {
-        self.stats.clone()
+        let mut latencies: Vec<u64> = self.recent_latencies.iter().copied().collect();
+        latencies.sort_unstable();
+        
+        InferenceStats {
+            p50_latency_us: percentile(&latencies, 50.0),
+            p95_latency_us: percentile(&latencies, 95.0),
+            p99_latency_us: percentile(&latencies, 99.0),
+            ..self.stats.clone()
+        }
     }
     
     /// Vérifie si le moteur d'inférence respecte les contraintes de latence
+    ///
+    /// La latence moyenne doit respecter `max_latency_us`, ainsi que le 99e centile si
+    /// `enforce_p99_latency` est activé.
     pub fn meets_latency_requirements(&self) -> bool {
-        self.stats.avg_latency_us <= self.config.max_latency_us as f64
+        let bound = self.config.max_latency_us;
+        self.stats.avg_latency_us <= bound as f64
+            && (!self.config.enforce_p99_latency || self.get_stats().p99_latency_us <= bound)
     }
     
     /// Optimise le moteur d'inférence pour les performances
+    ///
+    /// Les poids en pleine précision sont élagués selon `pruning_sparsity` puis quantifiés
+    /// sur `quantization_bits` bits.
     pub fn optimize_for_performance(&mut self) {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle ne fait rien
+        let pruned = prune_weights(&self.full_precision_weights, self.config.pruning_sparsity);
+        self.weights = quantize_weights(&pruned, self.config.quantization_bits);
     }
     
     /// Optimise le moteur d'inférence pour la précision
+    ///
+    /// Restaure les poids en pleine précision.
     pub fn optimize_for_accuracy(&mut self) {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle ne fait rien
+        self.weights = self.full_precision_weights.clone();
     }
 }
 
@@ -250,4 +342,77 @@ mod tests {
         // La latence simulée devrait être inférieure à 1000μs
         assert!(engine.meets_latency_requirements());
     }
+    
+    #[cfg(not(feature = "gpu"))]
+    #[test]
+    fn test_gpu_request_falls_back_to_cpu() {
+        let config = InferenceConfig { use_gpu: true, ..Default::default() };
+        let mut engine = InferenceEngine::new(config);
+        
+        assert_eq!(detect_device(), InferenceDevice::CPU);
+        assert_eq!(engine.device(), &InferenceDevice::CPU);
+        assert!(engine.gpu_fallback());
+        assert_eq!(engine.run_inference(&[0.5]).device_used, InferenceDevice::CPU);
+        
+        // Aucun repli lorsque le CPU est demandé explicitement
+        let engine = InferenceEngine::new(InferenceConfig { use_gpu: false, ..Default::default() });
+        assert!(!engine.gpu_fallback());
+    }
+    
+    #[test]
+    fn test_optimization_round_trip() {
+        let config = InferenceConfig { quantization_bits: 2, pruning_sparsity: 0.2, ..Default::default() };
+        let mut engine = InferenceEngine::new(config);
+        engine.full_precision_weights = (1..=OUTPUT_SIZE).map(|i| i as f32 / OUTPUT_SIZE as f32).collect();
+        engine.optimize_for_accuracy();
+        let original = engine.weights.clone();
+        
+        engine.optimize_for_performance();
+        assert_ne!(engine.weights, original);
+        // Sur 2 bits, seuls les niveaux -max, 0 et +max subsistent
+        assert!(engine.weights.iter().all(|&weight| weight == 0.0 || weight == 1.0));
+        assert_eq!(&engine.weights[..2], &[0.0, 0.0]);
+        
+        engine.optimize_for_accuracy();
+        assert_eq!(engine.weights, original);
+    }
+    
+    #[test]
+    fn test_latency_percentiles_expose_tail() {
+        let config = InferenceConfig { max_latency_us: 1000, ..Default::default() };
+        let mut engine = InferenceEngine::new(config);
+        
+        // 95 % d'inférences rapides, 4 % lentes et 1 % très lentes
+        for i in 0..1000 {
+            let latency_us = match i % 100 {
+                0 => 5000,
+                1..=4 => 400,
+                _ => 100,
+            };
+            engine.update_stats(latency_us, false);
+        }
+        
+        let stats = engine.get_stats();
+        assert!(stats.min_latency_us <= stats.p50_latency_us);
+        assert!(stats.p50_latency_us <= stats.p95_latency_us);
+        assert!(stats.p95_latency_us <= stats.p99_latency_us);
+        assert!(stats.p99_latency_us <= stats.max_latency_us);
+        assert_eq!(stats.p50_latency_us, 100);
+        assert_eq!(stats.p95_latency_us, 100);
+        assert_eq!(stats.p99_latency_us, 400);
+        
+        // La moyenne (161 µs) masque la queue de distribution que le 99e centile révèle
+        assert!(engine.meets_latency_requirements());
+        engine.config.max_latency_us = 300;
+        assert!(engine.meets_latency_requirements());
+        engine.config.enforce_p99_latency = true;
+        assert!(!engine.meets_latency_requirements());
+        
+        // Seules les `LATENCY_WINDOW` dernières latences sont prises en compte
+        for _ in 0..LATENCY_WINDOW {
+            engine.update_stats(50, false);
+        }
+        assert_eq!(engine.get_stats().p99_latency_us, 50);
+        assert_eq!(engine.get_stats().max_latency_us, 5000);
+    }
 }
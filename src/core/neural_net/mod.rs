@@ -21,11 +21,21 @@ mod anomaly_detection;
 mod model_training;
 mod optimization;
 
-use std::sync::Arc;
+pub use feature_extraction::{shannon_entropy, FeatureExtractionConfig, FeatureExtractor};
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
+use anomaly_detection::{AnomalyDetectionConfig, AnomalyDetector};
+use inference::{InferenceConfig, InferenceDevice, InferenceEngine};
+use transformer::{TransformerConfig, TransformerModel};
+
 /// Configuration du moteur neuronal
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NeuralNetConfig {
     /// Nombre de têtes d'attention dans le modèle transformer
     pub attention_heads: usize,
@@ -58,7 +68,7 @@ impl Default for NeuralNetConfig {
 }
 
 /// État du moteur neuronal
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NeuralNetState {
     /// Initialisation en cours
     Initializing,
@@ -70,51 +80,135 @@ pub enum NeuralNetState {
     Error(String),
 }
 
+/// Modèles du moteur neuronal, alloués lors du préchauffage
+struct EngineModels {
+    /// Modèle transformer
+    transformer_model: TransformerModel,
+    /// Extracteur de caractéristiques
+    feature_extractor: FeatureExtractor,
+    /// Détecteur d'anomalies
+    anomaly_detector: AnomalyDetector,
+}
+
 /// Moteur neuronal principal
 pub struct NeuralNetEngine {
     /// Configuration du moteur
     config: NeuralNetConfig,
     /// État actuel du moteur
     state: Arc<RwLock<NeuralNetState>>,
-    // Les champs suivants seront implémentés dans les versions futures
-    // transformer_model: Arc<TransformerModel>,
-    // feature_extractor: Arc<FeatureExtractor>,
-    // anomaly_detector: Arc<AnomalyDetector>,
+    /// Modèles (None tant que `warmup` n'a pas été appelé)
+    models: Arc<RwLock<Option<EngineModels>>>,
 }
 
 impl NeuralNetEngine {
     /// Crée une nouvelle instance du moteur neuronal avec la configuration spécifiée
+    ///
+    /// Les modèles ne sont pas alloués : le moteur reste en `Initializing` jusqu'à l'appel
+    /// de `warmup`.
     pub async fn new(config: NeuralNetConfig) -> Result<Self, String> {
-        let engine = Self {
+        Ok(Self {
             config,
             state: Arc::new(RwLock::new(NeuralNetState::Initializing)),
-            // Les champs suivants seront initialisés dans les versions futures
+            models: Arc::new(RwLock::new(None)),
+        })
+    }
+    
+    /// Alloue et initialise les modèles, puis passe le moteur à l'état `Ready`
+    ///
+    /// Sans effet si le moteur a déjà été préchauffé.
+    pub async fn warmup(&self) -> Result<(), String> {
+        let mut models = self.models.write().await;
+        if models.is_some() {
+            return Ok(());
+        }
+        
+        *self.state.write().await = NeuralNetState::Initializing;
+        
+        let transformer_config = TransformerConfig {
+            num_heads: self.config.attention_heads,
+            hidden_dim: self.config.hidden_dimensions,
+            max_seq_length: self.config.max_sequence_length,
+            use_gpu: self.config.use_gpu_acceleration,
+            ..TransformerConfig::default()
         };
         
-        // Initialisation asynchrone du moteur
-        // Cette partie sera implémentée dans les versions futures
+        let mut anomaly_detector = AnomalyDetector::new(AnomalyDetectionConfig {
+            base_threshold: self.config.anomaly_threshold,
+            ..AnomalyDetectionConfig::default()
+        });
+        anomaly_detector.start_baseline_learning();
         
-        // Transition vers l'état Ready
-        let mut state = engine.state.write().await;
-        *state = NeuralNetState::Ready;
+        *models = Some(EngineModels {
+            transformer_model: TransformerModel::new(transformer_config),
+            feature_extractor: FeatureExtractor::new(FeatureExtractionConfig::default()),
+            anomaly_detector,
+        });
         
-        Ok(engine)
+        *self.state.write().await = NeuralNetState::Ready;
+        
+        Ok(())
     }
     
     /// Analyse un flux réseau pour détecter des menaces potentielles
-    pub async fn analyze_network_flow(&self, _flow_data: &[u8]) -> Result<ThreatAnalysisResult, String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle renvoie un résultat fictif
+    ///
+    /// Renvoie une erreur si le moteur n'a pas été préchauffé. Le détecteur d'anomalies
+    /// n'est consulté qu'une fois sa ligne de base établie.
+    pub async fn analyze_network_flow(&self, flow_data: &[u8]) -> Result<ThreatAnalysisResult, String> {
+        let start_time = Instant::now();
+        
+        let models = self.models.read().await;
+        let models = models
+            .as_ref()
+            .ok_or("Le moteur neuronal n'est pas préchauffé (appeler warmup)")?;
+        
+        let (mut anomaly_score, _) = models.transformer_model.analyze_network_flow(flow_data)?;
+        
+        if models.anomaly_detector.is_baseline_established() {
+            let features = models.feature_extractor.extract_features(flow_data);
+            let detection = models.anomaly_detector.detect_anomalies(&features, None);
+            anomaly_score = anomaly_score.max(detection.anomaly_score);
+        }
+        
+        let threat_detected = anomaly_score >= self.config.anomaly_threshold;
         
         Ok(ThreatAnalysisResult {
-            threat_detected: false,
-            confidence: 0.0,
-            threat_type: None,
-            analysis_time_us: 150,
-            anomaly_score: 0.0,
+            threat_detected,
+            confidence: anomaly_score,
+            threat_type: threat_detected.then_some(ThreatType::UnknownZeroDay),
+            analysis_time_us: (start_time.elapsed().as_micros() as u64).max(1),
+            anomaly_score,
         })
     }
     
+    /// Ajuste le seuil du détecteur d'anomalies à partir des taux d'erreur constatés par les
+    /// opérateurs (voir `NeuroFireWall::error_rates`)
+    ///
+    /// Synchrone afin d'être appelée depuis le chemin des retours d'opérateur : renvoie une
+    /// erreur si le moteur n'a pas été préchauffé ou si ses modèles sont en cours d'utilisation
+    /// (l'ajustement peut alors être retenté).
+    pub fn adjust_thresholds(&self, false_positive_rate: f32, false_negative_rate: f32) -> Result<(), String> {
+        let mut models = self
+            .models
+            .try_write()
+            .map_err(|_| "Les modèles du moteur neuronal sont en cours d'utilisation".to_string())?;
+        let models = models
+            .as_mut()
+            .ok_or("Le moteur neuronal n'est pas préchauffé (appeler warmup)")?;
+        
+        models.anomaly_detector.adjust_thresholds(false_positive_rate, false_negative_rate);
+        
+        Ok(())
+    }
+    
+    /// Seuil courant du détecteur d'anomalies (None tant que le moteur n'est pas préchauffé)
+    pub async fn anomaly_threshold(&self) -> Option<f32> {
+        self.models
+            .read()
+            .await
+            .as_ref()
+            .map(|models| models.anomaly_detector.current_threshold())
+    }
+    
     /// Déclenche un cycle d'apprentissage adaptatif basé sur les données récentes
     pub async fn trigger_adaptive_learning(&self) -> Result<(), String> {
         // Cette fonction sera implémentée dans les versions futures
@@ -138,6 +232,133 @@ impl NeuralNetEngine {
     }
 }
 
+/// Interface synchrone de prédiction au-dessus du moteur d'inférence
+///
+/// Les clones partagent le même moteur d'inférence, ce qui permet de l'utiliser
+/// depuis plusieurs threads.
+#[derive(Clone)]
+pub struct NeuralNet {
+    /// Configuration du moteur
+    config: NeuralNetConfig,
+    /// Moteur d'inférence partagé
+    engine: Arc<Mutex<InferenceEngine>>,
+}
+
+impl NeuralNet {
+    /// Nombre de paires de perturbations opposées évaluées par le contrôle de robustesse
+    const ROBUSTNESS_SAMPLES: usize = 4;
+    /// Amplitude maximale des perturbations aléatoires
+    const ROBUSTNESS_EPSILON: f32 = 0.01;
+    /// Écart de score au-delà duquel la prédiction est jugée instable
+    const MAX_SCORE_DRIFT: f32 = 0.05;
+    
+    /// Crée une nouvelle instance avec la configuration spécifiée
+    pub fn new(config: NeuralNetConfig) -> Self {
+        let inference_config = InferenceConfig {
+            use_gpu: config.use_gpu_acceleration,
+            ..InferenceConfig::default()
+        };
+        
+        Self {
+            config,
+            engine: Arc::new(Mutex::new(InferenceEngine::new(inference_config))),
+        }
+    }
+    
+    /// Calcule le score d'anomalie d'un vecteur de caractéristiques
+    pub fn predict(&self, input: &[f32]) -> Result<PredictionResult, String> {
+        if input.is_empty() {
+            return Err("Vecteur d'entrée vide".to_string());
+        }
+        
+        if input.len() > self.config.max_sequence_length {
+            return Err(format!(
+                "Vecteur d'entrée trop long: {} > {}",
+                input.len(),
+                self.config.max_sequence_length
+            ));
+        }
+        
+        if input.iter().any(|value| !value.is_finite()) {
+            return Err("Le vecteur d'entrée contient des valeurs non finies".to_string());
+        }
+        
+        let result = self.engine.lock().unwrap().run_inference(input);
+        
+        // Ramener la sortie moyenne du modèle (dans [-1, 1]) vers [0, 1]
+        let mean = result.output.iter().sum::<f32>() / result.output.len().max(1) as f32;
+        let score = ((mean + 1.0) / 2.0).clamp(0.0, 1.0);
+        
+        Ok(PredictionResult {
+            score,
+            is_anomaly: score >= self.config.anomaly_threshold,
+            adversarial: false,
+            inference_time_us: result.inference_time_us,
+            device: result.device_used,
+        })
+    }
+    
+    /// Prédiction avec contrôle de sensibilité aux perturbations adversariales
+    ///
+    /// L'entrée est réévaluée avec de petites perturbations aléatoires (sans gradient),
+    /// appliquées par paires de signes opposés pour encadrer l'entrée d'origine.
+    /// Si la décision bascule ou si le score varie trop, la prédiction est marquée
+    /// adversariale et traitée comme une anomalie.
+    pub fn predict_with_robustness(&self, input: &[f32]) -> Result<PredictionResult, String> {
+        let mut prediction = self.predict(input)?;
+        let mut rng = rand::thread_rng();
+        let mut perturbed = input.to_vec();
+        
+        for _ in 0..Self::ROBUSTNESS_SAMPLES {
+            let noise: Vec<f32> = input
+                .iter()
+                .map(|_| rng.gen_range(-Self::ROBUSTNESS_EPSILON..=Self::ROBUSTNESS_EPSILON))
+                .collect();
+            
+            for sign in [1.0, -1.0] {
+                for ((value, original), delta) in perturbed.iter_mut().zip(input).zip(&noise) {
+                    *value = original + sign * delta;
+                }
+                
+                let sample = self.predict(&perturbed)?;
+                prediction.inference_time_us += sample.inference_time_us;
+                
+                if sample.is_anomaly != prediction.is_anomaly
+                    || (sample.score - prediction.score).abs() > Self::MAX_SCORE_DRIFT
+                {
+                    prediction.adversarial = true;
+                }
+            }
+        }
+        
+        if prediction.adversarial {
+            prediction.is_anomaly = true;
+        }
+        
+        Ok(prediction)
+    }
+    
+    /// Obtient la configuration du moteur
+    pub fn get_config(&self) -> &NeuralNetConfig {
+        &self.config
+    }
+}
+
+/// Résultat d'une prédiction
+#[derive(Debug, Clone)]
+pub struct PredictionResult {
+    /// Score d'anomalie (0.0 - 1.0)
+    pub score: f32,
+    /// Indique si le score dépasse le seuil d'anomalie
+    pub is_anomaly: bool,
+    /// Indique si la prédiction est instable face à de petites perturbations
+    pub adversarial: bool,
+    /// Temps d'inférence en microsecondes
+    pub inference_time_us: u64,
+    /// Appareil utilisé pour l'inférence
+    pub device: InferenceDevice,
+}
+
 /// Résultat de l'analyse de menace
 #[derive(Debug, Clone)]
 pub struct ThreatAnalysisResult {
@@ -184,18 +405,33 @@ mod tests {
     async fn test_engine_initialization() {
         let config = NeuralNetConfig::default();
         let engine = NeuralNetEngine::new(config).await.expect("Failed to initialize engine");
+        assert!(matches!(engine.get_state().await, NeuralNetState::Initializing));
+        
+        engine.warmup().await.expect("Warmup failed");
         
         let state = engine.get_state().await;
         match state {
             NeuralNetState::Ready => (),
-            _ => panic!("Engine should be in Ready state after initialization"),
+            _ => panic!("Engine should be in Ready state after warmup"),
         }
     }
     
+    #[tokio::test]
+    async fn test_analyze_requires_warmup() {
+        let engine = NeuralNetEngine::new(NeuralNetConfig::default()).await.unwrap();
+        let flow_data = vec![0u8; 1024];
+        
+        assert!(engine.analyze_network_flow(&flow_data).await.is_err());
+        
+        engine.warmup().await.unwrap();
+        assert!(engine.analyze_network_flow(&flow_data).await.is_ok());
+    }
+    
     #[tokio::test]
     async fn test_analyze_network_flow() {
         let config = NeuralNetConfig::default();
         let engine = NeuralNetEngine::new(config).await.expect("Failed to initialize engine");
+        engine.warmup().await.expect("Warmup failed");
         
         // Données fictives pour le test
         let flow_data = vec![0u8; 1024];
@@ -206,4 +442,44 @@ mod tests {
         assert!(!result.threat_detected);
         assert!(result.analysis_time_us > 0, "Analysis time should be positive");
     }
+    
+    #[test]
+    fn test_neural_net_predict() {
+        let neural_net = NeuralNet::new(NeuralNetConfig::default());
+        let input = vec![0.1; 128];
+        
+        let prediction = neural_net.predict(&input).expect("Prediction failed");
+        assert!((0.0..=1.0).contains(&prediction.score));
+        assert_eq!(prediction.is_anomaly, prediction.score >= 0.85);
+        
+        // Les clones partagent le moteur et restent utilisables depuis d'autres threads
+        let clone = neural_net.clone();
+        let handle = std::thread::spawn(move || clone.predict(&input).map(|p| p.score));
+        assert_eq!(handle.join().unwrap().unwrap(), prediction.score);
+    }
+    
+    #[test]
+    fn test_neural_net_rejects_invalid_input() {
+        let neural_net = NeuralNet::new(NeuralNetConfig::default());
+        
+        assert!(neural_net.predict(&[]).is_err());
+        assert!(neural_net.predict(&[0.1, f32::NAN]).is_err());
+        assert!(neural_net.predict(&vec![0.1; 4097]).is_err());
+    }
+    
+    #[test]
+    fn test_predict_with_robustness() {
+        let neural_net = NeuralNet::new(NeuralNetConfig::default());
+        
+        // Entrée loin du seuil : la décision reste stable
+        let stable = neural_net.predict_with_robustness(&[0.1; 128]).unwrap();
+        assert!(!stable.adversarial);
+        assert!(!stable.is_anomaly);
+        
+        // Entrée placée exactement sur le seuil de décision (score = 0.85)
+        let boundary_value = ((2.0 * 0.85_f32 - 1.0).atanh() + 1.0) / 2.0;
+        let boundary = neural_net.predict_with_robustness(&[boundary_value; 128]).unwrap();
+        assert!(boundary.adversarial);
+        assert!(boundary.is_anomaly);
+    }
 }
@@ -0,0 +1,86 @@
+//! # Module d'optimisation
+//!
+//! Ce module regroupe les transformations appliquées aux poids des modèles pour
+//! réduire leur coût d'inférence.
+//!
+//! ## Caractéristiques principales
+//!
+//! - Quantification uniforme symétrique sur un nombre de bits donné
+//! - Élagage des poids de plus faible amplitude
+
+/// Quantifie des poids sur `bits` bits (quantification uniforme symétrique)
+///
+/// Chaque poids est arrondi au multiple le plus proche du pas `max|w| / (2^(bits-1) - 1)`.
+/// Les poids sont renvoyés inchangés pour `bits >= 32`.
+pub fn quantize_weights(weights: &[f32], bits: u8) -> Vec<f32> {
+    if bits >= 32 {
+        return weights.to_vec();
+    }
+
+    let max_abs = weights.iter().fold(0.0f32, |max, weight| max.max(weight.abs()));
+    if max_abs == 0.0 {
+        return weights.to_vec();
+    }
+
+    let levels = ((1u32 << bits.saturating_sub(1)) - 1).max(1) as f32;
+    let scale = max_abs / levels;
+
+    weights
+        .iter()
+        .map(|weight| {
+            let level = (weight / scale).round().clamp(-levels, levels);
+            // Évite de produire -0.0, distinct de 0.0 au niveau binaire
+            if level == 0.0 { 0.0 } else { level * scale }
+        })
+        .collect()
+}
+
+/// Met à zéro la fraction `sparsity` (0.0 - 1.0) des poids de plus faible amplitude
+pub fn prune_weights(weights: &[f32], sparsity: f32) -> Vec<f32> {
+    let prune_count = (weights.len() as f32 * sparsity.clamp(0.0, 1.0)).floor() as usize;
+
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| weights[a].abs().total_cmp(&weights[b].abs()));
+
+    let mut pruned = weights.to_vec();
+    for &index in order.iter().take(prune_count) {
+        pruned[index] = 0.0;
+    }
+
+    pruned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_quantize_reduces_distinct_values() {
+        let weights: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.37).sin()).collect();
+        let distinct = |values: &[f32]| values.iter().map(|value| value.to_bits()).collect::<HashSet<_>>().len();
+
+        let quantized = quantize_weights(&weights, 4);
+        assert!(distinct(&quantized) <= 15);
+        assert!(distinct(&quantized) < distinct(&weights));
+
+        // L'erreur de quantification reste inférieure à un demi-pas
+        let step = 1.0 / 7.0;
+        assert!(weights.iter().zip(&quantized).all(|(w, q)| (w - q).abs() <= step / 2.0 + 1e-6));
+
+        assert_eq!(quantize_weights(&weights, 32), weights);
+    }
+
+    #[test]
+    fn test_prune_zeroes_smallest_weights() {
+        let weights = vec![0.5, -0.1, 0.9, 0.05, -0.7, 0.2, -0.01, 0.3, 0.8, -0.4];
+
+        let pruned = prune_weights(&weights, 0.3);
+        assert_eq!(pruned.iter().filter(|&&weight| weight == 0.0).count(), 3);
+        assert_eq!(pruned[1], 0.0);
+        assert_eq!(pruned[3], 0.0);
+        assert_eq!(pruned[6], 0.0);
+        assert_eq!(pruned[2], 0.9);
+    }
+}
@@ -34,6 +34,20 @@ pub struct TransformerConfig {
     pub dropout_rate: f32,
     /// Utiliser l'accélération GPU si disponible
     pub use_gpu: bool,
+    /// Traitement des entrées plus longues que `max_seq_length`
+    pub sequence_strategy: SequenceStrategy,
+}
+
+/// Stratégie appliquée aux séquences dépassant la longueur maximale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SequenceStrategy {
+    /// Conserver uniquement les `max_seq_length` premiers octets
+    Truncate,
+    /// Découper en fenêtres chevauchantes (pas de la moitié d'une fenêtre) et agréger
+    /// les scores par maximum
+    SlidingWindow,
+    /// Rejeter les séquences trop longues
+    Strict,
 }
 
 impl Default for TransformerConfig {
@@ -47,6 +61,7 @@ impl Default for TransformerConfig {
             max_seq_length: 4096,
             dropout_rate: 0.1,
             use_gpu: true,
+            sequence_strategy: SequenceStrategy::Truncate,
         }
     }
 }
@@ -137,13 +152,54 @@ impl EncoderLayer {
     }
 }
 
+/// Encodage positionnel sinusoïdal
+///
+/// Ajoute à chaque position `pos` et dimension `i` la valeur
+/// `sin(pos / 10000^(2k/d))` (i = 2k) ou `cos(pos / 10000^(2k/d))` (i = 2k + 1),
+/// rendant l'encodeur sensible à l'ordre des octets.
+#[derive(Debug, Clone)]
+pub struct PositionalEncoding {
+    hidden_dim: usize,
+    max_seq_length: usize,
+}
+
+impl PositionalEncoding {
+    /// Crée un encodage pour des séquences d'au plus `max_seq_length` positions
+    pub fn new(hidden_dim: usize, max_seq_length: usize) -> Self {
+        Self {
+            hidden_dim,
+            max_seq_length,
+        }
+    }
+    
+    /// Ajoute l'encodage positionnel à des plongements rangés position par position
+    /// (`seq_len` × `hidden_dim` valeurs)
+    ///
+    /// Les positions au-delà de `max_seq_length` ou de la taille du tampon sont ignorées.
+    pub fn apply(&self, embeddings: &mut [f32], seq_len: usize) {
+        if self.hidden_dim == 0 {
+            return;
+        }
+        
+        let positions = seq_len.min(self.max_seq_length);
+        for (pos, row) in embeddings.chunks_mut(self.hidden_dim).take(positions).enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
+                let exponent = (i - i % 2) as f32 / self.hidden_dim as f32;
+                let angle = pos as f32 / 10000f32.powf(exponent);
+                *value += if i % 2 == 0 { angle.sin() } else { angle.cos() };
+            }
+        }
+    }
+}
+
 /// Encodeur transformer complet
 pub struct Encoder {
     layers: Vec<EncoderLayer>,
     config: TransformerConfig,
+    /// Encodage positionnel appliqué aux plongements
+    positional_encoding: PositionalEncoding,
     // Les champs suivants seront implémentés dans les versions futures
     // embedding: Embedding,
-    // positional_encoding: PositionalEncoding,
     // output_layer_norm: LayerNorm,
 }
 
@@ -156,6 +212,7 @@ impl Encoder {
         }
         
         Self {
+            positional_encoding: PositionalEncoding::new(config.hidden_dim, config.max_seq_length),
             layers,
             config,
             // Les champs suivants seront initialisés dans les versions futures
@@ -163,11 +220,34 @@ impl Encoder {
     }
     
     /// Encode une séquence d'entrée
-    pub fn encode(&self, _input: &[u8], _mask: Option<&[bool]>) -> Vec<f32> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle renvoie un vecteur vide
+    ///
+    /// Renvoie les plongements positionnés (`hidden_dim` valeurs par octet, au plus
+    /// `max_seq_length` octets).
+    pub fn encode(&self, input: &[u8], _mask: Option<&[bool]>) -> Vec<f32> {
+        let input = &input[..input.len().min(self.config.max_seq_length)];
         
-        vec![]
+        let mut hidden = self.embed(input);
+        self.positional_encoding.apply(&mut hidden, input.len());
+        
+        // Les couches d'encodeur seront appliquées lorsque leur passe avant sera implémentée
+        
+        hidden
+    }
+    
+    /// Plonge chaque octet dans un vecteur de dimension `hidden_dim` (plongement fixe)
+    fn embed(&self, input: &[u8]) -> Vec<f32> {
+        let hidden_dim = self.config.hidden_dim;
+        let mut embeddings = Vec::with_capacity(input.len() * hidden_dim);
+        
+        for &byte in input {
+            for i in 0..hidden_dim {
+                // Valeur pseudo-aléatoire déterministe dans [-0.5, 0.5)
+                let value = ((byte as f32 + 1.0) * (i as f32 + 1.0) * 0.618_034).fract() - 0.5;
+                embeddings.push(value);
+            }
+        }
+        
+        embeddings
     }
 }
 
@@ -198,20 +278,83 @@ impl TransformerModel {
         Err("Not implemented yet".to_string())
     }
     
+    /// Découpe une entrée en séquences d'au plus `max_seq_length` octets selon la stratégie
+    /// configurée
+    pub fn sequence_windows<'a>(&self, input: &'a [u8]) -> Result<Vec<&'a [u8]>, String> {
+        let max_length = self.config.max_seq_length;
+        if max_length == 0 {
+            return Err("Longueur de séquence maximale nulle".to_string());
+        }
+        if input.len() <= max_length {
+            return Ok(vec![input]);
+        }
+        
+        match self.config.sequence_strategy {
+            SequenceStrategy::Truncate => Ok(vec![&input[..max_length]]),
+            SequenceStrategy::SlidingWindow => {
+                let stride = (max_length / 2).max(1);
+                let mut windows = Vec::new();
+                let mut start = 0;
+                loop {
+                    // La dernière fenêtre est alignée sur la fin de l'entrée
+                    let end = (start + max_length).min(input.len());
+                    windows.push(&input[end - max_length..end]);
+                    if end == input.len() {
+                        break;
+                    }
+                    start += stride;
+                }
+                Ok(windows)
+            }
+            SequenceStrategy::Strict => Err(format!(
+                "Séquence trop longue: {} octets (maximum {})",
+                input.len(),
+                max_length
+            )),
+        }
+    }
+    
     /// Traite une séquence à travers le modèle transformer
-    pub fn forward(&self, _input: &[u8]) -> Vec<f32> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle renvoie un vecteur vide
+    ///
+    /// En fenêtre glissante, les sorties des fenêtres sont agrégées élément par élément
+    /// par maximum.
+    pub fn forward(&self, input: &[u8]) -> Result<Vec<f32>, String> {
+        let mut output: Vec<f32> = Vec::new();
         
-        vec![]
+        for window in self.sequence_windows(input)? {
+            let encoded = self.encoder.encode(window, None);
+            if output.is_empty() {
+                output = encoded;
+            } else {
+                for (pooled, value) in output.iter_mut().zip(encoded) {
+                    *pooled = pooled.max(value);
+                }
+            }
+        }
+        
+        Ok(output)
     }
     
     /// Analyse un flux réseau pour détecter des anomalies
-    pub fn analyze_network_flow(&self, _flow_data: &[u8]) -> (f32, Vec<f32>) {
+    ///
+    /// Renvoie le score d'anomalie (maximum sur les fenêtres) et le score de chaque fenêtre.
+    pub fn analyze_network_flow(&self, flow_data: &[u8]) -> Result<(f32, Vec<f32>), String> {
+        let window_scores: Vec<f32> = self
+            .sequence_windows(flow_data)?
+            .into_iter()
+            .map(|window| self.score_sequence(window))
+            .collect();
+        
+        let score = window_scores.iter().copied().fold(0.0, f32::max);
+        Ok((score, window_scores))
+    }
+    
+    /// Calcule le score d'anomalie d'une séquence de longueur admissible
+    fn score_sequence(&self, _sequence: &[u8]) -> f32 {
         // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle renvoie un score d'anomalie fictif et un vecteur vide
+        // Pour l'instant, elle renvoie un score d'anomalie fictif
         
-        (0.0, vec![])
+        0.0
     }
 }
 
@@ -235,4 +378,60 @@ mod tests {
         // Vérifier que le modèle a été créé avec succès
         assert_eq!(model.encoder.layers.len(), 12);
     }
+    
+    #[test]
+    fn test_long_sequences_follow_strategy() {
+        let flow = vec![7u8; 5000];
+        let model_with = |sequence_strategy| {
+            TransformerModel::new(TransformerConfig {
+                max_seq_length: 4096,
+                hidden_dim: 8,
+                num_encoder_layers: 1,
+                sequence_strategy,
+                ..TransformerConfig::default()
+            })
+        };
+        
+        let truncate = model_with(SequenceStrategy::Truncate);
+        let windows = truncate.sequence_windows(&flow).unwrap();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].len(), 4096);
+        assert_eq!(truncate.analyze_network_flow(&flow).unwrap().1.len(), 1);
+        
+        // Fenêtres de 4096 octets décalées de 2048, la dernière alignée sur la fin du flux
+        let sliding = model_with(SequenceStrategy::SlidingWindow);
+        let windows = sliding.sequence_windows(&flow).unwrap();
+        assert_eq!(windows.len(), 2);
+        assert!(windows.iter().all(|window| window.len() == 4096));
+        let (score, window_scores) = sliding.analyze_network_flow(&flow).unwrap();
+        assert_eq!(window_scores.len(), 2);
+        assert_eq!(score, window_scores.iter().copied().fold(0.0, f32::max));
+        assert!(sliding.forward(&flow).is_ok());
+        
+        let strict = model_with(SequenceStrategy::Strict);
+        assert!(strict.analyze_network_flow(&flow).is_err());
+        assert!(strict.forward(&flow).is_err());
+        assert!(strict.analyze_network_flow(&flow[..4096]).is_ok());
+    }
+    
+    #[test]
+    fn test_positional_encoding_distinguishes_order() {
+        let config = TransformerConfig {
+            hidden_dim: 8,
+            max_seq_length: 16,
+            num_encoder_layers: 1,
+            ..TransformerConfig::default()
+        };
+        let encoder = Encoder::new(config);
+        
+        let forward = encoder.encode(&[1, 2, 3], None);
+        let reversed = encoder.encode(&[3, 2, 1], None);
+        assert_eq!(forward.len(), 3 * 8);
+        assert_ne!(forward, reversed);
+        
+        // Position 0 : sin(0) = 0 sur les dimensions paires, cos(0) = 1 sur les impaires
+        let mut embeddings = vec![0.0; 8];
+        PositionalEncoding::new(8, 16).apply(&mut embeddings, 1);
+        assert_eq!(embeddings, vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0]);
+    }
 }
@@ -10,24 +10,101 @@
 //! - Analyse comportementale du trafic réseau
 //! - Détection d'anomalies subtiles dans les flux de données
 //! - Prise de décision autonome et réactive
+//!
+//! ## Performances
+//!
+//! Mesures en mode release sur une machine à un seul cœur, 8 threads (benchmarks `#[ignore]`
+//! du module, `cargo test --release <benchmark> -- --ignored --nocapture`) :
+//!
+//! - `bench_stats_counters` : 125 000 à 137 000 paquets analysés par seconde. La
+//!   comptabilisation d'un paquet par compteurs atomiques atteint 78 millions d'opérations par
+//!   seconde contre 62 à 67 millions sous le verrou unique des statistiques (x1,18 à x1,26) ;
+//!   faute de cœurs concurrents, ce gain n'inclut pas la contention évitée.
+//! - `bench_shared_model_lock` : 680 000 à 755 000 prédictions par seconde sous `Mutex`
+//!   comme sous `RwLock` (x0,95 à x1,03). Sur un seul cœur, les lectures ne peuvent pas
+//!   s'exécuter en parallèle : la mesure montre seulement que le chemin de lecture n'est pas
+//!   plus lent, le gain en parallèle reste à mesurer sur une machine multicœur.
+//! - `bench_dot_product` : produit scalaire de 256 éléments en 128 à 137 ns en séquentiel,
+//!   en 23 à 24 ns par blocs (fonctionnalité `simd`), soit une accélération de x5,6 à x5,8.
 
-use std::collections::{HashMap, VecDeque};
-use std::sync::{Arc, Mutex};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::hash::BuildHasher;
+use std::io::{BufRead, BufReader, Read};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant, SystemTime};
 
+use ipnet::IpNet;
+use log::Level;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::bus::{BusEvent, EventBus};
+use crate::clock::{system_clock, Clock, SharedClock, SystemClock};
+use crate::id::{random_ids, IdGenerator, RandomIdGenerator, SharedIdGenerator};
+use crate::logging::module_log;
+use crate::neural_net::{shannon_entropy, FeatureExtractor, NeuralNetEngine};
+use crate::warpshield::AttackSignature;
+
 /// Configuration du NeuroFireWall
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NeuroFireWallConfig {
     /// Taille maximale de la mémoire tampon (nombre de paquets)
     pub buffer_size: usize,
+    /// Taille maximale de l'échantillon de charge utile conservé dans la mémoire tampon et la
+    /// quarantaine (en octets)
+    pub max_payload_sample_bytes: usize,
     /// Seuil de détection d'anomalies (0.0 - 1.0)
     pub anomaly_threshold: f32,
+    /// Seuils de décision explicites (dérivés de `anomaly_threshold` si absents)
+    pub decision_thresholds: Option<DecisionThresholds>,
     /// Intervalle d'apprentissage (en secondes)
     pub learning_interval: u64,
     /// Activer l'apprentissage continu
     pub enable_continuous_learning: bool,
+    /// Taux d'apprentissage de la descente de gradient
+    pub learning_rate: f32,
     /// Activer le mode strict (plus de faux positifs, moins de faux négatifs)
     pub strict_mode: bool,
+    /// Ajouter automatiquement à la liste de blocage les sources d'un paquet bloqué
+    pub auto_block_sources: bool,
+    /// Durée du premier blocage d'une source (en secondes)
+    pub block_ttl_secs: u64,
+    /// Facteur multiplicatif de la durée de blocage à chaque récidive
+    pub block_backoff_factor: u32,
+    /// Durée maximale d'un blocage (en secondes)
+    pub max_block_ttl_secs: u64,
+    /// Période sans blocage (en secondes, à compter de la fin du dernier blocage) après
+    /// laquelle les récidives d'une source sont oubliées
+    pub offense_decay_secs: u64,
+    /// Nombre maximal de sources dont les récidives sont suivies (la source bloquée le moins
+    /// récemment est oubliée au-delà)
+    pub max_tracked_offenders: usize,
+    /// Débit maximal autorisé par source (paquets par seconde) avant limitation
+    pub packets_per_second: u32,
+    /// Bloquer les sources dont le débit dépasse le double de la limite
+    pub escalate_rate_limit: bool,
+    /// Nombre maximal de paquets conservés en quarantaine
+    pub quarantine_size: usize,
+    /// Durée de rétention des statistiques de trafic glissantes (en secondes)
+    pub stats_window_secs: u64,
+    /// Nombre maximal de paquets conservés dans la fenêtre de statistiques (les plus anciens
+    /// sont oubliés au-delà, même s'ils sont encore dans la fenêtre)
+    pub max_window_samples: usize,
+    /// Nombre maximal d'événements de détection conservés pour l'export SIEM
+    #[serde(alias = "event_history_size")]
+    pub max_retained_events: usize,
+    /// Événement sacrifié lorsque la mémoire des événements de détection est pleine
+    pub event_overflow_policy: EventOverflowPolicy,
+    /// Nombre de retours d'opérateur les plus récents pris en compte dans les taux d'erreur
+    pub feedback_window_size: usize,
+    /// Nombre de retours d'opérateur entre deux ajustements des seuils du moteur neuronal
+    /// (0 = pas d'ajustement)
+    pub threshold_adjustment_interval: usize,
     /// Niveau de sensibilité (0.0 - 1.0)
     pub sensitivity: f32,
     /// Niveau de journalisation (0 = aucun, 1 = erreurs, 2 = avertissements, 3 = info, 4 = debug)
@@ -36,26 +113,130 @@ pub struct NeuroFireWallConfig {
     pub neural_layers: u8,
     /// Taille de la couche cachée
     pub hidden_layer_size: usize,
+    /// Graine de l'initialisation des poids du modèle : une même graine donne toujours les
+    /// mêmes poids initiaux ; `None` utilise l'entropie du système
+    #[serde(alias = "rng_seed")]
+    pub model_seed: Option<u64>,
+    /// Nombre d'échecs d'analyse consécutifs déclenchant le passage en mode dégradé
+    pub max_consecutive_errors: u32,
+    /// Sources de confiance (adresses IP ou plages CIDR) autorisées sans analyse
+    pub trusted_sources: Vec<String>,
+    /// Nombre de décisions du modèle conservées pour les paquets identiques (0 = cache désactivé)
+    pub classification_cache_size: usize,
 }
 
 impl Default for NeuroFireWallConfig {
     fn default() -> Self {
         Self {
             buffer_size: 10000,
+            max_payload_sample_bytes: 1500,
             anomaly_threshold: 0.85,
+            decision_thresholds: None,
             learning_interval: 3600,
             enable_continuous_learning: true,
+            learning_rate: 0.05,
             strict_mode: false,
+            auto_block_sources: false,
+            block_ttl_secs: 60,
+            block_backoff_factor: 5,
+            max_block_ttl_secs: 86400,
+            offense_decay_secs: 86400,
+            max_tracked_offenders: 10_000,
+            packets_per_second: 100,
+            escalate_rate_limit: false,
+            quarantine_size: 1000,
+            stats_window_secs: 60,
+            max_window_samples: 100_000,
+            max_retained_events: 1000,
+            event_overflow_policy: EventOverflowPolicy::DropOldest,
+            feedback_window_size: 1000,
+            threshold_adjustment_interval: 100,
             sensitivity: 0.75,
             log_level: 3,
             neural_layers: 4,
             hidden_layer_size: 256,
+            model_seed: None,
+            max_consecutive_errors: 5,
+            trusted_sources: Vec::new(),
+            classification_cache_size: 1024,
+        }
+    }
+}
+
+/// Politique de débordement de la mémoire des événements de détection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EventOverflowPolicy {
+    /// Évincer l'événement le plus ancien pour conserver le nouveau
+    #[default]
+    DropOldest,
+    /// Ignorer le nouvel événement tant que la mémoire est pleine
+    DropNewest,
+}
+
+impl NeuroFireWallConfig {
+    /// Seuils de décision effectifs, avant prise en compte de la sensibilité
+    pub fn thresholds(&self) -> DecisionThresholds {
+        self.decision_thresholds
+            .unwrap_or_else(|| DecisionThresholds::from_anomaly_threshold(self.anomaly_threshold))
+    }
+}
+
+/// Seuils de score d'anomalie associés à chaque décision (0.0 - 1.0)
+///
+/// Un score atteignant `block` est bloqué, `quarantine` mis en quarantaine et `alert`
+/// signalé ; les scores inférieurs sont autorisés.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecisionThresholds {
+    /// Seuil de blocage immédiat
+    pub block: f32,
+    /// Seuil de mise en quarantaine (seuil de détection)
+    pub quarantine: f32,
+    /// Seuil d'alerte
+    pub alert: f32,
+}
+
+impl DecisionThresholds {
+    /// Seuil de blocage par défaut
+    const DEFAULT_BLOCK: f32 = 0.95;
+    /// Rapport entre le seuil d'alerte et le seuil de quarantaine par défaut
+    const DEFAULT_ALERT_RATIO: f32 = 0.8;
+    
+    /// Seuils historiques : quarantaine au seuil de détection, alerte à 80 % de celui-ci
+    pub fn from_anomaly_threshold(anomaly_threshold: f32) -> Self {
+        Self {
+            block: Self::DEFAULT_BLOCK,
+            quarantine: anomaly_threshold,
+            alert: anomaly_threshold * Self::DEFAULT_ALERT_RATIO,
         }
     }
+    
+    /// Vérifie que les seuils sont dans [0, 1] et que `block >= quarantine >= alert`
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [("block", self.block), ("quarantine", self.quarantine), ("alert", self.alert)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("Seuil de décision {} hors de [0, 1]: {}", name, value));
+            }
+        }
+        
+        if self.block < self.quarantine || self.quarantine < self.alert {
+            return Err(format!(
+                "Seuils de décision incohérents (attendu block >= quarantine >= alert): {} / {} / {}",
+                self.block, self.quarantine, self.alert
+            ));
+        }
+        
+        Ok(())
+    }
+}
+
+impl Default for DecisionThresholds {
+    fn default() -> Self {
+        Self::from_anomaly_threshold(NeuroFireWallConfig::default().anomaly_threshold)
+    }
 }
 
 /// Types de trafic réseau
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TrafficType {
     /// Trafic HTTP/HTTPS
     Web,
@@ -78,7 +259,7 @@ pub enum TrafficType {
 }
 
 /// Décision du pare-feu
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FirewallDecision {
     /// Autoriser le trafic
     Allow,
@@ -95,7 +276,7 @@ pub enum FirewallDecision {
 }
 
 /// Paquet réseau analysé
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkPacket {
     /// Identifiant unique du paquet
     pub id: String,
@@ -110,17 +291,336 @@ pub struct NetworkPacket {
     /// Protocole
     pub protocol: String,
     /// Taille du paquet en octets
-    pub size: usize,
+    pub size: u32,
     /// Horodatage de réception
     pub timestamp: SystemTime,
     /// Type de trafic
     pub traffic_type: TrafficType,
     /// Charge utile (limitée pour l'analyse)
+    #[serde(default)]
     pub payload_sample: Vec<u8>,
     /// Métadonnées supplémentaires
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
 }
 
+impl NetworkPacket {
+    /// Crée un constructeur de paquet avec des valeurs par défaut
+    pub fn builder() -> NetworkPacketBuilder {
+        NetworkPacketBuilder::default()
+    }
+}
+
+/// Constructeur de `NetworkPacket` avec validation
+///
+/// Par défaut : identifiant aléatoire, horodatage courant, protocole TCP, trafic inconnu,
+/// charge utile et métadonnées vides. Les adresses IP source et destination doivent être fournies.
+/// `NeuroFireWall::packet_builder` tire l'identifiant et l'horodatage du générateur et de
+/// l'horloge du pare-feu.
+#[derive(Debug, Clone)]
+pub struct NetworkPacketBuilder {
+    packet: NetworkPacket,
+}
+
+impl Default for NetworkPacketBuilder {
+    fn default() -> Self {
+        Self::new(&RandomIdGenerator, &SystemClock)
+    }
+}
+
+impl NetworkPacketBuilder {
+    /// Crée un constructeur dont l'identifiant et l'horodatage par défaut proviennent des
+    /// sources fournies
+    pub fn new(ids: &dyn IdGenerator, clock: &dyn Clock) -> Self {
+        Self {
+            packet: NetworkPacket {
+                id: ids.next_id("packet"),
+                source_ip: String::new(),
+                destination_ip: String::new(),
+                source_port: 0,
+                destination_port: 0,
+                protocol: "TCP".to_string(),
+                size: 0,
+                timestamp: clock.now(),
+                traffic_type: TrafficType::Unknown,
+                payload_sample: Vec::new(),
+                metadata: HashMap::new(),
+            },
+        }
+    }
+    
+    /// Définit l'identifiant du paquet
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.packet.id = id.into();
+        self
+    }
+    
+    /// Définit l'adresse et le port source
+    pub fn source(mut self, ip: impl Into<String>, port: u16) -> Self {
+        self.packet.source_ip = ip.into();
+        self.packet.source_port = port;
+        self
+    }
+    
+    /// Définit l'adresse et le port destination
+    pub fn destination(mut self, ip: impl Into<String>, port: u16) -> Self {
+        self.packet.destination_ip = ip.into();
+        self.packet.destination_port = port;
+        self
+    }
+    
+    /// Définit le protocole
+    pub fn protocol(mut self, protocol: impl Into<String>) -> Self {
+        self.packet.protocol = protocol.into();
+        self
+    }
+    
+    /// Définit la taille du paquet en octets
+    pub fn size(mut self, size: u32) -> Self {
+        self.packet.size = size;
+        self
+    }
+    
+    /// Définit l'horodatage de réception
+    pub fn timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.packet.timestamp = timestamp;
+        self
+    }
+    
+    /// Définit le type de trafic
+    pub fn traffic_type(mut self, traffic_type: TrafficType) -> Self {
+        self.packet.traffic_type = traffic_type;
+        self
+    }
+    
+    /// Définit l'échantillon de charge utile
+    pub fn payload_sample(mut self, payload_sample: Vec<u8>) -> Self {
+        self.packet.payload_sample = payload_sample;
+        self
+    }
+    
+    /// Ajoute une métadonnée
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.packet.metadata.insert(key.into(), value.into());
+        self
+    }
+    
+    /// Valide et construit le paquet
+    ///
+    /// Les adresses doivent être des IPv4 ou IPv6 valides et les ports non nuls,
+    /// sauf pour ICMP qui n'utilise pas de ports.
+    pub fn build(self) -> Result<NetworkPacket, String> {
+        let packet = self.packet;
+        
+        for (name, ip) in [("source", &packet.source_ip), ("destination", &packet.destination_ip)] {
+            if ip.parse::<IpAddr>().is_err() {
+                return Err(format!("Adresse IP {} invalide: '{}'", name, ip));
+            }
+        }
+        
+        if !packet.protocol.eq_ignore_ascii_case("ICMP") {
+            for (name, port) in [("source", packet.source_port), ("destination", packet.destination_port)] {
+                if port == 0 {
+                    return Err(format!("Port {} invalide: 0 (protocole {})", name, packet.protocol));
+                }
+            }
+        }
+        
+        Ok(packet)
+    }
+}
+
+/// Identifiant d'un flux réseau (5-tuple)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FlowKey {
+    /// Adresse IP source
+    pub source_ip: String,
+    /// Adresse IP destination
+    pub destination_ip: String,
+    /// Port source
+    pub source_port: u16,
+    /// Port destination
+    pub destination_port: u16,
+    /// Protocole
+    pub protocol: String,
+}
+
+impl FlowKey {
+    /// Extrait le 5-tuple d'un paquet
+    pub fn from_packet(packet: &NetworkPacket) -> Self {
+        Self {
+            source_ip: packet.source_ip.clone(),
+            destination_ip: packet.destination_ip.clone(),
+            source_port: packet.source_port,
+            destination_port: packet.destination_port,
+            protocol: packet.protocol.clone(),
+        }
+    }
+}
+
+/// Flux réassemblé : paquets consécutifs d'un même 5-tuple et leurs charges utiles concaténées
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Flow {
+    /// Identifiant du flux
+    pub key: FlowKey,
+    /// Paquets du flux, dans l'ordre de réception
+    pub packets: Vec<NetworkPacket>,
+    /// Charges utiles concaténées
+    pub payload: Vec<u8>,
+    /// Horodatage du premier paquet
+    pub first_seen: SystemTime,
+    /// Horodatage du dernier paquet
+    pub last_seen: SystemTime,
+}
+
+impl Flow {
+    /// Démarre un flux à partir de son premier paquet
+    fn start(packet: NetworkPacket) -> Self {
+        Self {
+            key: FlowKey::from_packet(&packet),
+            payload: packet.payload_sample.clone(),
+            first_seen: packet.timestamp,
+            last_seen: packet.timestamp,
+            packets: vec![packet],
+        }
+    }
+    
+    /// Ajoute un paquet au flux
+    fn push(&mut self, packet: NetworkPacket) {
+        self.payload.extend_from_slice(&packet.payload_sample);
+        self.last_seen = self.last_seen.max(packet.timestamp);
+        self.packets.push(packet);
+    }
+    
+    /// Identifiants des paquets du flux
+    pub fn packet_ids(&self) -> Vec<String> {
+        self.packets.iter().map(|packet| packet.id.clone()).collect()
+    }
+    
+    /// Construit le paquet synthétique analysé pour le flux : 5-tuple et type de trafic du
+    /// premier paquet, tailles cumulées et charge utile réassemblée
+    pub fn to_packet(&self) -> NetworkPacket {
+        let first = &self.packets[0];
+        
+        NetworkPacket {
+            id: format!("flow-{}", first.id),
+            source_ip: self.key.source_ip.clone(),
+            destination_ip: self.key.destination_ip.clone(),
+            source_port: self.key.source_port,
+            destination_port: self.key.destination_port,
+            protocol: self.key.protocol.clone(),
+            size: self.packets.iter().fold(0u32, |total, packet| total.saturating_add(packet.size)),
+            timestamp: self.first_seen,
+            traffic_type: first.traffic_type.clone(),
+            payload_sample: self.payload.clone(),
+            metadata: first.metadata.clone(),
+        }
+    }
+}
+
+/// Réassemblage des paquets en flux avant analyse
+///
+/// Les paquets d'un même 5-tuple s'accumulent tant que l'écart entre deux paquets reste
+/// inférieur au délai d'inactivité ; un flux est restitué lorsqu'il expire, qu'un paquet
+/// arrive après son expiration, ou que sa charge utile atteint `MAX_FLOW_BYTES`.
+///
+/// Le nombre de flux en cours est borné : un nouveau flux au-delà de la limite restitue le
+/// plus ancien, de sorte qu'un balayage de ports ou une inondation SYN ne fait pas croître la
+/// table sans limite.
+#[derive(Debug)]
+pub struct FlowReassembler {
+    timeout: Duration,
+    max_flows: usize,
+    /// Flux en cours, avec leur numéro d'ordre de création
+    flows: HashMap<FlowKey, (u64, Flow)>,
+    /// Flux en cours par ordre de création
+    order: BTreeMap<u64, FlowKey>,
+    next_sequence: u64,
+}
+
+impl FlowReassembler {
+    /// Taille maximale de la charge utile réassemblée d'un flux (en octets)
+    pub const MAX_FLOW_BYTES: usize = 64 * 1024;
+    /// Nombre maximal de flux en cours adapté à un capteur courant
+    pub const DEFAULT_MAX_FLOWS: usize = 65_536;
+    
+    /// Crée un réassembleur avec le délai d'inactivité et le nombre maximal de flux en cours donnés
+    pub fn new(timeout: Duration, max_flows: usize) -> Self {
+        Self {
+            timeout,
+            max_flows: max_flows.max(1),
+            flows: HashMap::new(),
+            order: BTreeMap::new(),
+            next_sequence: 0,
+        }
+    }
+    
+    /// Ajoute un paquet à son flux
+    ///
+    /// Renvoie les flux terminés par ce paquet : le flux précédent du même 5-tuple s'il
+    /// avait expiré, le plus ancien flux si la limite de flux en cours est atteinte, et le
+    /// flux courant si sa charge utile atteint la taille maximale.
+    pub fn push(&mut self, packet: NetworkPacket) -> Vec<Flow> {
+        let key = FlowKey::from_packet(&packet);
+        let mut completed = Vec::new();
+        
+        if self.flows.get(&key).is_some_and(|(_, flow)| self.is_expired(flow, packet.timestamp)) {
+            completed.extend(self.remove(&key));
+        }
+        
+        match self.flows.get_mut(&key) {
+            Some((_, flow)) => flow.push(packet),
+            None => {
+                if self.flows.len() >= self.max_flows {
+                    let oldest = self.order.values().next().cloned();
+                    completed.extend(oldest.and_then(|oldest| self.remove(&oldest)));
+                }
+                let sequence = self.next_sequence;
+                self.next_sequence += 1;
+                self.order.insert(sequence, key.clone());
+                self.flows.insert(key.clone(), (sequence, Flow::start(packet)));
+            }
+        }
+        
+        if self.flows[&key].1.payload.len() >= Self::MAX_FLOW_BYTES {
+            completed.extend(self.remove(&key));
+        }
+        
+        completed
+    }
+    
+    /// Restitue les flux inactifs depuis plus que le délai à la date donnée
+    pub fn flush_expired(&mut self, now: SystemTime) -> Vec<Flow> {
+        let expired: Vec<FlowKey> = self
+            .flows
+            .iter()
+            .filter(|(_, (_, flow))| self.is_expired(flow, now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        
+        let mut flows: Vec<Flow> = expired.iter().filter_map(|key| self.remove(key)).collect();
+        flows.sort_by_key(|flow| flow.first_seen);
+        flows
+    }
+    
+    /// Nombre de flux en cours de réassemblage
+    pub fn pending_flows(&self) -> usize {
+        self.flows.len()
+    }
+    
+    /// Retire un flux en cours
+    fn remove(&mut self, key: &FlowKey) -> Option<Flow> {
+        let (sequence, flow) = self.flows.remove(key)?;
+        self.order.remove(&sequence);
+        Some(flow)
+    }
+    
+    /// Indique si un flux a dépassé le délai d'inactivité à la date donnée
+    fn is_expired(&self, flow: &Flow, now: SystemTime) -> bool {
+        now.duration_since(flow.last_seen).is_ok_and(|idle| idle > self.timeout)
+    }
+}
+
 /// Caractéristiques extraites d'un paquet
 #[derive(Debug, Clone)]
 pub struct PacketFeatures {
@@ -135,7 +635,7 @@ pub struct PacketFeatures {
 }
 
 /// Événement de détection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectionEvent {
     /// Identifiant unique de l'événement
     pub id: String,
@@ -147,14 +647,28 @@ pub struct DetectionEvent {
     pub decision: FirewallDecision,
     /// Paquets impliqués
     pub related_packets: Vec<String>,
-    /// Caractéristiques ayant déclenché la détection
+    /// Caractéristiques ayant déclenché la détection, par contribution décroissante
     pub trigger_features: Vec<String>,
+    /// Contribution de chaque caractéristique de `trigger_features` (même ordre)
+    pub feature_scores: Vec<f32>,
     /// Description de la détection
     pub description: String,
 }
 
+impl DetectionEvent {
+    /// Encode l'événement au format binaire bincode (plus compact et plus rapide que JSON)
+    pub fn to_bincode(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|e| format!("Encodage bincode de l'événement de détection impossible: {}", e))
+    }
+    
+    /// Décode un événement encodé par `to_bincode`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, String> {
+        bincode::deserialize(bytes).map_err(|e| format!("Décodage bincode de l'événement de détection impossible: {}", e))
+    }
+}
+
 /// Statistiques du NeuroFireWall
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct NeuroFireWallStats {
     /// Nombre total de paquets analysés
     pub total_packets_analyzed: u64,
@@ -164,10 +678,22 @@ pub struct NeuroFireWallStats {
     pub packets_blocked: u64,
     /// Nombre de paquets mis en quarantaine
     pub packets_quarantined: u64,
+    /// Nombre de paquets dont le débit a été limité
+    pub packets_rate_limited: u64,
     /// Nombre d'événements de détection
     pub detection_events: u64,
+    /// Nombre d'événements de détection écartés faute de place en mémoire
+    pub events_dropped: u64,
+    /// Nombre de paquets de sources de confiance autorisés sans analyse (inclus dans `packets_allowed`)
+    pub allowed_trusted: u64,
+    /// Nombre de paquets dont la décision du modèle a été servie par le cache de classification
+    pub cache_hits: u64,
     /// Temps d'analyse moyen par paquet (en microsecondes)
     pub avg_analysis_time_us: f64,
+    /// Temps d'analyse minimal observé (en microsecondes)
+    pub min_analysis_time_us: f64,
+    /// Temps d'analyse maximal observé (en microsecondes)
+    pub max_analysis_time_us: f64,
     /// Taux de faux positifs estimé
     pub false_positive_rate: f32,
     /// Taux de faux négatifs estimé
@@ -178,8 +704,21 @@ pub struct NeuroFireWallStats {
     pub uptime_seconds: u64,
 }
 
+/// Agrégats de trafic sur une fenêtre glissante
+#[derive(Debug, Clone, Default)]
+pub struct WindowStats {
+    /// Paquets par seconde
+    pub pps: f32,
+    /// Octets par seconde
+    pub bps: f32,
+    /// Nombre de sources distinctes
+    pub unique_sources: usize,
+    /// Sources les plus actives (adresse, nombre de paquets), par nombre de paquets décroissant
+    pub top_talkers: Vec<(String, u64)>,
+}
+
 /// État du NeuroFireWall
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NeuroFireWallState {
     /// Initialisation en cours
     Initializing,
@@ -198,216 +737,592 @@ pub enum NeuroFireWallState {
 }
 
 /// Modèle neuronal
+///
+/// Perceptron à deux couches : entrée -> couche cachée (ReLU) -> sortie (sigmoïde).
+#[derive(Clone, Serialize, Deserialize)]
 struct NeuralModel {
-    // Cette structure sera implémentée dans les versions futures
-    // Pour l'instant, elle est simplifiée
+    /// Poids entrée -> couche cachée, une ligne par neurone caché (hidden_size x input_size)
     weights: Vec<Vec<f32>>,
+    /// Biais de la couche cachée
+    hidden_biases: Vec<f32>,
+    /// Poids couche cachée -> sortie, une ligne par neurone de sortie (output_size x hidden_size)
+    output_weights: Vec<Vec<f32>>,
+    /// Biais de la couche de sortie
     biases: Vec<f32>,
     input_size: usize,
+    hidden_size: usize,
     output_size: usize,
+    learning_rate: f32,
 }
 
 impl NeuralModel {
-    /// Crée un nouveau modèle neuronal
-    fn new(input_size: usize, hidden_size: usize, output_size: usize) -> Self {
-        // Initialisation simplifiée pour la démonstration
-        let weights = vec![vec![0.1; hidden_size]; input_size];
-        let biases = vec![0.0; output_size];
+    /// Biais de sortie initial : le trafic est supposé majoritairement légitime
+    /// tant que le modèle n'a pas appris (sigmoïde(-2.0) ≈ 0.12).
+    const INITIAL_OUTPUT_BIAS: f32 = -2.0;
+    
+    /// Crée un nouveau modèle neuronal, initialisé depuis la graine si elle est fournie
+    fn new(input_size: usize, hidden_size: usize, output_size: usize, learning_rate: f32, rng_seed: Option<u64>) -> Self {
+        let mut rng = match rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        
+        // Initialisation de Xavier/Glorot : poids uniformes dans [-limite, limite], une ligne
+        // par neurone de la couche
+        let mut init_layer = |fan_in: usize, fan_out: usize| -> Vec<Vec<f32>> {
+            let limit = (6.0 / (fan_in + fan_out) as f32).sqrt();
+            (0..fan_out)
+                .map(|_| (0..fan_in).map(|_| rng.gen_range(-limit..limit)).collect())
+                .collect()
+        };
+        
+        let weights = init_layer(input_size, hidden_size);
+        let output_weights = init_layer(hidden_size, output_size);
         
         Self {
             weights,
-            biases,
+            hidden_biases: vec![0.0; hidden_size],
+            output_weights,
+            biases: vec![Self::INITIAL_OUTPUT_BIAS; output_size],
             input_size,
+            hidden_size,
             output_size,
+            learning_rate,
         }
     }
     
-    /// Prédit un score d'anomalie à partir de caractéristiques
-    fn predict(&self, features: &[f32]) -> f32 {
-        // Prédiction simplifiée pour la démonstration
-        // Dans une implémentation réelle, ce serait un réseau neuronal complet
-        
-        if features.len() != self.input_size {
-            return 0.5; // Valeur par défaut en cas d'erreur
-        }
-        
-        // Calcul simplifié du score d'anomalie
-        let mut sum = 0.0;
-        for (i, &feature) in features.iter().enumerate() {
-            for (j, &weight) in self.weights[i].iter().enumerate() {
-                sum += feature * weight;
+    /// Vérifie que les poids et biais ont les dimensions déclarées
+    fn check_dimensions(&self) -> Result<(), String> {
+        let layers = [
+            ("weights", &self.weights, self.hidden_size, self.input_size),
+            ("output_weights", &self.output_weights, self.output_size, self.hidden_size),
+        ];
+        for (name, layer, rows, columns) in layers {
+            if layer.len() != rows || layer.iter().any(|row| row.len() != columns) {
+                return Err(format!("Dimensions de {} incohérentes (attendu {}x{})", name, rows, columns));
             }
         }
+        if self.hidden_biases.len() != self.hidden_size || self.biases.len() != self.output_size {
+            return Err("Nombre de biais incohérent avec les tailles de couches".to_string());
+        }
         
-        // Normaliser entre 0 et 1
-        let score = 1.0 / (1.0 + (-sum).exp());
-        
-        score
+        Ok(())
     }
     
-    /// Met à jour le modèle avec de nouvelles données
-    fn update(&mut self, _features: &[f32], _label: f32) {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle ne fait rien
+    /// Calcule les activations de la couche cachée (ReLU)
+    fn hidden_activations(&self, features: &[f32]) -> Vec<f32> {
+        self.weights
+            .iter()
+            .zip(&self.hidden_biases)
+            .map(|(row, &bias)| (bias + dot_product(row, features)).max(0.0))
+            .collect()
     }
-}
-
-/// NeuroFireWall principal
-pub struct NeuroFireWall {
-    config: NeuroFireWallConfig,
-    state: Arc<Mutex<NeuroFireWallState>>,
-    stats: Arc<Mutex<NeuroFireWallStats>>,
-    packet_buffer: Arc<Mutex<VecDeque<NetworkPacket>>>,
-    model: Arc<Mutex<NeuralModel>>,
-    // Les champs suivants seront implémentés dans les versions futures
-    // feature_extractor: FeatureExtractor,
-    // decision_engine: DecisionEngine,
-    // learning_manager: LearningManager,
-}
-
-impl NeuroFireWall {
-    /// Crée une nouvelle instance de NeuroFireWall
-    pub fn new(config: NeuroFireWallConfig) -> Self {
-        let stats = NeuroFireWallStats {
-            total_packets_analyzed: 0,
-            packets_allowed: 0,
-            packets_blocked: 0,
-            packets_quarantined: 0,
-            detection_events: 0,
-            avg_analysis_time_us: 0.0,
-            false_positive_rate: 0.0,
-            false_negative_rate: 0.0,
-            learning_cycles: 0,
-            uptime_seconds: 0,
-        };
-        
-        // Créer un modèle neuronal simplifié
-        // Dans une implémentation réelle, ce serait un réseau neuronal plus complexe
-        let model = NeuralModel::new(10, config.hidden_layer_size, 1);
-        
-        Self {
-            config,
-            state: Arc::new(Mutex::new(NeuroFireWallState::Initializing)),
-            stats: Arc::new(Mutex::new(stats)),
-            packet_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(config.buffer_size))),
-            model: Arc::new(Mutex::new(model)),
-            // Les champs suivants seront initialisés dans les versions futures
+    
+    /// Prédit un score d'anomalie à partir de caractéristiques
+    ///
+    /// Échoue si le nombre de caractéristiques ne correspond pas à la couche d'entrée.
+    fn predict(&self, features: &[f32]) -> Result<f32, String> {
+        if features.len() != self.input_size {
+            return Err(format!(
+                "Le modèle attend {} caractéristiques, {} reçues",
+                self.input_size,
+                features.len()
+            ));
         }
+        
+        let hidden = self.hidden_activations(features);
+        Ok(self.output(&hidden))
     }
     
-    /// Initialise le NeuroFireWall
-    pub fn initialize(&mut self) -> Result<(), String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle change simplement l'état
-        
-        let mut state = self.state.lock().unwrap();
-        *state = NeuroFireWallState::Operational;
+    /// Attribue à chaque caractéristique une contribution au score
+    ///
+    /// Contribution = |valeur| × norme L1 de ses poids vers la couche cachée.
+    fn feature_contributions(&self, features: &[f32]) -> Vec<f32> {
+        features
+            .iter()
+            .enumerate()
+            .map(|(index, &feature)| feature.abs() * self.weights.iter().map(|row| row[index].abs()).sum::<f32>())
+            .collect()
+    }
+    
+    /// Calcule la sortie (sigmoïde) à partir des activations cachées
+    fn output(&self, hidden: &[f32]) -> f32 {
+        // Couche de sortie : seule la première sortie porte le score d'anomalie
+        let logit = self.biases[0] + dot_product(&self.output_weights[0], hidden);
         
-        Ok(())
+        // Normaliser entre 0 et 1
+        1.0 / (1.0 + (-logit).exp())
     }
     
-    /// Analyse un paquet réseau
-    pub fn analyze_packet(&self, packet: NetworkPacket) -> Result<(FirewallDecision, Option<DetectionEvent>), String> {
-        // Vérifier l'état du système
-        let state = self.state.lock().unwrap();
-        if *state != NeuroFireWallState::Operational && *state != NeuroFireWallState::Learning {
-            return Err(format!("NeuroFireWall n'est pas opérationnel, état actuel: {:?}", state));
+    /// Met à jour le modèle avec un échantillon étiqueté (0.0 = légitime, 1.0 = malveillant)
+    ///
+    /// Effectue une descente de gradient sur l'entropie croisée binaire, rétropropagée
+    /// à travers les deux couches.
+    fn update(&mut self, features: &[f32], label: f32) {
+        if features.len() != self.input_size {
+            return;
         }
-        drop(state);
         
-        let start_time = Instant::now();
-        
-        // Extraire les caractéristiques du paquet
-        let features = self.extract_features(&packet)?;
+        let hidden = self.hidden_activations(features);
+        let prediction = self.output(&hidden);
         
-        // Prédire le score d'anomalie
-        let anomaly_score = {
-            let model = self.model.lock().unwrap();
-            model.predict(&features.features)
-        };
+        // Dérivée de l'entropie croisée par rapport au logit de sortie
+        let output_delta = prediction - label;
         
-        // Prendre une décision basée sur le score d'anomalie
-        let decision = self.make_decision(anomaly_score);
+        // Gradients de la couche cachée (calculés avant la mise à jour des poids de sortie)
+        let hidden_deltas: Vec<f32> = hidden
+            .iter()
+            .zip(&self.output_weights[0])
+            .map(|(&h, &weight)| if h > 0.0 { output_delta * weight } else { 0.0 })
+            .collect();
         
-        // Créer un événement de détection si nécessaire
-        let detection_event = if anomaly_score >= self.config.anomaly_threshold {
-            Some(DetectionEvent {
-                id: format!("event-{}", uuid::Uuid::new_v4()),
-                timestamp: SystemTime::now(),
-                anomaly_score,
-                decision: decision.clone(),
-                related_packets: vec![packet.id.clone()],
-                trigger_features: features.feature_labels.clone(),
-                description: format!("Anomalie détectée avec un score de {:.2}", anomaly_score),
-            })
-        } else {
-            None
-        };
+        // Couche de sortie
+        for (weight, &h) in self.output_weights[0].iter_mut().zip(&hidden) {
+            *weight -= self.learning_rate * output_delta * h;
+        }
+        self.biases[0] -= self.learning_rate * output_delta;
         
-        // Ajouter le paquet au buffer pour apprentissage futur
-        {
-            let mut buffer = self.packet_buffer.lock().unwrap();
-            buffer.push_back(packet.clone());
-            
-            // Limiter la taille du buffer
-            while buffer.len() > self.config.buffer_size {
-                buffer.pop_front();
+        // Couche cachée
+        for (row, &delta) in self.weights.iter_mut().zip(&hidden_deltas) {
+            for (weight, &feature) in row.iter_mut().zip(features) {
+                *weight -= self.learning_rate * delta * feature;
             }
         }
-        
-        // Mettre à jour les statistiques
-        let analysis_time_us = start_time.elapsed().as_micros() as f64;
-        let mut stats = self.stats.lock().unwrap();
-        stats.total_packets_analyzed += 1;
-        
-        match decision {
-            FirewallDecision::Allow => stats.packets_allowed += 1,
-            FirewallDecision::Block => stats.packets_blocked += 1,
-            FirewallDecision::Quarantine => stats.packets_quarantined += 1,
-            _ => {}
+        for (bias, &delta) in self.hidden_biases.iter_mut().zip(&hidden_deltas) {
+            *bias -= self.learning_rate * delta;
         }
-        
-        if detection_event.is_some() {
-            stats.detection_events += 1;
+    }
+}
+
+/// Nombre de sommes partielles indépendantes du produit scalaire accéléré
+const DOT_LANES: usize = 8;
+
+/// Produit scalaire de deux vecteurs, limité à la longueur du plus court
+///
+/// Avec la fonctionnalité `simd` (activée par défaut), l'accumulation est répartie sur
+/// `DOT_LANES` sommes partielles que le compilateur vectorise ; sinon elle est séquentielle.
+/// Les deux versions diffèrent uniquement par l'ordre des additions (écart de l'ordre de
+/// l'epsilon f32).
+fn dot_product(a: &[f32], b: &[f32]) -> f32 {
+    if cfg!(feature = "simd") {
+        dot_product_chunked(a, b)
+    } else {
+        dot_product_scalar(a, b)
+    }
+}
+
+/// Produit scalaire séquentiel
+fn dot_product_scalar(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(&x, &y)| x * y).sum()
+}
+
+/// Produit scalaire par blocs de `DOT_LANES` éléments, sans dépendance entre les sommes partielles
+fn dot_product_chunked(a: &[f32], b: &[f32]) -> f32 {
+    let length = a.len().min(b.len());
+    let (a, b) = (&a[..length], &b[..length]);
+    
+    let mut lanes = [0.0f32; DOT_LANES];
+    let mut a_chunks = a.chunks_exact(DOT_LANES);
+    let mut b_chunks = b.chunks_exact(DOT_LANES);
+    for (a_chunk, b_chunk) in a_chunks.by_ref().zip(b_chunks.by_ref()) {
+        for lane in 0..DOT_LANES {
+            lanes[lane] += a_chunk[lane] * b_chunk[lane];
         }
-        
-        // Mettre à jour le temps d'analyse moyen
-        stats.avg_analysis_time_us = (stats.avg_analysis_time_us * (stats.total_packets_analyzed - 1) as f64 + analysis_time_us) / stats.total_packets_analyzed as f64;
-        
-        Ok((decision, detection_event))
     }
     
-    /// Extrait les caractéristiques d'un paquet réseau
-    fn extract_features(&self, packet: &NetworkPacket) -> Result<PacketFeatures, String> {
-        // Cette fonction sera implémentée de manière plus sophistiquée dans les versions futures
-        // Pour l'instant, elle extrait des caractéristiques simples
-        
-        let mut features = Vec::with_capacity(10);
-        let mut feature_labels = Vec::with_capacity(10);
-        
-        // Caractéristique 1: Port de destination
-        features.push(packet.destination_port as f32 / 65535.0);
-        feature_labels.push("destination_port".to_string());
-        
-        // Caractéristique 2: Port source
-        features.push(packet.source_port as f32 / 65535.0);
-        feature_labels.push("source_port".to_string());
-        
-        // Caractéristique 3: Taille du paquet
-        features.push(packet.size as f32 / 1500.0); // Normaliser par MTU typique
-        feature_labels.push("packet_size".to_string());
-        
-        // Caractéristique 4: Type de protocole (simplifié)
-        let protocol_value = match packet.protocol.as_str() {
-            "TCP" => 0.1,
-            "UDP" => 0.2,
-            "ICMP" => 0.3,
-            "HTTP" => 0.4,
-            "HTTPS" => 0.5,
+    lanes.iter().sum::<f32>() + dot_product_scalar(a_chunks.remainder(), b_chunks.remainder())
+}
+
+/// Indique si une adresse appartient à un espace privé (RFC 1918, lien local, ULA IPv6)
+fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            let first_segment = v6.segments()[0];
+            // fc00::/7 (adresses locales uniques) et fe80::/10 (lien local)
+            (first_segment & 0xfe00) == 0xfc00 || (first_segment & 0xffc0) == 0xfe80
+        }
+    }
+}
+
+/// Indique si deux adresses partagent le même sous-réseau (/24 en IPv4, /64 en IPv6)
+fn is_same_subnet(a: &IpAddr, b: &IpAddr) -> bool {
+    match (a, b) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => a.octets()[..3] == b.octets()[..3],
+        (IpAddr::V6(a), IpAddr::V6(b)) => a.segments()[..4] == b.segments()[..4],
+        _ => false,
+    }
+}
+
+/// Paquets retenus en quarantaine, du plus ancien au plus récent
+#[derive(Default)]
+struct QuarantineStore {
+    /// Paquets indexés par identifiant
+    packets: HashMap<String, NetworkPacket>,
+    /// Ordre d'arrivée des identifiants
+    order: VecDeque<String>,
+}
+
+/// Classification d'un paquet par le modèle, conservée dans le cache
+#[derive(Debug, Clone)]
+struct CachedClassification {
+    /// Score d'anomalie calculé par le modèle
+    anomaly_score: f32,
+    /// Décision du modèle, avant limitation de débit
+    decision: FirewallDecision,
+}
+
+/// Classifications des paquets déjà analysés, évincées de la moins récemment utilisée
+///
+/// Chaque accès ajoute l'empreinte en fin de `recency` avec un nouveau numéro d'accès ; seule
+/// l'occurrence portant le dernier numéro de l'entrée est valide, les autres sont ignorées à
+/// l'éviction puis retirées lorsque `recency` dépasse le double de la capacité. Lecture et
+/// insertion coûtent ainsi O(1) amorti.
+#[derive(Default)]
+struct ClassificationCache {
+    /// Classification et numéro du dernier accès, indexés par empreinte du paquet
+    entries: HashMap<u64, (CachedClassification, u64)>,
+    /// Empreintes et numéros d'accès, du plus ancien au plus récent
+    recency: VecDeque<(u64, u64)>,
+    /// Numéro du prochain accès
+    next_access: u64,
+    /// Fonction de hachage à clé aléatoire, pour qu'un attaquant ne puisse pas provoquer de collision
+    hasher: RandomState,
+}
+
+impl ClassificationCache {
+    /// Classification d'une empreinte, marquée comme la plus récemment utilisée
+    fn get(&mut self, key: u64, capacity: usize) -> Option<CachedClassification> {
+        let access = self.next_access;
+        let (classification, last_access) = self.entries.get_mut(&key)?;
+        *last_access = access;
+        let classification = classification.clone();
+        self.record_access(key, access, capacity);
+        Some(classification)
+    }
+    
+    /// Conserve une classification, en évinçant les moins récemment utilisées au-delà de `capacity`
+    fn insert(&mut self, key: u64, classification: CachedClassification, capacity: usize) {
+        let access = self.next_access;
+        self.entries.insert(key, (classification, access));
+        self.record_access(key, access, capacity);
+        
+        while self.entries.len() > capacity {
+            let Some((oldest, oldest_access)) = self.recency.pop_front() else {
+                break;
+            };
+            if self.is_current(oldest, oldest_access) {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+    
+    /// Vide le cache
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+    
+    /// Ajoute un accès à l'ordre d'utilisation et retire les occurrences périmées si nécessaire
+    fn record_access(&mut self, key: u64, access: u64, capacity: usize) {
+        self.next_access += 1;
+        self.recency.push_back((key, access));
+        if self.recency.len() > capacity.max(1) * 2 {
+            let entries = &self.entries;
+            self.recency
+                .retain(|(key, access)| entries.get(key).is_some_and(|(_, last_access)| last_access == access));
+        }
+    }
+    
+    /// Indique si une occurrence de `recency` correspond au dernier accès de son entrée
+    fn is_current(&self, key: u64, access: u64) -> bool {
+        self.entries.get(&key).is_some_and(|(_, last_access)| *last_access == access)
+    }
+}
+
+/// Sources bloquées et historique des récidives
+#[derive(Default)]
+struct Blocklist {
+    /// Échéance du blocage de chaque source actuellement bloquée
+    entries: HashMap<String, SystemTime>,
+    /// Récidives par source, conservées après expiration pour le calcul du délai
+    offenses: HashMap<String, Offense>,
+    /// Sources suivies, de la moins récemment bloquée à la plus récente (par numéro d'ordre)
+    offense_order: BTreeMap<u64, String>,
+    /// Numéro d'ordre attribué au prochain blocage
+    next_sequence: u64,
+}
+
+/// Récidives d'une source
+struct Offense {
+    /// Nombre de blocages subis depuis la dernière période calme
+    count: u32,
+    /// Échéance du dernier blocage
+    expires_at: SystemTime,
+    /// Numéro d'ordre du dernier blocage dans `Blocklist::offense_order`
+    sequence: u64,
+}
+
+impl Blocklist {
+    /// Nombre de blocages passés d'une source, 0 si elle est restée calme pendant `decay` depuis
+    /// la fin de son dernier blocage
+    fn offense_count(&self, ip: &str, now: SystemTime, decay: Duration) -> u32 {
+        self.offenses
+            .get(ip)
+            .filter(|offense| offense.expires_at + decay > now)
+            .map_or(0, |offense| offense.count)
+    }
+    
+    /// Enregistre le `count`-ième blocage d'une source, en oubliant la source bloquée le moins
+    /// récemment si `max_offenders` sources sont déjà suivies
+    fn record_offense(&mut self, ip: &str, count: u32, expires_at: SystemTime, max_offenders: usize) {
+        if let Some(previous) = self.offenses.remove(ip) {
+            self.offense_order.remove(&previous.sequence);
+        }
+        while self.offenses.len() >= max_offenders.max(1) {
+            let Some((_, oldest)) = self.offense_order.pop_first() else {
+                break;
+            };
+            self.offenses.remove(&oldest);
+        }
+        
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.offense_order.insert(sequence, ip.to_string());
+        self.offenses.insert(ip.to_string(), Offense { count, expires_at, sequence });
+    }
+    
+    /// Oublie les récidives des sources restées calmes pendant `decay`
+    fn forget_offenses(&mut self, now: SystemTime, decay: Duration) {
+        let order = &mut self.offense_order;
+        self.offenses.retain(|_, offense| {
+            let keep = offense.expires_at + decay > now;
+            if !keep {
+                order.remove(&offense.sequence);
+            }
+            keep
+        });
+    }
+}
+
+/// Compteurs de paquets mis à jour sans verrou depuis le chemin d'analyse
+#[derive(Default)]
+struct StatsCounters {
+    total_packets_analyzed: AtomicU64,
+    packets_allowed: AtomicU64,
+    packets_blocked: AtomicU64,
+    packets_quarantined: AtomicU64,
+    packets_rate_limited: AtomicU64,
+    detection_events: AtomicU64,
+    events_dropped: AtomicU64,
+    allowed_trusted: AtomicU64,
+    cache_hits: AtomicU64,
+    learning_cycles: AtomicU64,
+    /// Nombre de scores d'anomalie observés par intervalle de largeur 1 / `SCORE_HISTOGRAM_BINS`
+    score_histogram: [AtomicU64; NeuroFireWall::SCORE_HISTOGRAM_BINS],
+}
+
+impl StatsCounters {
+    /// Remet tous les compteurs à zéro
+    fn reset(&self) {
+        for counter in [
+            &self.total_packets_analyzed,
+            &self.packets_allowed,
+            &self.packets_blocked,
+            &self.packets_quarantined,
+            &self.packets_rate_limited,
+            &self.detection_events,
+            &self.events_dropped,
+            &self.allowed_trusted,
+            &self.cache_hits,
+            &self.learning_cycles,
+        ]
+        .into_iter()
+        .chain(&self.score_histogram)
+        {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Statistiques en virgule flottante, mises à jour sous verrou
+#[derive(Default)]
+struct TimingStats {
+    /// Nombre de mesures prises en compte dans la moyenne
+    samples: u64,
+    avg_analysis_time_us: f64,
+    min_analysis_time_us: f64,
+    max_analysis_time_us: f64,
+}
+
+/// Retour d'un opérateur sur un événement de détection
+struct OperatorFeedback {
+    /// Identifiant de l'événement évalué
+    event_id: String,
+    /// Le trafic a été signalé comme malveillant par le pare-feu
+    flagged: bool,
+    /// Le trafic était réellement malveillant
+    malicious: bool,
+}
+
+/// Taux d'erreur estimés sur une fenêtre de retours d'opérateur
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ErrorRates {
+    /// Part du trafic légitime signalé à tort (0.0 sans retour sur du trafic légitime)
+    pub false_positive_rate: f32,
+    /// Part du trafic malveillant non signalé (0.0 sans retour sur du trafic malveillant)
+    pub false_negative_rate: f32,
+}
+
+/// Paquet observé, conservé pour les statistiques glissantes
+struct TrafficSample {
+    /// Instant de réception
+    received_at: Instant,
+    /// Adresse IP source
+    source_ip: String,
+    /// Taille du paquet en octets
+    size: u32,
+}
+
+/// Instantané du modèle appris, produit par `NeuroFireWall::export_model`
+#[derive(Serialize, Deserialize)]
+struct ModelSnapshot {
+    /// Version du format (voir `NeuroFireWall::MODEL_SNAPSHOT_VERSION`)
+    version: u32,
+    model: NeuralModel,
+}
+
+/// Rappel invoqué lorsqu'un paquet est redirigé vers un honeypot
+pub type RedirectHook = Arc<dyn Fn(&NetworkPacket) + Send + Sync>;
+
+/// Extraction des caractéristiques d'un paquet, enfichable dans le NeuroFireWall
+///
+/// La dimension détermine la taille d'entrée du modèle : chaque appel à `extract` doit
+/// produire exactement `dimension()` valeurs, dans l'ordre de `feature_labels()`.
+///
+/// Le cache de classification suppose que les caractéristiques ne dépendent ni de
+/// l'identifiant ni de l'horodatage du paquet ; un extracteur qui les utilise impose
+/// `classification_cache_size = 0`.
+pub trait PacketFeatureExtractor: Send + Sync {
+    /// Extrait le vecteur de caractéristiques d'un paquet
+    fn extract(&self, packet: &NetworkPacket) -> Vec<f32>;
+    
+    /// Nombre de caractéristiques produites par paquet
+    fn dimension(&self) -> usize;
+    
+    /// Nom de chaque caractéristique (repris dans les événements de détection)
+    fn feature_labels(&self) -> Vec<String> {
+        (0..self.dimension()).map(|i| format!("feature_{}", i)).collect()
+    }
+}
+
+/// Extracteur par défaut : en-têtes, premiers octets et entropie de la charge utile
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFeatureExtractor;
+
+impl DefaultFeatureExtractor {
+    /// Nombre de caractéristiques extraites par paquet
+    pub const FEATURE_COUNT: usize = 20;
+    /// Nombre d'octets de la charge utile repris tels quels dans les caractéristiques
+    pub const PAYLOAD_BYTE_FEATURES: usize = 5;
+    /// Nombre de caractéristiques propres au DNS (nulles pour les autres types de trafic)
+    pub const DNS_FEATURES: usize = 4;
+    /// Longueur maximale d'un nom de domaine
+    const MAX_DNS_NAME_LENGTH: f32 = 253.0;
+    /// Nombre de labels au-delà duquel la caractéristique est saturée
+    const MAX_DNS_LABELS: f32 = 16.0;
+    
+    /// Indicateurs de tunnel DNS : longueur du nom interrogé, nombre de labels, proportion de
+    /// caractères hexadécimaux et entropie des sous-domaines (normalisés dans [0, 1])
+    fn dns_features(packet: &NetworkPacket) -> [f32; Self::DNS_FEATURES] {
+        let Some(name) = dns_query_name(&packet.payload_sample) else {
+            return [0.0; Self::DNS_FEATURES];
+        };
+        let labels: Vec<&str> = name.split('.').filter(|label| !label.is_empty()).collect();
+        
+        let characters: Vec<char> = labels.iter().flat_map(|label| label.chars()).collect();
+        let hex_ratio = if characters.is_empty() {
+            0.0
+        } else {
+            characters.iter().filter(|c| c.is_ascii_hexdigit()).count() as f32 / characters.len() as f32
+        };
+        
+        // Les sous-domaines précèdent le domaine enregistré (deux derniers labels)
+        let subdomains = labels[..labels.len().saturating_sub(2)].concat();
+        
+        [
+            (name.len() as f32 / Self::MAX_DNS_NAME_LENGTH).min(1.0),
+            (labels.len() as f32 / Self::MAX_DNS_LABELS).min(1.0),
+            hex_ratio,
+            (shannon_entropy(subdomains.as_bytes()) / 8.0).clamp(0.0, 1.0),
+        ]
+    }
+}
+
+/// Nom de domaine interrogé par une requête DNS
+///
+/// La charge utile est lue au format filaire (en-tête de 12 octets suivi du nom en labels
+/// préfixés par leur longueur) ou, à défaut, comme un nom textuel (`www.example.com`).
+fn dns_query_name(payload: &[u8]) -> Option<String> {
+    const DNS_HEADER_LENGTH: usize = 12;
+    
+    let wire_name = || {
+        let mut labels = Vec::new();
+        let mut offset = DNS_HEADER_LENGTH;
+        loop {
+            let length = *payload.get(offset)? as usize;
+            if length == 0 {
+                break;
+            }
+            // Les pointeurs de compression (deux bits de poids fort) n'apparaissent pas dans une question
+            if length > 63 {
+                return None;
+            }
+            let label = payload.get(offset + 1..offset + 1 + length)?;
+            if !label.iter().all(|byte| byte.is_ascii_graphic()) {
+                return None;
+            }
+            labels.push(String::from_utf8_lossy(label).into_owned());
+            offset += 1 + length;
+        }
+        (!labels.is_empty()).then(|| labels.join("."))
+    };
+    
+    let text_name = || {
+        let text = payload.trim_ascii();
+        (!text.is_empty() && text.iter().all(|byte| byte.is_ascii_graphic()))
+            .then(|| String::from_utf8_lossy(text).into_owned())
+    };
+    
+    wire_name().or_else(text_name)
+}
+
+impl PacketFeatureExtractor for DefaultFeatureExtractor {
+    fn extract(&self, packet: &NetworkPacket) -> Vec<f32> {
+        // Cette fonction sera implémentée de manière plus sophistiquée dans les versions futures
+        // Pour l'instant, elle extrait des caractéristiques simples
+        
+        let mut features = Vec::with_capacity(Self::FEATURE_COUNT);
+        
+        // Caractéristique 1: Port de destination
+        features.push(packet.destination_port as f32 / 65535.0);
+        
+        // Caractéristique 2: Port source
+        features.push(packet.source_port as f32 / 65535.0);
+        
+        // Caractéristique 3: Taille du paquet
+        features.push((packet.size as f32 / 1500.0).min(1.0)); // Normaliser par MTU typique, borné pour les trames jumbo
+        
+        // Caractéristique 4: Type de protocole (simplifié)
+        let protocol_value = match packet.protocol.as_str() {
+            "TCP" => 0.1,
+            "UDP" => 0.2,
+            "ICMP" => 0.3,
+            "HTTP" => 0.4,
+            "HTTPS" => 0.5,
             _ => 0.9,
         };
         features.push(protocol_value);
-        feature_labels.push("protocol".to_string());
         
         // Caractéristique 5: Type de trafic
         let traffic_type_value = match packet.traffic_type {
@@ -422,187 +1337,3086 @@ impl NeuroFireWall {
             TrafficType::Unknown => 0.9,
         };
         features.push(traffic_type_value);
-        feature_labels.push("traffic_type".to_string());
         
         // Caractéristiques 6-10: Échantillon de charge utile (simplifié)
-        // Prendre jusqu'à 5 octets de la charge utile et les normaliser
-        for i in 0..5 {
-            let byte_value = if i < packet.payload_sample.len() {
-                packet.payload_sample[i] as f32 / 255.0
-            } else {
-                0.0
-            };
+        // Prendre jusqu'à 5 octets de la charge utile et les normaliser (0.0 au-delà de la fin)
+        for i in 0..Self::PAYLOAD_BYTE_FEATURES {
+            let byte_value = packet.payload_sample.get(i).map_or(0.0, |&byte| byte as f32 / 255.0);
             features.push(byte_value);
-            feature_labels.push(format!("payload_byte_{}", i));
         }
         
-        // Calculer un score d'anomalie fictif (sera remplacé par le modèle)
-        let anomaly_score = 0.0; // Sera calculé par le modèle
+        // Caractéristiques 11-15: Adresses IP (IPv4 et IPv6)
+        // Une adresse illisible est signalée explicitement plutôt que rejetée
+        let source = packet.source_ip.parse::<IpAddr>().ok();
+        let destination = packet.destination_ip.parse::<IpAddr>().ok();
         
-        Ok(PacketFeatures {
-            packet_id: packet.id.clone(),
-            features,
-            feature_labels,
-            anomaly_score,
-        })
+        let flag = |value: bool| if value { 1.0 } else { 0.0 };
+        
+        features.push(flag(source.as_ref().is_some_and(is_private_ip)));
+        
+        features.push(flag(source.as_ref().is_some_and(IpAddr::is_loopback)));
+        
+        features.push(flag(source.as_ref().is_some_and(IpAddr::is_ipv6)));
+        
+        let same_subnet = match (&source, &destination) {
+            (Some(source), Some(destination)) => is_same_subnet(source, destination),
+            _ => false,
+        };
+        features.push(flag(same_subnet));
+        
+        features.push(flag(source.is_none() || destination.is_none()));
+        
+        // Caractéristique 16: Entropie de la charge utile (normalisée sur 8 bits par octet)
+        // Une entropie élevée signale un contenu chiffré ou compressé (exfiltration, malware packé).
+        // Une charge vide ou d'un seul octet a une entropie nulle ; le résultat est borné pour
+        // absorber les erreurs d'arrondi.
+        features.push((shannon_entropy(&packet.payload_sample) / 8.0).clamp(0.0, 1.0));
+        
+        // Caractéristiques 17-20: Indicateurs de tunnel DNS, extraits uniquement pour le trafic DNS
+        if packet.traffic_type == TrafficType::Dns {
+            features.extend(Self::dns_features(packet));
+        } else {
+            features.extend([0.0; Self::DNS_FEATURES]);
+        }
+        
+        features
+    }
+    
+    fn dimension(&self) -> usize {
+        Self::FEATURE_COUNT
+    }
+    
+    fn feature_labels(&self) -> Vec<String> {
+        let mut labels = Vec::with_capacity(Self::FEATURE_COUNT);
+        labels.extend(["destination_port", "source_port", "packet_size", "protocol", "traffic_type"].map(String::from));
+        labels.extend((0..Self::PAYLOAD_BYTE_FEATURES).map(|i| format!("payload_byte_{}", i)));
+        labels.extend(
+            ["source_is_private", "source_is_loopback", "source_is_ipv6", "same_subnet", "invalid_address", "payload_entropy"]
+                .map(String::from),
+        );
+        labels.extend(
+            ["dns_query_length", "dns_label_count", "dns_hex_ratio", "dns_subdomain_entropy"].map(String::from),
+        );
+        labels
+    }
+}
+
+/// Adaptateur de l'extracteur du moteur neuronal : la charge utile tient lieu de flux
+impl PacketFeatureExtractor for FeatureExtractor {
+    fn extract(&self, packet: &NetworkPacket) -> Vec<f32> {
+        self.extract_features(&packet.payload_sample)
+    }
+    
+    fn dimension(&self) -> usize {
+        self.feature_dimension()
+    }
+}
+
+/// NeuroFireWall principal
+pub struct NeuroFireWall {
+    config: NeuroFireWallConfig,
+    state: Arc<Mutex<NeuroFireWallState>>,
+    /// Instant de passage à l'état opérationnel (None si arrêté)
+    started_at: Arc<Mutex<Option<Instant>>>,
+    /// Compteurs atomiques : incrémentés sans sérialiser les analyses concurrentes
+    counters: Arc<StatsCounters>,
+    /// Temps d'analyse et taux d'erreur, seuls à nécessiter un verrou
+    timing: Arc<Mutex<TimingStats>>,
+    packet_buffer: Arc<Mutex<VecDeque<NetworkPacket>>>,
+    /// Étiquettes fournies par les opérateurs (identifiant de paquet -> 1.0 si malveillant)
+    labels: Arc<Mutex<HashMap<String, f32>>>,
+    /// Derniers retours d'opérateur sur les détections (au plus `feedback_window_size`)
+    operator_feedback: Arc<Mutex<VecDeque<OperatorFeedback>>>,
+    /// Retours d'opérateur reçus depuis le dernier ajustement des seuils
+    feedback_since_adjustment: Arc<AtomicU64>,
+    /// Moteur neuronal dont les seuils suivent les retours d'opérateur
+    neural_engine: Option<Arc<NeuralNetEngine>>,
+    /// Plages d'adresses des sources de confiance, autorisées sans analyse
+    trusted_sources: Arc<Mutex<HashSet<IpNet>>>,
+    /// Adresses IP source bloquées sans analyse, avec leur échéance
+    blocklist: Arc<Mutex<Blocklist>>,
+    /// Horodatages des paquets récents par source (fenêtre glissante d'une seconde)
+    source_windows: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+    /// Paquets observés sur les `stats_window_secs` dernières secondes (au plus
+    /// `max_window_samples`), du plus ancien au plus récent
+    traffic_window: Arc<Mutex<VecDeque<TrafficSample>>>,
+    /// Derniers événements de détection (au plus `max_retained_events`), du plus ancien au plus récent
+    recent_events: Arc<Mutex<VecDeque<DetectionEvent>>>,
+    /// Rappel de redirection (activation d'un environnement WarpShield par exemple)
+    redirect_hook: Option<RedirectHook>,
+    /// Bus sur lequel les détections sont publiées
+    event_bus: Option<EventBus>,
+    /// Paquets mis en quarantaine en attente de revue
+    quarantine: Arc<Mutex<QuarantineStore>>,
+    /// Décisions du modèle pour les paquets identiques récents (invalidées à chaque apprentissage)
+    classification_cache: Arc<Mutex<ClassificationCache>>,
+    /// Signatures d'attaque (générées par WarpShield) appliquées en temps réel
+    signatures: Arc<Mutex<Vec<AttackSignature>>>,
+    /// Modèle partagé : les analyses concurrentes le lisent en parallèle, seul l'apprentissage l'écrit
+    model: Arc<RwLock<NeuralModel>>,
+    /// Source de temps (échéances de blocage, horodatage des détections)
+    clock: SharedClock,
+    /// Générateur des identifiants d'événements de détection
+    ids: SharedIdGenerator,
+    /// Extracteur des caractéristiques fournies au modèle
+    feature_extractor: Box<dyn PacketFeatureExtractor>,
+    /// Noms des caractéristiques, dans l'ordre du vecteur extrait
+    feature_labels: Vec<String>,
+    /// Échecs d'analyse consécutifs (remis à zéro au premier succès)
+    consecutive_errors: Arc<AtomicU64>,
+    // Les champs suivants seront implémentés dans les versions futures
+    // decision_engine: DecisionEngine,
+    // learning_manager: LearningManager,
+}
+
+impl NeuroFireWall {
+    /// Nombre de caractéristiques retenues dans un événement de détection
+    const TOP_FEATURES: usize = 3;
+    /// Sensibilité pour laquelle les seuils configurés s'appliquent tels quels
+    const NEUTRAL_SENSITIVITY: f32 = 0.75;
+    /// Variation relative des seuils par unité de sensibilité
+    const SENSITIVITY_GAIN: f32 = 0.4;
+    
+    /// Fenêtre glissante utilisée pour la limitation de débit par source
+    const RATE_WINDOW: Duration = Duration::from_secs(1);
+    /// Nombre de sources retenues dans `WindowStats::top_talkers`
+    const TOP_TALKERS: usize = 5;
+    /// Version du format des instantanés de modèle
+    const MODEL_SNAPSHOT_VERSION: u32 = 2;
+    /// Nombre d'intervalles de l'histogramme des scores d'anomalie
+    pub const SCORE_HISTOGRAM_BINS: usize = 20;
+    
+    /// Crée une nouvelle instance de NeuroFireWall avec l'extracteur par défaut
+    pub fn new(config: NeuroFireWallConfig) -> Self {
+        Self::with_feature_extractor(config, Box::new(DefaultFeatureExtractor))
+    }
+    
+    /// Crée une instance utilisant l'extracteur de caractéristiques fourni
+    ///
+    /// La taille d'entrée du modèle suit la dimension de l'extracteur.
+    pub fn with_feature_extractor(config: NeuroFireWallConfig, feature_extractor: Box<dyn PacketFeatureExtractor>) -> Self {
+        // Créer un modèle neuronal simplifié
+        // Dans une implémentation réelle, ce serait un réseau neuronal plus complexe
+        let model = NeuralModel::new(
+            feature_extractor.dimension(),
+            config.hidden_layer_size,
+            1,
+            config.learning_rate,
+            config.model_seed,
+        );
+        let buffer_capacity = config.buffer_size;
+        
+        Self {
+            config,
+            state: Arc::new(Mutex::new(NeuroFireWallState::Initializing)),
+            started_at: Arc::new(Mutex::new(None)),
+            counters: Arc::new(StatsCounters::default()),
+            timing: Arc::new(Mutex::new(TimingStats::default())),
+            packet_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(buffer_capacity))),
+            labels: Arc::new(Mutex::new(HashMap::new())),
+            operator_feedback: Arc::new(Mutex::new(VecDeque::new())),
+            feedback_since_adjustment: Arc::new(AtomicU64::new(0)),
+            neural_engine: None,
+            trusted_sources: Arc::new(Mutex::new(HashSet::new())),
+            blocklist: Arc::new(Mutex::new(Blocklist::default())),
+            source_windows: Arc::new(Mutex::new(HashMap::new())),
+            traffic_window: Arc::new(Mutex::new(VecDeque::new())),
+            recent_events: Arc::new(Mutex::new(VecDeque::new())),
+            redirect_hook: None,
+            event_bus: None,
+            quarantine: Arc::new(Mutex::new(QuarantineStore::default())),
+            classification_cache: Arc::new(Mutex::new(ClassificationCache::default())),
+            signatures: Arc::new(Mutex::new(Vec::new())),
+            model: Arc::new(RwLock::new(model)),
+            clock: system_clock(),
+            ids: random_ids(),
+            feature_labels: feature_extractor.feature_labels(),
+            feature_extractor,
+            consecutive_errors: Arc::new(AtomicU64::new(0)),
+            // Les champs suivants seront initialisés dans les versions futures
+        }
+    }
+    
+    /// Initialise le NeuroFireWall
+    pub fn initialize(&mut self) -> Result<(), String> {
+        if let Some(thresholds) = &self.config.decision_thresholds {
+            thresholds.validate()?;
+        }
+        for source in &self.config.trusted_sources {
+            self.add_trusted(source)?;
+        }
+        
+        let mut state = self.state.lock().unwrap();
+        *state = NeuroFireWallState::Operational;
+        *self.started_at.lock().unwrap() = Some(Instant::now());
+        
+        module_log!(self.config.log_level, Level::Info, "NeuroFireWall opérationnel");
+        
+        Ok(())
+    }
+    
+    /// Analyse un paquet réseau
+    ///
+    /// Après `max_consecutive_errors` échecs consécutifs (extraction des caractéristiques ou
+    /// prédiction du modèle), le pare-feu passe en mode
+    /// dégradé et applique `fallback_decision` aux paquets qu'il ne parvient pas à analyser ; il
+    /// redevient opérationnel à la première analyse réussie.
+    pub fn analyze_packet(&self, packet: NetworkPacket) -> Result<(FirewallDecision, Option<DetectionEvent>), String> {
+        // Vérifier l'état du système
+        let state = self.state.lock().unwrap();
+        if !matches!(
+            *state,
+            NeuroFireWallState::Operational | NeuroFireWallState::Learning | NeuroFireWallState::Degraded
+        ) {
+            return Err(format!("NeuroFireWall n'est pas opérationnel, état actuel: {:?}", state));
+        }
+        drop(state);
+        
+        let start_time = Instant::now();
+        self.record_traffic(&packet, start_time);
+        
+        // Les sources de confiance ne sont ni analysées ni bloquées
+        if self.is_trusted(&packet.source_ip) {
+            let decision = FirewallDecision::Allow;
+            self.counters.allowed_trusted.fetch_add(1, Ordering::Relaxed);
+            self.record_analysis(&decision, false, start_time);
+            module_log!(self.config.log_level, Level::Debug, "Paquet {} de la source de confiance {} autorisé", packet.id, packet.source_ip);
+            return Ok((decision, None));
+        }
+        
+        // Les sources bloquées le restent sans passer par le modèle
+        if self.is_blocked(&packet.source_ip) {
+            let decision = FirewallDecision::Block;
+            self.record_analysis(&decision, false, start_time);
+            return Ok((decision, None));
+        }
+        
+        // Les paquets correspondant à une signature connue sont bloqués directement
+        if let Some((signature_id, pattern, confidence)) = self.match_signatures(&packet) {
+            let decision = FirewallDecision::Block;
+            let detection_event = DetectionEvent {
+                id: self.ids.next_id("event"),
+                timestamp: self.clock.now(),
+                anomaly_score: confidence,
+                decision: decision.clone(),
+                related_packets: vec![packet.id.clone()],
+                trigger_features: vec![pattern],
+                feature_scores: vec![confidence],
+                description: format!("Correspondance avec la signature {}", signature_id),
+            };
+            
+            module_log!(self.config.log_level, Level::Info, "Paquet {} bloqué par la signature {}", packet.id, signature_id);
+            
+            if self.config.auto_block_sources {
+                self.block_source(&packet.source_ip);
+            }
+            
+            self.retain_event(&detection_event);
+            self.record_analysis(&decision, true, start_time);
+            return Ok((decision, Some(detection_event)));
+        }
+        
+        // Les paquets identiques à un paquet déjà classé sans détection reprennent le score et la
+        // décision du modèle sans nouvelle extraction ; le reste du traitement est inchangé
+        let cache_key = (self.config.classification_cache_size > 0).then(|| self.classification_key(&packet));
+        if let Some(cached) = cache_key.and_then(|key| self.cached_classification(key)) {
+            self.counters.cache_hits.fetch_add(1, Ordering::Relaxed);
+            self.record_score(cached.anomaly_score);
+            let decision = self.apply_rate_limit(&packet.source_ip, cached.decision, start_time);
+            self.retain_for_learning(&packet);
+            if decision == FirewallDecision::Block && self.config.auto_block_sources {
+                self.block_source(&packet.source_ip);
+            }
+            self.record_analysis(&decision, false, start_time);
+            return Ok((decision, None));
+        }
+        
+        // Extraire les caractéristiques du paquet, prédire le score d'anomalie et l'attribuer
+        // aux caractéristiques
+        let scored = self.extract_features(&packet).and_then(|features| {
+            let model = self.model.read().unwrap();
+            let anomaly_score = model.predict(&features.features)?;
+            let contributions = model.feature_contributions(&features.features);
+            Ok((features, anomaly_score, contributions))
+        });
+        let (features, anomaly_score, contributions) = match scored {
+            Ok(scored) => {
+                self.recover_from_errors();
+                scored
+            }
+            Err(e) => {
+                if !self.record_analysis_error(&e) {
+                    return Err(e);
+                }
+                let decision = Self::fallback_decision(&packet);
+                self.record_analysis(&decision, false, start_time);
+                return Ok((decision, None));
+            }
+        };
+        self.record_score(anomaly_score);
+        
+        // Prendre une décision basée sur le score d'anomalie
+        let mut decision = self.make_decision(anomaly_score);
+        
+        // Les anomalies de confiance moyenne visant un service piégeable sont redirigées
+        if decision == FirewallDecision::Alert && Self::is_redirect_candidate(&packet) {
+            decision = FirewallDecision::Redirect;
+        }
+        let model_decision = decision.clone();
+        let decision = self.apply_rate_limit(&packet.source_ip, decision, start_time);
+        
+        // Créer un événement de détection si nécessaire
+        let detection_event = if anomaly_score >= self.get_current_threshold() || decision == FirewallDecision::Redirect {
+            let (trigger_features, feature_scores) = Self::top_contributors(&features.feature_labels, &contributions);
+            Some(DetectionEvent {
+                id: self.ids.next_id("event"),
+                timestamp: self.clock.now(),
+                anomaly_score,
+                decision: decision.clone(),
+                related_packets: vec![packet.id.clone()],
+                trigger_features,
+                feature_scores,
+                description: format!("Anomalie détectée avec un score de {:.2}", anomaly_score),
+            })
+        } else {
+            None
+        };
+        
+        // Seules les décisions sans détection sont mises en cache : les copies d'une menace
+        // restent analysées pour produire leurs événements
+        if let Some(key) = cache_key.filter(|_| detection_event.is_none() && model_decision != FirewallDecision::Quarantine) {
+            self.cache_classification(key, CachedClassification { anomaly_score, decision: model_decision });
+        }
+        
+        self.retain_for_learning(&packet);
+        
+        if decision == FirewallDecision::Block && self.config.auto_block_sources {
+            self.block_source(&packet.source_ip);
+        }
+        
+        if decision == FirewallDecision::Quarantine {
+            self.quarantine_packet(packet.clone());
+        }
+        
+        if decision == FirewallDecision::Redirect {
+            if let Some(hook) = &self.redirect_hook {
+                hook(&packet);
+            }
+        }
+        
+        if decision != FirewallDecision::Allow {
+            module_log!(
+                self.config.log_level,
+                Level::Debug,
+                "Paquet {} de {}: {:?} (score {:.2})",
+                packet.id,
+                packet.source_ip,
+                decision,
+                anomaly_score
+            );
+        }
+        
+        // Mettre à jour les statistiques
+        if let Some(event) = &detection_event {
+            self.retain_event(event);
+        }
+        self.record_analysis(&decision, detection_event.is_some(), start_time);
+        
+        Ok((decision, detection_event))
+    }
+    
+    /// Analyse un paquet sans bloquer l'exécuteur asynchrone
+    ///
+    /// L'analyse (verrous, calcul du modèle) s'exécute sur le pool de threads bloquants de
+    /// tokio, ce qui permet de l'attendre depuis un gestionnaire Rocket. Le résultat est celui
+    /// d'`analyze_packet`.
+    pub async fn analyze_packet_async(self: Arc<Self>, packet: NetworkPacket) -> Result<(FirewallDecision, Option<DetectionEvent>), String> {
+        tokio::task::spawn_blocking(move || self.analyze_packet(packet))
+            .await
+            .map_err(|e| format!("Analyse asynchrone interrompue: {}", e))?
+    }
+    
+    /// Analyse un flux réassemblé comme un paquet unique portant la charge utile concaténée
+    ///
+    /// Détecte les motifs répartis sur plusieurs paquets ; l'événement de détection éventuel
+    /// référence l'ensemble des paquets du flux.
+    ///
+    /// Les paquets du flux ayant déjà été analysés un par un, le flux est évalué directement
+    /// (signatures puis modèle) sans repasser par la comptabilisation des paquets : statistiques
+    /// de paquets, limitation de débit, cache de classification et mémoire tampon
+    /// d'apprentissage sont inchangés. Seule une détection est comptabilisée.
+    pub fn analyze_flow(&self, flow: Flow) -> Result<(FirewallDecision, Option<DetectionEvent>), String> {
+        if flow.packets.is_empty() {
+            return Err(format!("Flux vide: {:?}", flow.key));
+        }
+        
+        let state = self.get_state();
+        if !matches!(
+            state,
+            NeuroFireWallState::Operational | NeuroFireWallState::Learning | NeuroFireWallState::Degraded
+        ) {
+            return Err(format!("NeuroFireWall n'est pas opérationnel, état actuel: {:?}", state));
+        }
+        
+        let packet = flow.to_packet();
+        if self.is_trusted(&packet.source_ip) {
+            return Ok((FirewallDecision::Allow, None));
+        }
+        if self.is_blocked(&packet.source_ip) {
+            return Ok((FirewallDecision::Block, None));
+        }
+        
+        let detection_event = match self.match_signatures(&packet) {
+            Some((signature_id, pattern, confidence)) => DetectionEvent {
+                id: self.ids.next_id("event"),
+                timestamp: self.clock.now(),
+                anomaly_score: confidence,
+                decision: FirewallDecision::Block,
+                related_packets: flow.packet_ids(),
+                trigger_features: vec![pattern],
+                feature_scores: vec![confidence],
+                description: format!("Correspondance avec la signature {}", signature_id),
+            },
+            None => {
+                let features = self.extract_features(&packet)?;
+                let (anomaly_score, contributions) = {
+                    let model = self.model.read().unwrap();
+                    (model.predict(&features.features)?, model.feature_contributions(&features.features))
+                };
+                let decision = self.make_decision(anomaly_score);
+                if anomaly_score < self.get_current_threshold() {
+                    return Ok((decision, None));
+                }
+                
+                let (trigger_features, feature_scores) = Self::top_contributors(&features.feature_labels, &contributions);
+                DetectionEvent {
+                    id: self.ids.next_id("event"),
+                    timestamp: self.clock.now(),
+                    anomaly_score,
+                    decision,
+                    related_packets: flow.packet_ids(),
+                    trigger_features,
+                    feature_scores,
+                    description: format!("Anomalie détectée avec un score de {:.2}", anomaly_score),
+                }
+            }
+        };
+        
+        let decision = detection_event.decision.clone();
+        if decision == FirewallDecision::Block && self.config.auto_block_sources {
+            self.block_source(&packet.source_ip);
+        }
+        
+        self.counters.detection_events.fetch_add(1, Ordering::Relaxed);
+        self.retain_event(&detection_event);
+        
+        Ok((decision, Some(detection_event)))
+    }
+    
+    /// Sélectionne les `TOP_FEATURES` caractéristiques de plus forte contribution non nulle
+    fn top_contributors(labels: &[String], contributions: &[f32]) -> (Vec<String>, Vec<f32>) {
+        let mut ranked: Vec<(&String, f32)> = labels
+            .iter()
+            .zip(contributions.iter().copied())
+            .filter(|&(_, contribution)| contribution > 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        
+        ranked
+            .into_iter()
+            .take(Self::TOP_FEATURES)
+            .map(|(label, contribution)| (label.clone(), contribution))
+            .unzip()
+    }
+    
+    /// Indique si un paquet peut être redirigé vers un honeypot :
+    /// trafic web ou SSH provenant d'une adresse externe
+    fn is_redirect_candidate(packet: &NetworkPacket) -> bool {
+        if !matches!(packet.traffic_type, TrafficType::Web | TrafficType::Ssh) {
+            return false;
+        }
+        
+        match packet.source_ip.parse::<IpAddr>() {
+            Ok(ip) => !is_private_ip(&ip) && !ip.is_loopback(),
+            Err(_) => false,
+        }
+    }
+    
+    /// Enregistre le rappel invoqué pour chaque paquet redirigé
+    ///
+    /// Typiquement utilisé pour activer un environnement WarpShield pour la source du paquet.
+    pub fn on_redirect<F>(&mut self, hook: F)
+    where
+        F: Fn(&NetworkPacket) + Send + Sync + 'static,
+    {
+        self.redirect_hook = Some(Arc::new(hook));
+    }
+    
+    /// Met à jour les statistiques après l'analyse d'un paquet
+    ///
+    /// Les compteurs sont incrémentés atomiquement ; seuls les temps d'analyse passent par un
+    /// verrou, avec leur propre nombre de mesures afin que la moyenne reste cohérente même
+    /// sous accès concurrent.
+    fn record_analysis(&self, decision: &FirewallDecision, detected: bool, start_time: Instant) {
+        let analysis_time_us = start_time.elapsed().as_micros() as f64;
+        let counters = &self.counters;
+        counters.total_packets_analyzed.fetch_add(1, Ordering::Relaxed);
+        
+        let decision_counter = match decision {
+            FirewallDecision::Allow => Some(&counters.packets_allowed),
+            FirewallDecision::Block => Some(&counters.packets_blocked),
+            FirewallDecision::Quarantine => Some(&counters.packets_quarantined),
+            FirewallDecision::RateLimit => Some(&counters.packets_rate_limited),
+            _ => None,
+        };
+        if let Some(counter) = decision_counter {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        
+        if detected {
+            counters.detection_events.fetch_add(1, Ordering::Relaxed);
+        }
+        
+        // Mettre à jour les temps d'analyse (moyenne incrémentale)
+        let mut timing = self.timing.lock().unwrap();
+        timing.samples += 1;
+        if timing.samples == 1 {
+            timing.min_analysis_time_us = analysis_time_us;
+            timing.max_analysis_time_us = analysis_time_us;
+        } else {
+            timing.min_analysis_time_us = timing.min_analysis_time_us.min(analysis_time_us);
+            timing.max_analysis_time_us = timing.max_analysis_time_us.max(analysis_time_us);
+        }
+        timing.avg_analysis_time_us += (analysis_time_us - timing.avg_analysis_time_us) / timing.samples as f64;
+    }
+    
+    /// Conserve un événement de détection pour l'export, selon la politique de débordement,
+    /// et le publie sur le bus d'événements
+    fn retain_event(&self, event: &DetectionEvent) {
+        if let Some(bus) = &self.event_bus {
+            bus.publish(BusEvent::Detection(event.clone()));
+        }
+        
+        let mut events = self.recent_events.lock().unwrap();
+        if events.len() >= self.config.max_retained_events {
+            self.counters.events_dropped.fetch_add(1, Ordering::Relaxed);
+            if self.config.event_overflow_policy == EventOverflowPolicy::DropNewest {
+                return;
+            }
+        }
+        events.push_back(event.clone());
+        while events.len() > self.config.max_retained_events {
+            events.pop_front();
+        }
+    }
+    
+    /// Obtient au plus `limit` événements de détection conservés, les plus récents, du plus
+    /// ancien au plus récent
+    pub fn recent_events(&self, limit: usize) -> Vec<DetectionEvent> {
+        let events = self.recent_events.lock().unwrap();
+        events.iter().skip(events.len().saturating_sub(limit)).cloned().collect()
+    }
+    
+    /// Rejoue une capture de paquets et renvoie les décisions dans l'ordre de la capture
+    ///
+    /// Format de capture (JSONL) : un `NetworkPacket` sérialisé en JSON par ligne, les lignes
+    /// vides étant ignorées. `payload_sample` (tableau d'octets) et `metadata` sont optionnels ;
+    /// `timestamp` suit la représentation serde de `SystemTime`
+    /// (`{"secs_since_epoch": ..., "nanos_since_epoch": ...}`).
+    ///
+    /// Le rejeu s'interrompt à la première ligne illisible ou au premier échec d'analyse.
+    pub fn replay_from_reader<R: Read>(&self, reader: R) -> Result<Vec<FirewallDecision>, String> {
+        let mut decisions = Vec::new();
+        
+        for (index, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line.map_err(|e| format!("Lecture de la capture impossible (ligne {}): {}", index + 1, e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            let packet: NetworkPacket = serde_json::from_str(&line)
+                .map_err(|e| format!("Paquet invalide à la ligne {}: {}", index + 1, e))?;
+            let (decision, _) = self.analyze_packet(packet)?;
+            decisions.push(decision);
+        }
+        
+        Ok(decisions)
+    }
+    
+    /// Exporte les événements de détection récents au format JSON délimité par des sauts de ligne
+    pub fn detection_events_ndjson(&self) -> String {
+        let events = self.recent_events.lock().unwrap();
+        events
+            .iter()
+            .filter_map(|event| serde_json::to_string(event).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+    
+    /// Exporte les événements de détection récents au format CEF (Common Event Format), un par ligne
+    pub fn detection_events_cef(&self) -> String {
+        let events = self.recent_events.lock().unwrap();
+        events.iter().map(|event| Self::format_cef(event) + "\n").collect()
+    }
+    
+    /// Formate un événement de détection en ligne CEF
+    ///
+    /// La sévérité CEF (0 - 10) est dérivée du score d'anomalie.
+    fn format_cef(event: &DetectionEvent) -> String {
+        let escape_header = |value: &str| value.replace('\\', "\\\\").replace('|', "\\|");
+        let escape_extension = |value: &str| value.replace('\\', "\\\\").replace('=', "\\=").replace('\n', " ");
+        
+        let timestamp_ms = event
+            .timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or(0);
+        let severity = (event.anomaly_score.clamp(0.0, 1.0) * 10.0).round() as u8;
+        
+        format!(
+            "CEF:0|ICARUS|NeuroFireWall|{}|{:?}|{}|{}|externalId={} rt={} cfp1={:.4} cfp1Label=anomalyScore act={:?} cs1={} cs1Label=triggerFeatures",
+            env!("CARGO_PKG_VERSION"),
+            event.decision,
+            escape_header(&event.description),
+            severity,
+            escape_extension(&event.id),
+            timestamp_ms,
+            event.anomaly_score,
+            event.decision,
+            escape_extension(&event.trigger_features.join(",")),
+        )
+    }
+    
+    /// Limite le débit des sources trop bavardes, sans masquer un blocage ou une quarantaine
+    fn apply_rate_limit(&self, source_ip: &str, decision: FirewallDecision, now: Instant) -> FirewallDecision {
+        let source_rate = self.record_source_packet(source_ip, now);
+        let rate_limit = self.config.packets_per_second as f32;
+        if source_rate > rate_limit && matches!(decision, FirewallDecision::Allow | FirewallDecision::Alert) {
+            if self.config.escalate_rate_limit && source_rate > rate_limit * 2.0 {
+                FirewallDecision::Block
+            } else {
+                FirewallDecision::RateLimit
+            }
+        } else {
+            decision
+        }
+    }
+    
+    /// Empreinte d'un paquet pour le cache de classification
+    ///
+    /// Couvre tous les champs dont peuvent dépendre les caractéristiques extraites : tout le
+    /// paquet sauf son identifiant et son horodatage.
+    fn classification_key(&self, packet: &NetworkPacket) -> u64 {
+        let mut metadata: Vec<(&String, &String)> = packet.metadata.iter().collect();
+        metadata.sort();
+        
+        self.classification_cache.lock().unwrap().hasher.hash_one((
+            &packet.source_ip,
+            &packet.destination_ip,
+            packet.source_port,
+            packet.destination_port,
+            &packet.protocol,
+            packet.size,
+            &packet.traffic_type,
+            &packet.payload_sample,
+            metadata,
+        ))
+    }
+    
+    /// Classification mise en cache pour une empreinte
+    fn cached_classification(&self, key: u64) -> Option<CachedClassification> {
+        self.classification_cache.lock().unwrap().get(key, self.config.classification_cache_size)
+    }
+    
+    /// Met en cache la classification du modèle
+    fn cache_classification(&self, key: u64, classification: CachedClassification) {
+        self.classification_cache
+            .lock()
+            .unwrap()
+            .insert(key, classification, self.config.classification_cache_size);
+    }
+    
+    /// Vide le cache de classification (après toute modification du modèle)
+    fn invalidate_classification_cache(&self) {
+        self.classification_cache.lock().unwrap().clear();
+    }
+    
+    /// Ajoute le paquet au buffer d'apprentissage, avec un échantillon de charge utile borné
+    /// (les caractéristiques ont été extraites de l'échantillon complet)
+    fn retain_for_learning(&self, packet: &NetworkPacket) {
+        let mut retained = packet.clone();
+        retained.payload_sample.truncate(self.config.max_payload_sample_bytes);
+        
+        let mut buffer = self.packet_buffer.lock().unwrap();
+        buffer.push_back(retained);
+        
+        // Limiter la taille du buffer
+        while buffer.len() > self.config.buffer_size {
+            buffer.pop_front();
+        }
+    }
+    
+    /// Enregistre un paquet dans la fenêtre glissante de sa source et renvoie le débit courant
+    fn record_source_packet(&self, ip: &str, now: Instant) -> f32 {
+        let mut windows = self.source_windows.lock().unwrap();
+        
+        // Éviter la croissance illimitée de la table sous un grand nombre de sources
+        if windows.len() > self.config.buffer_size {
+            windows.retain(|_, window| {
+                Self::evict_expired(window, now);
+                !window.is_empty()
+            });
+        }
+        
+        let window = windows.entry(ip.to_string()).or_default();
+        window.push_back(now);
+        Self::evict_expired(window, now);
+        
+        window.len() as f32 / Self::RATE_WINDOW.as_secs_f32()
+    }
+    
+    /// Retire de la fenêtre les paquets plus anciens que `RATE_WINDOW`
+    fn evict_expired(window: &mut VecDeque<Instant>, now: Instant) {
+        while let Some(&oldest) = window.front() {
+            if now.duration_since(oldest) > Self::RATE_WINDOW {
+                window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+    
+    /// Obtient le débit courant d'une source (paquets par seconde)
+    pub fn get_source_rate(&self, ip: &str) -> f32 {
+        let mut windows = self.source_windows.lock().unwrap();
+        match windows.get_mut(ip) {
+            Some(window) => {
+                Self::evict_expired(window, Instant::now());
+                window.len() as f32 / Self::RATE_WINDOW.as_secs_f32()
+            }
+            None => 0.0,
+        }
+    }
+    
+    /// Enregistre un paquet dans la fenêtre de statistiques de trafic
+    fn record_traffic(&self, packet: &NetworkPacket, now: Instant) {
+        let mut window = self.traffic_window.lock().unwrap();
+        window.push_back(TrafficSample {
+            received_at: now,
+            source_ip: packet.source_ip.clone(),
+            size: packet.size,
+        });
+        Self::evict_traffic(&mut window, now, Duration::from_secs(self.config.stats_window_secs));
+        while window.len() > self.config.max_window_samples.max(1) {
+            window.pop_front();
+        }
+    }
+    
+    /// Retire de la fenêtre de trafic les paquets plus anciens que `retention`
+    fn evict_traffic(window: &mut VecDeque<TrafficSample>, now: Instant, retention: Duration) {
+        while let Some(oldest) = window.front() {
+            if now.duration_since(oldest.received_at) > retention {
+                window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+    
+    /// Calcule les agrégats de trafic sur les `window_secs` dernières secondes
+    ///
+    /// La fenêtre est bornée par `stats_window_secs`, au-delà duquel les paquets ne sont pas conservés.
+    /// Sous un trafic dépassant `max_window_samples` paquets sur la fenêtre, seuls les plus récents
+    /// sont comptés et les agrégats sous-estiment le trafic réel.
+    pub fn get_window_stats(&self, window_secs: u64) -> WindowStats {
+        let window_secs = window_secs.clamp(1, self.config.stats_window_secs.max(1));
+        let span = Duration::from_secs(window_secs);
+        let now = Instant::now();
+        
+        let mut window = self.traffic_window.lock().unwrap();
+        Self::evict_traffic(&mut window, now, Duration::from_secs(self.config.stats_window_secs));
+        
+        let mut packets = 0u64;
+        let mut bytes = 0u64;
+        let mut per_source: HashMap<&str, u64> = HashMap::new();
+        for sample in window.iter().rev().take_while(|sample| now.duration_since(sample.received_at) <= span) {
+            packets += 1;
+            bytes += sample.size as u64;
+            *per_source.entry(&sample.source_ip).or_default() += 1;
+        }
+        
+        let unique_sources = per_source.len();
+        let mut top_talkers: Vec<(String, u64)> = per_source
+            .into_iter()
+            .map(|(ip, count)| (ip.to_string(), count))
+            .collect();
+        top_talkers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_talkers.truncate(Self::TOP_TALKERS);
+        
+        WindowStats {
+            pps: packets as f32 / window_secs as f32,
+            bps: bytes as f32 / window_secs as f32,
+            unique_sources,
+            top_talkers,
+        }
+    }
+    
+    /// Remplace la source de temps (horloge simulée dans les tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+    
+    /// Remplace le générateur d'identifiants (identifiants séquentiels dans les tests)
+    pub fn set_id_generator(&mut self, ids: SharedIdGenerator) {
+        self.ids = ids;
+    }
+    
+    /// Ajuste les seuils du moteur neuronal toutes les `threshold_adjustment_interval`
+    /// évaluations d'opérateur
+    pub fn set_neural_engine(&mut self, engine: Arc<NeuralNetEngine>) {
+        self.neural_engine = Some(engine);
+    }
+    
+    /// Constructeur de paquet identifié et horodaté par le générateur et l'horloge du pare-feu
+    pub fn packet_builder(&self) -> NetworkPacketBuilder {
+        NetworkPacketBuilder::new(self.ids.as_ref(), self.clock.as_ref())
+    }
+    
+    /// Publie les événements de détection sur le bus d'événements
+    pub fn set_event_bus(&mut self, bus: EventBus) {
+        self.event_bus = Some(bus);
+    }
+    
+    /// Ajoute une adresse IP source à la liste de blocage pour une durée limitée
+    ///
+    /// Le n-ième blocage d'une même source dure `block_ttl_secs * block_backoff_factor^(n-1)`
+    /// secondes, borné par `max_block_ttl_secs`. Une source déjà bloquée n'est pas prolongée.
+    /// Les récidives sont oubliées après `offense_decay_secs` sans blocage.
+    pub fn block_source(&self, ip: &str) {
+        let now = self.clock.now();
+        let mut blocklist = self.blocklist.lock().unwrap();
+        if blocklist.entries.get(ip).is_some_and(|&expires_at| expires_at > now) {
+            return;
+        }
+        
+        let offenses = blocklist.offense_count(ip, now, self.offense_decay()).saturating_add(1);
+        let ttl = self.block_ttl(offenses);
+        blocklist.entries.insert(ip.to_string(), now + ttl);
+        blocklist.record_offense(ip, offenses, now + ttl, self.config.max_tracked_offenders);
+        
+        module_log!(self.config.log_level, Level::Info, "Source {} ajoutée à la liste de blocage pour {}s", ip, ttl.as_secs());
+    }
+    
+    /// Durée du blocage d'une source pour son n-ième blocage
+    fn block_ttl(&self, offenses: u32) -> Duration {
+        let backoff = u64::from(self.config.block_backoff_factor).saturating_pow(offenses.saturating_sub(1));
+        let ttl = self.config.block_ttl_secs.saturating_mul(backoff).min(self.config.max_block_ttl_secs);
+        Duration::from_secs(ttl)
+    }
+    
+    /// Période calme après laquelle les récidives d'une source sont oubliées
+    fn offense_decay(&self) -> Duration {
+        Duration::from_secs(self.config.offense_decay_secs)
+    }
+    
+    /// Ajoute une source de confiance : adresse IP (`192.0.2.10`) ou plage CIDR (`10.0.0.0/8`)
+    pub fn add_trusted(&self, cidr: &str) -> Result<(), String> {
+        let network = match cidr.parse::<IpNet>() {
+            Ok(network) => network.trunc(),
+            Err(_) => cidr
+                .parse::<IpAddr>()
+                .map(IpNet::from)
+                .map_err(|_| format!("Source de confiance invalide: '{}' (adresse IP ou plage CIDR attendue)", cidr))?,
+        };
+        
+        module_log!(self.config.log_level, Level::Info, "Source de confiance ajoutée: {}", network);
+        self.trusted_sources.lock().unwrap().insert(network);
+        
+        Ok(())
+    }
+    
+    /// Indique si une adresse IP appartient à une source de confiance
+    pub fn is_trusted(&self, ip: &str) -> bool {
+        let Ok(address) = ip.parse::<IpAddr>() else {
+            return false;
+        };
+        
+        self.trusted_sources.lock().unwrap().iter().any(|network| network.contains(&address))
+    }
+    
+    /// Retire une adresse IP source de la liste de blocage
+    ///
+    /// Le nombre de blocages passés est conservé : une récidive avant `offense_decay_secs` reste
+    /// sanctionnée plus longuement.
+    pub fn unblock_source(&self, ip: &str) -> bool {
+        self.blocklist.lock().unwrap().entries.remove(ip).is_some()
+    }
+    
+    /// Échéance du blocage d'une source (None si elle n'est pas bloquée)
+    pub fn blocked_until(&self, ip: &str) -> Option<SystemTime> {
+        let now = self.clock.now();
+        self.blocklist.lock().unwrap().entries.get(ip).copied().filter(|&expires_at| expires_at > now)
+    }
+    
+    /// Retire de la liste de blocage les sources dont le blocage a expiré et oublie les
+    /// récidives des sources restées calmes pendant `offense_decay_secs`
+    ///
+    /// Peut être appelée périodiquement depuis une autre tâche : le verrou n'est tenu que le
+    /// temps du parcours. Renvoie le nombre de sources débloquées.
+    pub fn expire_blocklist(&self) -> usize {
+        let now = self.clock.now();
+        let mut blocklist = self.blocklist.lock().unwrap();
+        let before = blocklist.entries.len();
+        blocklist.entries.retain(|_, expires_at| *expires_at > now);
+        let expired = before - blocklist.entries.len();
+        blocklist.forget_offenses(now, self.offense_decay());
+        drop(blocklist);
+        
+        if expired > 0 {
+            module_log!(self.config.log_level, Level::Debug, "{} source(s) retirée(s) de la liste de blocage", expired);
+        }
+        
+        expired
+    }
+    
+    /// Place un paquet en quarantaine, en évinçant les plus anciens au-delà de la capacité
+    ///
+    /// Comme dans la mémoire tampon, seuls `max_payload_sample_bytes` octets de la charge utile
+    /// sont conservés.
+    fn quarantine_packet(&self, mut packet: NetworkPacket) {
+        packet.payload_sample.truncate(self.config.max_payload_sample_bytes);
+        
+        let mut quarantine = self.quarantine.lock().unwrap();
+        
+        if quarantine.packets.contains_key(&packet.id) {
+            quarantine.order.retain(|id| *id != packet.id);
+        }
+        quarantine.order.push_back(packet.id.clone());
+        quarantine.packets.insert(packet.id.clone(), packet);
+        
+        while quarantine.order.len() > self.config.quarantine_size {
+            if let Some(oldest) = quarantine.order.pop_front() {
+                quarantine.packets.remove(&oldest);
+            }
+        }
+    }
+    
+    /// Liste les identifiants des paquets en quarantaine, du plus ancien au plus récent
+    pub fn list_quarantined(&self) -> Vec<String> {
+        self.quarantine.lock().unwrap().order.iter().cloned().collect()
+    }
+    
+    /// Libère un paquet de la quarantaine et le renvoie
+    pub fn release_packet(&self, packet_id: &str) -> Result<NetworkPacket, String> {
+        let mut quarantine = self.quarantine.lock().unwrap();
+        let packet = quarantine
+            .packets
+            .remove(packet_id)
+            .ok_or(format!("Paquet non trouvé en quarantaine: {}", packet_id))?;
+        quarantine.order.retain(|id| id != packet_id);
+        
+        module_log!(self.config.log_level, Level::Info, "Paquet {} libéré de la quarantaine", packet_id);
+        
+        Ok(packet)
+    }
+    
+    /// Vide la quarantaine et renvoie le nombre de paquets supprimés
+    pub fn purge_quarantine(&self) -> usize {
+        let mut quarantine = self.quarantine.lock().unwrap();
+        let purged = quarantine.packets.len();
+        quarantine.packets.clear();
+        quarantine.order.clear();
+        purged
+    }
+    
+    /// Charge des signatures d'attaque ; une signature de même identifiant est remplacée
+    pub fn load_signatures(&self, signatures: Vec<AttackSignature>) {
+        let mut loaded = self.signatures.lock().unwrap();
+        for signature in signatures {
+            loaded.retain(|existing| existing.id != signature.id);
+            loaded.push(signature);
+        }
+    }
+    
+    /// Recherche la première signature dont un motif correspond au paquet
+    ///
+    /// Motifs reconnus : `source:<ip>` (adresse source exacte) et `payload:<texte>`
+    /// (sous-chaîne de l'échantillon de charge utile). Les autres motifs sont ignorés.
+    /// Renvoie l'identifiant de la signature, le motif et la confiance associée.
+    fn match_signatures(&self, packet: &NetworkPacket) -> Option<(String, String, f32)> {
+        let signatures = self.signatures.lock().unwrap();
+        
+        signatures.iter().find_map(|signature| {
+            signature
+                .patterns
+                .iter()
+                .find(|pattern| Self::pattern_matches(pattern, packet))
+                .map(|pattern| (signature.id.clone(), pattern.clone(), signature.confidence))
+        })
+    }
+    
+    /// Indique si un motif de signature correspond au paquet
+    fn pattern_matches(pattern: &str, packet: &NetworkPacket) -> bool {
+        if let Some(source) = pattern.strip_prefix("source:") {
+            !source.is_empty() && packet.source_ip == source
+        } else if let Some(needle) = pattern.strip_prefix("payload:") {
+            let needle = needle.as_bytes();
+            !needle.is_empty() && packet.payload_sample.windows(needle.len()).any(|window| window == needle)
+        } else {
+            false
+        }
+    }
+    
+    /// Indique si une adresse IP source est bloquée
+    ///
+    /// Un blocage expiré ne s'applique plus, même avant l'appel à `expire_blocklist`.
+    pub fn is_blocked(&self, ip: &str) -> bool {
+        self.blocked_until(ip).is_some()
+    }
+    
+    /// Extrait les caractéristiques d'un paquet réseau via l'extracteur configuré
+    fn extract_features(&self, packet: &NetworkPacket) -> Result<PacketFeatures, String> {
+        let features = self.feature_extractor.extract(packet);
+        if features.len() != self.feature_labels.len() {
+            return Err(format!(
+                "L'extracteur a produit {} caractéristiques pour le paquet {} ({} attendues)",
+                features.len(),
+                packet.id,
+                self.feature_labels.len()
+            ));
+        }
+        
+        Ok(PacketFeatures {
+            packet_id: packet.id.clone(),
+            features,
+            feature_labels: self.feature_labels.clone(),
+            anomaly_score: 0.0, // Sera calculé par le modèle
+        })
+    }
+    
+    /// Multiplicateur appliqué aux seuils de décision selon la sensibilité
+    ///
+    /// `1 + 0.4 * (0.75 - sensitivity)` : la sensibilité par défaut (0.75) laisse les seuils
+    /// inchangés, une sensibilité de 1.0 les abaisse de 10 % et une sensibilité nulle les
+    /// relève de 30 %.
+    fn sensitivity_multiplier(&self) -> f32 {
+        1.0 + Self::SENSITIVITY_GAIN * (Self::NEUTRAL_SENSITIVITY - self.config.sensitivity)
+    }
+    
+    /// Comptabilise un échec d'analyse et passe en mode dégradé au-delà de
+    /// `max_consecutive_errors` échecs consécutifs ; renvoie `true` en mode dégradé
+    fn record_analysis_error(&self, error: &str) -> bool {
+        let errors = self.consecutive_errors.fetch_add(1, Ordering::Relaxed) + 1;
+        if errors < u64::from(self.config.max_consecutive_errors) {
+            module_log!(self.config.log_level, Level::Warn, "Échec d'analyse ({} consécutifs): {}", errors, error);
+            return false;
+        }
+        
+        let mut state = self.state.lock().unwrap();
+        if *state != NeuroFireWallState::Degraded {
+            *state = NeuroFireWallState::Degraded;
+            module_log!(
+                self.config.log_level,
+                Level::Error,
+                "NeuroFireWall en mode dégradé après {} échecs d'analyse consécutifs: {}",
+                errors,
+                error
+            );
+        }
+        
+        true
+    }
+    
+    /// Remet à zéro les échecs consécutifs et quitte le mode dégradé
+    fn recover_from_errors(&self) {
+        if self.consecutive_errors.swap(0, Ordering::Relaxed) == 0 {
+            return;
+        }
+        
+        let mut state = self.state.lock().unwrap();
+        if *state == NeuroFireWallState::Degraded {
+            *state = NeuroFireWallState::Operational;
+            module_log!(self.config.log_level, Level::Info, "NeuroFireWall rétabli après le mode dégradé");
+        }
+    }
+    
+    /// Décision de repli du mode dégradé : seuls les protocoles et types de trafic connus
+    /// sont autorisés
+    fn fallback_decision(packet: &NetworkPacket) -> FirewallDecision {
+        let known_protocol = matches!(packet.protocol.as_str(), "TCP" | "UDP" | "ICMP" | "HTTP" | "HTTPS");
+        if known_protocol && packet.traffic_type != TrafficType::Unknown {
+            FirewallDecision::Allow
+        } else {
+            FirewallDecision::Block
+        }
+    }
+    
+    /// Comptabilise un score d'anomalie dans l'histogramme
+    fn record_score(&self, anomaly_score: f32) {
+        let bin = ((anomaly_score * Self::SCORE_HISTOGRAM_BINS as f32) as usize).min(Self::SCORE_HISTOGRAM_BINS - 1);
+        self.counters.score_histogram[bin].fetch_add(1, Ordering::Relaxed);
+    }
+    
+    /// Histogramme des scores d'anomalie calculés par le modèle
+    ///
+    /// L'intervalle `i` compte les scores dans `[i / 20, (i + 1) / 20[` (le dernier inclut 1.0).
+    /// Les paquets bloqués sans analyse (liste de blocage, signature) ne sont pas comptés.
+    pub fn score_histogram(&self) -> [u64; Self::SCORE_HISTOGRAM_BINS] {
+        std::array::from_fn(|bin| self.counters.score_histogram[bin].load(Ordering::Relaxed))
+    }
+    
+    /// Score seuil au-delà duquel environ `target_alert_rate` (0.0 - 1.0) des paquets observés
+    /// auraient été signalés
+    ///
+    /// Les scores sont supposés uniformément répartis dans chaque intervalle de l'histogramme.
+    /// Sans score observé, le seuil effectif actuel est renvoyé.
+    pub fn suggest_threshold(&self, target_alert_rate: f32) -> f32 {
+        let histogram = self.score_histogram();
+        let total: u64 = histogram.iter().sum();
+        if total == 0 {
+            return self.get_current_threshold();
+        }
+        
+        let bin_width = 1.0 / Self::SCORE_HISTOGRAM_BINS as f32;
+        let target = target_alert_rate.clamp(0.0, 1.0) * total as f32;
+        let mut above = 0.0;
+        for (bin, &count) in histogram.iter().enumerate().rev() {
+            let count = count as f32;
+            if count > 0.0 && above + count >= target {
+                let upper = (bin + 1) as f32 * bin_width;
+                return (upper - (target - above) / count * bin_width).clamp(0.0, 1.0);
+            }
+            above += count;
+        }
+        
+        0.0
+    }
+    
+    /// Seuil effectif de détection (quarantaine) après prise en compte de la sensibilité
+    pub fn get_current_threshold(&self) -> f32 {
+        (self.config.thresholds().quarantine * self.sensitivity_multiplier()).min(1.0)
+    }
+    
+    /// Prend une décision basée sur le score d'anomalie
+    ///
+    /// Chaque seuil de `NeuroFireWallConfig::thresholds` est multiplié par le multiplicateur
+    /// de sensibilité `m` (et borné à 1) :
+    /// - `Block` si le score atteint `block * m`, ou `quarantine * m` en mode strict
+    /// - `Quarantine` si le score atteint `quarantine * m`
+    /// - `Alert` si le score atteint `alert * m`
+    /// - `Allow` sinon
+    fn make_decision(&self, anomaly_score: f32) -> FirewallDecision {
+        let thresholds = self.config.thresholds();
+        let multiplier = self.sensitivity_multiplier();
+        let threshold = self.get_current_threshold();
+        let block_threshold = (thresholds.block * multiplier).min(1.0);
+        let alert_threshold = (thresholds.alert * multiplier).min(1.0);
+        
+        if anomaly_score >= block_threshold {
+            FirewallDecision::Block
+        } else if anomaly_score >= threshold {
+            if self.config.strict_mode {
+                FirewallDecision::Block
+            } else {
+                FirewallDecision::Quarantine
+            }
+        } else if anomaly_score >= alert_threshold {
+            FirewallDecision::Alert
+        } else {
+            FirewallDecision::Allow
+        }
+    }
+    
+    /// Étiquette un paquet de la mémoire tampon pour le prochain cycle d'apprentissage
+    pub fn feedback(&self, packet_id: &str, is_malicious: bool) -> Result<(), String> {
+        let buffer = self.packet_buffer.lock().unwrap();
+        if !buffer.iter().any(|packet| packet.id == packet_id) {
+            return Err(format!("Paquet non trouvé dans la mémoire tampon: {}", packet_id));
+        }
+        drop(buffer);
+        
+        let label = if is_malicious { 1.0 } else { 0.0 };
+        self.labels.lock().unwrap().insert(packet_id.to_string(), label);
+        
+        Ok(())
+    }
+    
+    /// Enregistre l'évaluation d'un événement de détection par un opérateur
+    ///
+    /// `was_correct` indique si la décision du pare-feu était juste et `was_malicious` si le
+    /// trafic était réellement malveillant : une décision injuste sur du trafic légitime est
+    /// un faux positif, sur du trafic malveillant un faux négatif. Une nouvelle évaluation
+    /// d'un même événement remplace la précédente.
+    ///
+    /// Toutes les `threshold_adjustment_interval` évaluations, les taux d'erreur qui en
+    /// découlent (`error_rates`) sont transmis au moteur neuronal installé par
+    /// `set_neural_engine` (`NeuralNetEngine::adjust_thresholds`). Un ajustement impossible
+    /// (moteur non préchauffé ou occupé) est retenté à l'évaluation suivante.
+    pub fn record_feedback(&self, event_id: &str, was_correct: bool, was_malicious: bool) -> Result<(), String> {
+        if event_id.is_empty() {
+            return Err("Identifiant d'événement manquant".to_string());
+        }
+        
+        let mut feedback = self.operator_feedback.lock().unwrap();
+        feedback.retain(|entry| entry.event_id != event_id);
+        feedback.push_back(OperatorFeedback {
+            event_id: event_id.to_string(),
+            flagged: was_correct == was_malicious,
+            malicious: was_malicious,
+        });
+        while feedback.len() > self.config.feedback_window_size {
+            feedback.pop_front();
+        }
+        drop(feedback);
+        
+        self.adjust_neural_thresholds();
+        
+        Ok(())
+    }
+    
+    /// Transmet les taux d'erreur au moteur neuronal une fois l'intervalle d'ajustement atteint
+    fn adjust_neural_thresholds(&self) {
+        let engine = match &self.neural_engine {
+            Some(engine) if self.config.threshold_adjustment_interval > 0 => engine,
+            _ => return,
+        };
+        
+        let received = self.feedback_since_adjustment.fetch_add(1, Ordering::Relaxed) + 1;
+        if received < self.config.threshold_adjustment_interval as u64 {
+            return;
+        }
+        
+        let rates = self.error_rates();
+        match engine.adjust_thresholds(rates.false_positive_rate, rates.false_negative_rate) {
+            Ok(()) => self.feedback_since_adjustment.store(0, Ordering::Relaxed),
+            Err(e) => module_log!(self.config.log_level, Level::Warn, "Ajustement des seuils reporté : {}", e),
+        }
+    }
+    
+    /// Taux de faux positifs et de faux négatifs sur la fenêtre de retours d'opérateur
+    pub fn error_rates(&self) -> ErrorRates {
+        let feedback = self.operator_feedback.lock().unwrap();
+        let rate = |malicious: bool| {
+            let relevant: Vec<&OperatorFeedback> = feedback.iter().filter(|entry| entry.malicious == malicious).collect();
+            if relevant.is_empty() {
+                return 0.0;
+            }
+            // Erreur : trafic légitime signalé, ou trafic malveillant non signalé
+            let errors = relevant.iter().filter(|entry| entry.flagged != malicious).count();
+            errors as f32 / relevant.len() as f32
+        };
+        
+        ErrorRates {
+            false_positive_rate: rate(false),
+            false_negative_rate: rate(true),
+        }
+    }
+    
+    /// Exécute un cycle d'apprentissage
+    ///
+    /// Les paquets étiquetés via `feedback` sont retirés de la mémoire tampon et
+    /// utilisés pour entraîner le modèle.
+    pub fn run_learning_cycle(&self) -> Result<(), String> {
+        // Vérifier si l'apprentissage continu est activé
+        if !self.config.enable_continuous_learning {
+            return Err("L'apprentissage continu est désactivé".to_string());
+        }
+        
+        // Changer l'état en mode apprentissage ; seul un pare-feu en service peut apprendre
+        let previous_state = {
+            let mut state = self.state.lock().unwrap();
+            if !matches!(*state, NeuroFireWallState::Operational | NeuroFireWallState::Degraded) {
+                return Err(format!("Apprentissage impossible dans l'état {:?}", *state));
+            }
+            std::mem::replace(&mut *state, NeuroFireWallState::Learning)
+        };
+        
+        // Récupérer les échantillons étiquetés
+        let samples: Vec<(NetworkPacket, f32)> = {
+            let mut labels = self.labels.lock().unwrap();
+            let mut buffer = self.packet_buffer.lock().unwrap();
+            let mut samples = Vec::with_capacity(labels.len());
+            
+            buffer.retain(|packet| match labels.remove(&packet.id) {
+                Some(label) => {
+                    samples.push((packet.clone(), label));
+                    false
+                }
+                None => true,
+            });
+            
+            // Les étiquettes orphelines (paquets évincés du tampon) sont abandonnées
+            labels.clear();
+            samples
+        };
+        
+        // Entraîner le modèle sur chaque échantillon
+        let result = samples.iter().try_for_each(|(packet, label)| {
+            let features = self.extract_features(packet)?;
+            self.model.write().unwrap().update(&features.features, *label);
+            Ok::<(), String>(())
+        });
+        
+        // Les décisions en cache reflètent l'ancien modèle
+        self.invalidate_classification_cache();
+        
+        // Mettre à jour les statistiques
+        match &result {
+            Ok(()) => {
+                self.counters.learning_cycles.fetch_add(1, Ordering::Relaxed);
+                module_log!(self.config.log_level, Level::Debug, "Cycle d'apprentissage terminé ({} échantillons)", samples.len());
+            }
+            Err(e) => module_log!(self.config.log_level, Level::Error, "Échec du cycle d'apprentissage: {}", e),
+        }
+        
+        // Restaurer l'état précédent, sauf si une analyse l'a modifié entre-temps ; un mode
+        // dégradé n'est conservé que si les échecs consécutifs persistent
+        {
+            let mut state = self.state.lock().unwrap();
+            if *state == NeuroFireWallState::Learning {
+                *state = match previous_state {
+                    NeuroFireWallState::Degraded if self.consecutive_errors.load(Ordering::Relaxed) == 0 => {
+                        NeuroFireWallState::Operational
+                    }
+                    previous_state => previous_state,
+                };
+            }
+        }
+        
+        result
+    }
+    
+    /// Exporte les poids et biais du modèle appris (format binaire versionné)
+    pub fn export_model(&self) -> Vec<u8> {
+        let snapshot = ModelSnapshot {
+            version: Self::MODEL_SNAPSHOT_VERSION,
+            model: self.model.read().unwrap().clone(),
+        };
+        bincode::serialize(&snapshot).expect("un modèle en mémoire est toujours sérialisable")
+    }
+    
+    /// Remplace le modèle par un instantané produit par `export_model`
+    ///
+    /// L'instantané doit avoir la même version de format et les mêmes tailles de couches
+    /// d'entrée et de sortie que le modèle courant ; le taux d'apprentissage configuré est conservé.
+    pub fn import_model(&self, bytes: &[u8]) -> Result<(), String> {
+        let ModelSnapshot { version, model: mut imported } = bincode::deserialize(bytes)
+            .map_err(|e| format!("Instantané de modèle illisible: {}", e))?;
+        if version != Self::MODEL_SNAPSHOT_VERSION {
+            return Err(format!(
+                "Version d'instantané non supportée: {} (attendue: {})",
+                version,
+                Self::MODEL_SNAPSHOT_VERSION
+            ));
+        }
+        imported.check_dimensions()?;
+        
+        let mut model = self.model.write().unwrap();
+        if imported.input_size != model.input_size || imported.output_size != model.output_size {
+            return Err(format!(
+                "Instantané incompatible: {} entrées et {} sorties (attendu: {} et {})",
+                imported.input_size, imported.output_size, model.input_size, model.output_size
+            ));
+        }
+        
+        imported.learning_rate = model.learning_rate;
+        *model = imported;
+        self.invalidate_classification_cache();
+        
+        module_log!(self.config.log_level, Level::Info, "Modèle importé ({} neurones cachés)", model.hidden_size);
+        
+        Ok(())
+    }
+    
+    /// Obtient l'état actuel du système
+    pub fn get_state(&self) -> NeuroFireWallState {
+        self.state.lock().unwrap().clone()
+    }
+    
+    /// Obtient la configuration du système
+    pub fn get_config(&self) -> &NeuroFireWallConfig {
+        &self.config
+    }
+    
+    /// Obtient les statistiques actuelles
+    ///
+    /// Les compteurs sont lus individuellement : sous charge, l'instantané peut refléter
+    /// un paquet en cours de comptabilisation.
+    pub fn get_stats(&self) -> NeuroFireWallStats {
+        let counters = &self.counters;
+        let error_rates = self.error_rates();
+        let timing = self.timing.lock().unwrap();
+        NeuroFireWallStats {
+            total_packets_analyzed: counters.total_packets_analyzed.load(Ordering::Relaxed),
+            packets_allowed: counters.packets_allowed.load(Ordering::Relaxed),
+            packets_blocked: counters.packets_blocked.load(Ordering::Relaxed),
+            packets_quarantined: counters.packets_quarantined.load(Ordering::Relaxed),
+            packets_rate_limited: counters.packets_rate_limited.load(Ordering::Relaxed),
+            detection_events: counters.detection_events.load(Ordering::Relaxed),
+            events_dropped: counters.events_dropped.load(Ordering::Relaxed),
+            allowed_trusted: counters.allowed_trusted.load(Ordering::Relaxed),
+            cache_hits: counters.cache_hits.load(Ordering::Relaxed),
+            avg_analysis_time_us: timing.avg_analysis_time_us,
+            min_analysis_time_us: timing.min_analysis_time_us,
+            max_analysis_time_us: timing.max_analysis_time_us,
+            false_positive_rate: error_rates.false_positive_rate,
+            false_negative_rate: error_rates.false_negative_rate,
+            learning_cycles: counters.learning_cycles.load(Ordering::Relaxed),
+            uptime_seconds: self.uptime_seconds(),
+        }
+    }
+    
+    /// Temps écoulé depuis le dernier démarrage (en secondes)
+    fn uptime_seconds(&self) -> u64 {
+        self.started_at.lock().unwrap().map_or(0, |started_at| started_at.elapsed().as_secs())
+    }
+    
+    /// Remet à zéro les compteurs statistiques et les retours d'opérateur sans modifier l'état
+    /// opérationnel
+    ///
+    /// Peut être appelé sans risque entre deux phases d'un benchmark (échauffement, mesure).
+    pub fn reset_stats(&self) {
+        let mut timing = self.timing.lock().unwrap();
+        self.counters.reset();
+        *timing = TimingStats::default();
+        self.operator_feedback.lock().unwrap().clear();
+        self.feedback_since_adjustment.store(0, Ordering::Relaxed);
+    }
+    
+    /// Arrête le NeuroFireWall
+    pub fn shutdown(&self) -> Result<(), String> {
+        // Cette fonction sera implémentée dans les versions futures
+        // Pour l'instant, elle change simplement l'état
+        
+        let mut state = self.state.lock().unwrap();
+        *state = NeuroFireWallState::Shutdown;
+        *self.started_at.lock().unwrap() = None;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use crate::bench::Benchmark;
+    use crate::clock::{Clock, MockClock};
+    use crate::id::SequentialIdGenerator;
+    
+    fn create_test_packet() -> NetworkPacket {
+        NetworkPacket::builder()
+            .source("192.168.1.100", 12345)
+            .destination("192.168.1.1", 80)
+            .size(1024)
+            .traffic_type(TrafficType::Web)
+            .payload_sample(vec![0, 1, 2, 3, 4])
+            .build()
+            .unwrap()
+    }
+    
+    /// Fige le modèle pour qu'il renvoie toujours le score donné
+    fn force_score(firewall: &NeuroFireWall, score: f32) {
+        let mut model = firewall.model.write().unwrap();
+        let model = &mut *model;
+        for row in model.weights.iter_mut().chain(model.output_weights.iter_mut()) {
+            row.iter_mut().for_each(|weight| *weight = 0.0);
+        }
+        model.biases[0] = (score / (1.0 - score)).ln();
+        firewall.invalidate_classification_cache();
+    }
+    
+    #[test]
+    fn test_neurofirewall_initialization() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Initializing);
+        
+        let result = firewall.initialize();
+        assert!(result.is_ok());
+        
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+    }
+    
+    #[test]
+    fn test_analyze_normal_packet() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let packet = create_test_packet();
+        
+        let result = firewall.analyze_packet(packet);
+        assert!(result.is_ok());
+        
+        let (decision, event) = result.unwrap();
+        assert_eq!(decision, FirewallDecision::Allow);
+        assert!(event.is_none());
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 1);
+        assert_eq!(stats.packets_allowed, 1);
+    }
+    
+    #[test]
+    fn test_extract_features() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let packet = create_test_packet();
+        
+        let result = firewall.extract_features(&packet);
+        assert!(result.is_ok());
+        
+        let features = result.unwrap();
+        assert_eq!(features.packet_id, packet.id);
+        assert_eq!(features.features.len(), DefaultFeatureExtractor::FEATURE_COUNT);
+        assert_eq!(features.feature_labels.len(), DefaultFeatureExtractor::FEATURE_COUNT);
+    }
+    
+    /// Extracteur de test : trois caractéristiques constantes, appels comptés
+    struct CountingExtractor {
+        calls: Arc<AtomicU64>,
+    }
+    
+    impl PacketFeatureExtractor for CountingExtractor {
+        fn extract(&self, _packet: &NetworkPacket) -> Vec<f32> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            vec![0.2, 0.4, 0.6]
+        }
+        
+        fn dimension(&self) -> usize {
+            3
+        }
+    }
+    
+    #[test]
+    fn test_custom_feature_extractor() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let extractor = CountingExtractor { calls: Arc::clone(&calls) };
+        let mut firewall = NeuroFireWall::with_feature_extractor(NeuroFireWallConfig::default(), Box::new(extractor));
+        firewall.initialize().unwrap();
+        
+        assert_eq!(firewall.model.read().unwrap().input_size, 3);
+        
+        firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        
+        let features = firewall.extract_features(&create_test_packet()).unwrap();
+        assert_eq!(features.features, vec![0.2, 0.4, 0.6]);
+        assert_eq!(features.feature_labels, vec!["feature_0", "feature_1", "feature_2"]);
+        
+        // L'extracteur du moteur neuronal s'utilise tel quel
+        let neural = FeatureExtractor::new(crate::neural_net::FeatureExtractionConfig::default());
+        let mut firewall = NeuroFireWall::with_feature_extractor(NeuroFireWallConfig::default(), Box::new(neural));
+        firewall.initialize().unwrap();
+        
+        assert!(firewall.analyze_packet(create_test_packet()).is_ok());
+        assert_eq!(firewall.extract_features(&create_test_packet()).unwrap().features.len(), 128);
+    }
+    
+    #[test]
+    fn test_extract_features_clamps_packet_size() {
+        let config = NeuroFireWallConfig::default();
+        let firewall = NeuroFireWall::new(config);
+        
+        let size_feature = |size: u32| {
+            let packet = NetworkPacket { size, ..create_test_packet() };
+            let features = firewall.extract_features(&packet).unwrap();
+            let index = features.feature_labels.iter().position(|l| l == "packet_size").unwrap();
+            features.features[index]
+        };
+        
+        assert_eq!(size_feature(0), 0.0);
+        assert_eq!(size_feature(750), 0.5);
+        assert_eq!(size_feature(9000), 1.0);
+        assert_eq!(size_feature(u32::MAX), 1.0);
+    }
+    
+    #[test]
+    fn test_extract_features_arbitrary_payload_lengths() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        
+        let firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        let mut rng = StdRng::seed_from_u64(858);
+        
+        let mut lengths = vec![0, 1, 2, DefaultFeatureExtractor::PAYLOAD_BYTE_FEATURES, 4096];
+        lengths.extend((0..200).map(|_| rng.gen_range(0..=4096)));
+        
+        for length in lengths {
+            let payload: Vec<u8> = (0..length).map(|_| rng.gen()).collect();
+            let packet = NetworkPacket { payload_sample: payload, ..create_test_packet() };
+            
+            let features = firewall.extract_features(&packet).unwrap();
+            assert_eq!(features.features.len(), DefaultFeatureExtractor::FEATURE_COUNT, "longueur {}", length);
+            assert_eq!(features.feature_labels.len(), DefaultFeatureExtractor::FEATURE_COUNT);
+            assert!(
+                features.features.iter().all(|value| value.is_finite() && (0.0..=1.0).contains(value)),
+                "caractéristiques invalides pour une charge de {} octets: {:?}",
+                length,
+                features.features
+            );
+            
+            // Une charge vide ou d'un seul octet a une entropie nulle
+            if length <= 1 {
+                let index = features.feature_labels.iter().position(|l| l == "payload_entropy").unwrap();
+                assert_eq!(features.features[index], 0.0);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_learning_cycle() {
+        let mut config = NeuroFireWallConfig::default();
+        config.enable_continuous_learning = true;
+        
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let result = firewall.run_learning_cycle();
+        assert!(result.is_ok());
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.learning_cycles, 1);
+    }
+    
+    #[test]
+    fn test_neural_model_distinguishes_inputs() {
+        let model = NeuralModel::new(10, 256, 1, 0.05, None);
+        
+        let quiet = vec![0.0; 10];
+        let loud = vec![1.0; 10];
+        
+        let quiet_score = model.predict(&quiet).unwrap();
+        let loud_score = model.predict(&loud).unwrap();
+        
+        assert!((quiet_score - loud_score).abs() > 1e-5);
+        assert!((quiet_score - 0.5).abs() > 0.1);
+        assert!(quiet_score > 0.0 && quiet_score < 1.0);
+        assert!(loud_score > 0.0 && loud_score < 1.0);
+        
+        // Taille d'entrée invalide : erreur
+        assert!(model.predict(&[0.5; 3]).is_err());
+    }
+    
+    #[test]
+    fn test_learning_cycle_trains_on_feedback() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let mut malicious = create_test_packet();
+        malicious.destination_port = 4444;
+        malicious.traffic_type = TrafficType::Unknown;
+        malicious.payload_sample = vec![0x90, 0x90, 0xCC, 0xEB, 0xFE];
+        
+        let features = firewall.extract_features(&malicious).unwrap().features;
+        let initial_score = firewall.model.read().unwrap().predict(&features).unwrap();
+        
+        for _ in 0..20 {
+            let mut packet = malicious.clone();
+            packet.id = format!("packet-{}", uuid::Uuid::new_v4());
+            firewall.analyze_packet(packet.clone()).unwrap();
+            firewall.feedback(&packet.id, true).unwrap();
+            firewall.run_learning_cycle().unwrap();
+        }
+        
+        let trained_score = firewall.model.read().unwrap().predict(&features).unwrap();
+        assert!(trained_score > initial_score, "{} <= {}", trained_score, initial_score);
+        assert_eq!(firewall.get_stats().learning_cycles, 20);
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+        
+        // Les paquets étiquetés sont consommés par l'apprentissage
+        assert!(firewall.packet_buffer.lock().unwrap().is_empty());
+        assert!(firewall.feedback("packet-inconnu", true).is_err());
+    }
+    
+    #[test]
+    fn test_blocklisted_source_is_blocked() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let packet = create_test_packet();
+        firewall.block_source(&packet.source_ip);
+        assert!(firewall.is_blocked(&packet.source_ip));
+        
+        // Un paquet normalement autorisé est bloqué sans détection
+        let (decision, event) = firewall.analyze_packet(packet.clone()).unwrap();
+        assert_eq!(decision, FirewallDecision::Block);
+        assert!(event.is_none());
+        assert_eq!(firewall.get_stats().packets_blocked, 1);
+        
+        // Le paquet court-circuité n'est pas conservé pour l'apprentissage
+        assert!(firewall.packet_buffer.lock().unwrap().is_empty());
+        
+        assert!(firewall.unblock_source(&packet.source_ip));
+        let (decision, _) = firewall.analyze_packet(packet).unwrap();
+        assert_eq!(decision, FirewallDecision::Allow);
+    }
+    
+    #[test]
+    fn test_rate_limit_burst_from_single_source() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        let mut decisions = Vec::new();
+        for i in 0..200 {
+            let mut packet = create_test_packet();
+            packet.id = format!("burst-{}", i);
+            packet.protocol = "UDP".to_string();
+            packet.size = 64;
+            let (decision, _) = firewall.analyze_packet(packet).unwrap();
+            decisions.push(decision);
+        }
+        
+        assert_eq!(decisions[0], FirewallDecision::Allow);
+        assert_eq!(decisions[199], FirewallDecision::RateLimit);
+        assert!(firewall.get_source_rate("192.168.1.100") > 100.0);
+        assert_eq!(firewall.get_source_rate("10.0.0.1"), 0.0);
+        assert!(firewall.get_stats().packets_rate_limited >= 99);
+    }
+    
+    #[test]
+    fn test_extract_features_ipv6_and_malformed_addresses() {
+        let config = NeuroFireWallConfig::default();
+        let firewall = NeuroFireWall::new(config);
+        
+        let feature = |features: &PacketFeatures, label: &str| {
+            let index = features.feature_labels.iter().position(|l| l == label).unwrap();
+            features.features[index]
+        };
+        
+        let mut ipv6 = create_test_packet();
+        ipv6.source_ip = "2001:db8::1".to_string();
+        ipv6.destination_ip = "2001:db8::2".to_string();
+        let ipv6_features = firewall.extract_features(&ipv6).unwrap();
+        
+        let mut malformed = create_test_packet();
+        malformed.source_ip = "999.1.1.not-an-ip".to_string();
+        let malformed_features = firewall.extract_features(&malformed).unwrap();
+        
+        let ipv4_features = firewall.extract_features(&create_test_packet()).unwrap();
+        
+        assert_eq!(ipv6_features.features.len(), DefaultFeatureExtractor::FEATURE_COUNT);
+        assert_eq!(malformed_features.features.len(), DefaultFeatureExtractor::FEATURE_COUNT);
+        
+        assert_eq!(feature(&ipv6_features, "source_is_ipv6"), 1.0);
+        assert_eq!(feature(&ipv6_features, "same_subnet"), 1.0);
+        assert_eq!(feature(&ipv6_features, "invalid_address"), 0.0);
+        
+        assert_eq!(feature(&ipv4_features, "source_is_ipv6"), 0.0);
+        assert_eq!(feature(&ipv4_features, "source_is_private"), 1.0);
+        assert_eq!(feature(&ipv4_features, "same_subnet"), 1.0);
+        
+        assert_eq!(feature(&malformed_features, "invalid_address"), 1.0);
+        assert_eq!(feature(&malformed_features, "same_subnet"), 0.0);
+        assert_ne!(ipv6_features.features, malformed_features.features);
+        
+        // Extraction stable pour une même entrée
+        assert_eq!(firewall.extract_features(&ipv6).unwrap().features, ipv6_features.features);
+    }
+    
+    #[test]
+    fn test_redirect_external_web_anomaly() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        
+        let redirected = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&redirected);
+        firewall.on_redirect(move |packet| sink.lock().unwrap().push(packet.source_ip.clone()));
+        firewall.initialize().unwrap();
+        
+        // Score dans la bande d'alerte : [0.8 * seuil, seuil[
+        force_score(&firewall, 0.75);
+        
+        let mut packet = create_test_packet();
+        packet.source_ip = "203.0.113.7".to_string();
+        let (decision, event) = firewall.analyze_packet(packet).unwrap();
+        
+        assert_eq!(decision, FirewallDecision::Redirect);
+        assert_eq!(event.unwrap().decision, FirewallDecision::Redirect);
+        assert_eq!(*redirected.lock().unwrap(), vec!["203.0.113.7".to_string()]);
+        
+        // Une source interne reste en simple alerte
+        let (decision, _) = firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(decision, FirewallDecision::Alert);
+        assert_eq!(redirected.lock().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn test_concurrent_stats_stay_consistent() {
+        let config = NeuroFireWallConfig {
+            packets_per_second: 10_000,
+            ..Default::default()
+        };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        let firewall = Arc::new(firewall);
+        
+        let workers: Vec<_> = (0..2)
+            .map(|worker| {
+                let firewall = Arc::clone(&firewall);
+                std::thread::spawn(move || {
+                    for i in 0..200 {
+                        let mut packet = create_test_packet();
+                        packet.id = format!("packet-{}-{}", worker, i);
+                        firewall.analyze_packet(packet).unwrap();
+                    }
+                })
+            })
+            .collect();
+        
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 400);
+        assert!(stats.min_analysis_time_us <= stats.avg_analysis_time_us);
+        assert!(stats.avg_analysis_time_us <= stats.max_analysis_time_us);
+    }
+    
+    #[test]
+    fn test_atomic_counters_exact_under_contention() {
+        let config = NeuroFireWallConfig {
+            packets_per_second: 1_000_000,
+            hidden_layer_size: 8,
+            ..Default::default()
+        };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        let firewall = Arc::new(firewall);
+        
+        let workers: Vec<_> = (0..8)
+            .map(|worker| {
+                let firewall = Arc::clone(&firewall);
+                std::thread::spawn(move || {
+                    let mut allowed = 0;
+                    for i in 0..500 {
+                        let mut packet = create_test_packet();
+                        packet.id = format!("packet-{}-{}", worker, i);
+                        packet.source_ip = format!("10.0.{}.{}", worker, i % 250);
+                        let (decision, _) = firewall.analyze_packet(packet).unwrap();
+                        if decision == FirewallDecision::Allow {
+                            allowed += 1;
+                        }
+                    }
+                    allowed
+                })
+            })
+            .collect();
+        
+        let allowed: u64 = workers.into_iter().map(|worker| worker.join().unwrap()).sum();
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 4000);
+        assert_eq!(stats.packets_allowed, allowed);
+    }
+    
+    #[test]
+    fn test_concurrent_predictions_with_learning() {
+        let config = NeuroFireWallConfig { packets_per_second: 1_000_000, ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        let firewall = Arc::new(firewall);
+        
+        let mut malicious = create_test_packet();
+        malicious.destination_port = 4444;
+        malicious.traffic_type = TrafficType::Unknown;
+        malicious.payload_sample = vec![0x90, 0x90, 0xCC, 0xEB, 0xFE];
+        let features = firewall.extract_features(&malicious).unwrap().features;
+        let initial_score = firewall.model.read().unwrap().predict(&features).unwrap();
+        
+        // Les analyses (lecture) et l'apprentissage (écriture) s'exécutent simultanément ;
+        // un interblocage ferait expirer l'attente ci-dessous
+        let (done, finished) = std::sync::mpsc::channel();
+        let supervisor = {
+            let firewall = Arc::clone(&firewall);
+            std::thread::spawn(move || {
+                let readers: Vec<_> = (0..8)
+                    .map(|worker| {
+                        let firewall = Arc::clone(&firewall);
+                        std::thread::spawn(move || {
+                            for i in 0..200 {
+                                let packet = NetworkPacket {
+                                    id: format!("packet-{}-{}", worker, i),
+                                    ..create_test_packet()
+                                };
+                                firewall.analyze_packet(packet).unwrap();
+                            }
+                        })
+                    })
+                    .collect();
+                
+                for _ in 0..20 {
+                    let packet = NetworkPacket { id: format!("packet-{}", uuid::Uuid::new_v4()), ..malicious.clone() };
+                    firewall.analyze_packet(packet.clone()).unwrap();
+                    firewall.feedback(&packet.id, true).unwrap();
+                    firewall.run_learning_cycle().unwrap();
+                }
+                
+                readers.into_iter().for_each(|reader| reader.join().unwrap());
+                done.send(()).unwrap();
+            })
+        };
+        
+        finished.recv_timeout(Duration::from_secs(60)).expect("Interblocage entre analyse et apprentissage");
+        supervisor.join().unwrap();
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 8 * 200 + 20);
+        assert_eq!(stats.learning_cycles, 20);
+        
+        let trained_score = firewall.model.read().unwrap().predict(&features).unwrap();
+        assert!(trained_score > initial_score, "{} <= {}", trained_score, initial_score);
+    }
+    
+    #[test]
+    fn test_sensitivity_shifts_decision_thresholds() {
+        let decide = |sensitivity: f32| {
+            let config = NeuroFireWallConfig { sensitivity, ..Default::default() };
+            NeuroFireWall::new(config).make_decision(0.78)
+        };
+        
+        assert_eq!(decide(0.0), FirewallDecision::Allow);
+        assert_eq!(decide(0.75), FirewallDecision::Alert);
+        assert_eq!(decide(1.0), FirewallDecision::Quarantine);
+        
+        // Le mode strict bloque dès le seuil effectif
+        let config = NeuroFireWallConfig { sensitivity: 1.0, strict_mode: true, ..Default::default() };
+        assert_eq!(NeuroFireWall::new(config).make_decision(0.78), FirewallDecision::Block);
+    }
+    
+    #[test]
+    fn test_blocklist_expires_with_backoff() {
+        let clock = Arc::new(MockClock::new(SystemTime::now()));
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.set_clock(clock.clone());
+        firewall.initialize().unwrap();
+        
+        let source = "203.0.113.7";
+        firewall.block_source(source);
+        let first_ttl = firewall.blocked_until(source).unwrap().duration_since(clock.now()).unwrap();
+        assert_eq!(first_ttl, Duration::from_secs(60));
+        
+        // Toujours bloquée avant l'échéance ; un nouveau blocage ne prolonge pas le premier
+        clock.advance(Duration::from_secs(30));
+        firewall.block_source(source);
+        assert_eq!(firewall.expire_blocklist(), 0);
+        assert!(firewall.is_blocked(source));
+        
+        clock.advance(Duration::from_secs(31));
+        assert!(!firewall.is_blocked(source));
+        assert_eq!(firewall.expire_blocklist(), 1);
+        assert!(firewall.blocked_until(source).is_none());
+        
+        // La récidive est bloquée plus longtemps
+        firewall.block_source(source);
+        let second_ttl = firewall.blocked_until(source).unwrap().duration_since(clock.now()).unwrap();
+        assert_eq!(second_ttl, Duration::from_secs(300));
+        assert!(second_ttl > first_ttl);
+        
+        // La durée est bornée par max_block_ttl_secs
+        let config = NeuroFireWallConfig { max_block_ttl_secs: 120, ..Default::default() };
+        let firewall = NeuroFireWall::new(config);
+        assert_eq!(firewall.block_ttl(10), Duration::from_secs(120));
+    }
+    
+    #[test]
+    fn test_offenses_decay_and_are_bounded() {
+        let clock = Arc::new(MockClock::new(SystemTime::now()));
+        let config = NeuroFireWallConfig {
+            offense_decay_secs: 3600,
+            max_tracked_offenders: 2,
+            ..Default::default()
+        };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.set_clock(clock.clone());
+        let ttl = |source: &str| firewall.blocked_until(source).unwrap().duration_since(clock.now()).unwrap();
+        
+        // Une récidive peu après la fin du blocage est sanctionnée plus longuement
+        let source = "203.0.113.7";
+        firewall.block_source(source);
+        clock.advance(Duration::from_secs(61));
+        firewall.block_source(source);
+        assert_eq!(ttl(source), Duration::from_secs(300));
+        
+        // Après une période calme, les récidives sont oubliées
+        clock.advance(Duration::from_secs(300 + 3600));
+        assert_eq!(firewall.expire_blocklist(), 1);
+        assert!(firewall.blocklist.lock().unwrap().offenses.is_empty());
+        firewall.block_source(source);
+        assert_eq!(ttl(source), Duration::from_secs(60));
+        
+        // Au-delà de max_tracked_offenders, la source bloquée le moins récemment est oubliée
+        firewall.block_source("198.51.100.1");
+        firewall.block_source("198.51.100.2");
+        let blocklist = firewall.blocklist.lock().unwrap();
+        assert_eq!(blocklist.offenses.len(), 2);
+        assert_eq!(blocklist.offense_order.len(), 2);
+        assert!(!blocklist.offenses.contains_key(source));
+    }
+    
+    #[test]
+    fn test_replay_matches_individual_analysis() {
+        let packets: Vec<NetworkPacket> = [("192.168.1.100", 80), ("10.0.0.66", 22), ("192.168.1.101", 443)]
+            .iter()
+            .map(|&(source, port)| NetworkPacket::builder()
+                .source(source, 40000)
+                .destination("192.168.1.1", port)
+                .size(512)
+                .payload_sample(b"GET / HTTP/1.1".to_vec())
+                .build()
+                .unwrap())
+            .collect();
+        
+        let create_firewall = || {
+            let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+            firewall.initialize().unwrap();
+            force_score(&firewall, 0.9);
+            firewall.block_source("10.0.0.66");
+            firewall
+        };
+        
+        let individual = create_firewall();
+        let expected: Vec<FirewallDecision> = packets
+            .iter()
+            .map(|packet| individual.analyze_packet(packet.clone()).unwrap().0)
+            .collect();
+        assert_eq!(expected, vec![FirewallDecision::Quarantine, FirewallDecision::Block, FirewallDecision::Quarantine]);
+        
+        let mut capture = Vec::new();
+        for packet in &packets {
+            capture.extend(serde_json::to_vec(packet).unwrap());
+            capture.extend(b"\n\n");
+        }
+        
+        let replayed = create_firewall().replay_from_reader(capture.as_slice()).unwrap();
+        assert_eq!(replayed, expected);
+        
+        let error = create_firewall().replay_from_reader(&b"{\"id\": \"incomplet\"}\n"[..]).unwrap_err();
+        assert!(error.contains("ligne 1"));
+    }
+    
+    #[test]
+    fn test_custom_decision_thresholds() {
+        let thresholds = DecisionThresholds { block: 0.8, quarantine: 0.6, alert: 0.3 };
+        let config = NeuroFireWallConfig { decision_thresholds: Some(thresholds), ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        assert_eq!(firewall.make_decision(0.2), FirewallDecision::Allow);
+        assert_eq!(firewall.make_decision(0.3), FirewallDecision::Alert);
+        assert_eq!(firewall.make_decision(0.65), FirewallDecision::Quarantine);
+        assert_eq!(firewall.make_decision(0.85), FirewallDecision::Block);
+        assert_eq!(firewall.get_current_threshold(), 0.6);
+        
+        // Les seuils par défaut reproduisent le comportement historique
+        let defaults = NeuroFireWallConfig::default().thresholds();
+        assert_eq!(defaults, DecisionThresholds::default());
+        assert_eq!((defaults.block, defaults.quarantine, defaults.alert), (0.95, 0.85, 0.85 * 0.8));
+    }
+    
+    #[test]
+    fn test_inverted_decision_thresholds_rejected() {
+        let inverted = DecisionThresholds { block: 0.5, quarantine: 0.7, alert: 0.2 };
+        assert!(inverted.validate().is_err());
+        assert!(DecisionThresholds { block: 1.2, quarantine: 0.7, alert: 0.2 }.validate().is_err());
+        assert!(DecisionThresholds::default().validate().is_ok());
+        
+        let config = NeuroFireWallConfig { decision_thresholds: Some(inverted), ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        assert!(firewall.initialize().is_err());
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Initializing);
+    }
+    
+    #[test]
+    fn test_quarantine_list_and_release() {
+        let config = NeuroFireWallConfig { quarantine_size: 2, max_payload_sample_bytes: 256, ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        force_score(&firewall, 0.9);
+        
+        let payload: Vec<u8> = (0..10 * 1024).map(|i| (i % 251) as u8).collect();
+        for id in ["packet-q1", "packet-q2", "packet-q3"] {
+            let packet = NetworkPacket { id: id.to_string(), payload_sample: payload.clone(), ..create_test_packet() };
+            let (decision, _) = firewall.analyze_packet(packet).unwrap();
+            assert_eq!(decision, FirewallDecision::Quarantine);
+        }
+        
+        // Le plus ancien paquet est évincé au-delà de la capacité
+        assert_eq!(firewall.list_quarantined(), vec!["packet-q2".to_string(), "packet-q3".to_string()]);
+        
+        let released = firewall.release_packet("packet-q2").unwrap();
+        assert_eq!(released.id, "packet-q2");
+        assert_eq!(released.payload_sample, payload[..256]);
+        assert_eq!(firewall.list_quarantined(), vec!["packet-q3".to_string()]);
+        assert!(firewall.release_packet("packet-q2").is_err());
+        
+        assert_eq!(firewall.purge_quarantine(), 1);
+        assert!(firewall.list_quarantined().is_empty());
+    }
+    
+    #[test]
+    fn test_signature_match_blocks_packet() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        firewall.load_signatures(vec![AttackSignature {
+            id: "sig-webshell".to_string(),
+            name: "Webshell upload".to_string(),
+            description: "Téléversement de webshell observé dans un honeypot".to_string(),
+            patterns: vec!["env_type:WebServer".to_string(), "payload:malicious_script.php".to_string()],
+            confidence: 0.85,
+            created_at: SystemTime::now(),
+            related_attack_events: vec![],
+            recommended_countermeasures: vec!["block_ip".to_string()],
+        }]);
+        
+        let mut packet = create_test_packet();
+        packet.payload_sample = b"POST /upload malicious_script.php HTTP/1.1".to_vec();
+        let (decision, event) = firewall.analyze_packet(packet).unwrap();
+        
+        assert_eq!(decision, FirewallDecision::Block);
+        let event = event.unwrap();
+        assert!(event.description.contains("sig-webshell"));
+        assert_eq!(event.trigger_features, vec!["payload:malicious_script.php".to_string()]);
+        
+        // Un paquet sans motif correspondant suit le chemin normal
+        let (decision, _) = firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_ne!(decision, FirewallDecision::Block);
+    }
+    
+    #[test]
+    fn test_payload_entropy_feature() {
+        let config = NeuroFireWallConfig::default();
+        let firewall = NeuroFireWall::new(config);
+        
+        let entropy = |payload: Vec<u8>| {
+            let packet = NetworkPacket { payload_sample: payload, ..create_test_packet() };
+            let features = firewall.extract_features(&packet).unwrap();
+            let index = features.feature_labels.iter().position(|l| l == "payload_entropy").unwrap();
+            features.features[index]
+        };
+        
+        let text = entropy(b"GET /index.html HTTP/1.1 aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".repeat(8));
+        let random = entropy((0..1024).map(|_| rand::random::<u8>()).collect());
+        
+        assert!(text < 0.6, "entropie du texte: {}", text);
+        assert!(random > 0.9, "entropie aléatoire: {}", random);
+        assert_eq!(entropy(vec![]), 0.0);
+    }
+    
+    #[test]
+    fn test_reset_stats_keeps_state() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        for _ in 0..3 {
+            firewall.analyze_packet(create_test_packet()).unwrap();
+        }
+        assert_eq!(firewall.get_stats().total_packets_analyzed, 3);
+        
+        firewall.reset_stats();
+        
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 0);
+        assert_eq!(stats.packets_allowed, 0);
+        assert_eq!(stats.avg_analysis_time_us, 0.0);
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+        
+        // Le calcul de la moyenne repart proprement après la remise à zéro
+        firewall.analyze_packet(create_test_packet()).unwrap();
+        let stats = firewall.get_stats();
+        assert_eq!(stats.total_packets_analyzed, 1);
+        assert_eq!(stats.avg_analysis_time_us, stats.min_analysis_time_us);
+    }
+    
+    #[test]
+    fn test_detection_event_ranks_contributing_features() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        force_score(&firewall, 0.99);
+        {
+            // La taille du paquet porte des poids bien plus forts que les autres caractéristiques
+            let mut model = firewall.model.write().unwrap();
+            for row in model.weights.iter_mut() {
+                for (index, w) in row.iter_mut().enumerate() {
+                    *w = if index == 2 { 1.0 } else { 0.01 };
+                }
+            }
+        }
+        
+        let packet = NetworkPacket { size: 1500, ..create_test_packet() };
+        let (_, event) = firewall.analyze_packet(packet).unwrap();
+        let event = event.unwrap();
+        
+        assert_eq!(event.trigger_features.len(), NeuroFireWall::TOP_FEATURES);
+        assert_eq!(event.trigger_features[0], "packet_size");
+        assert_eq!(event.feature_scores.len(), event.trigger_features.len());
+        assert!(event.feature_scores.windows(2).all(|pair| pair[0] >= pair[1]));
     }
     
-    /// Prend une décision basée sur le score d'anomalie
-    fn make_decision(&self, anomaly_score: f32) -> FirewallDecision {
-        // Cette fonction sera implémentée de manière plus sophistiquée dans les versions futures
-        // Pour l'instant, elle utilise des seuils simples
+    #[test]
+    fn test_window_stats_aggregates_sources() {
+        let config = NeuroFireWallConfig::default();
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
         
-        if anomaly_score >= 0.95 {
-            FirewallDecision::Block
-        } else if anomaly_score >= self.config.anomaly_threshold {
-            if self.config.strict_mode {
-                FirewallDecision::Block
-            } else {
-                FirewallDecision::Quarantine
+        for (source_ip, count) in [("10.0.0.2", 2), ("10.0.0.1", 3), ("10.0.0.3", 1)] {
+            for _ in 0..count {
+                let packet = NetworkPacket { source_ip: source_ip.to_string(), size: 100, ..create_test_packet() };
+                firewall.analyze_packet(packet).unwrap();
             }
-        } else if anomaly_score >= self.config.anomaly_threshold * 0.8 {
-            FirewallDecision::Alert
-        } else {
-            FirewallDecision::Allow
         }
+        
+        let stats = firewall.get_window_stats(10);
+        assert_eq!(stats.unique_sources, 3);
+        assert_eq!(
+            stats.top_talkers,
+            vec![("10.0.0.1".to_string(), 3), ("10.0.0.2".to_string(), 2), ("10.0.0.3".to_string(), 1)]
+        );
+        assert!((stats.pps - 0.6).abs() < 1e-6);
+        assert!((stats.bps - 60.0).abs() < 1e-6);
     }
     
-    /// Exécute un cycle d'apprentissage
-    pub fn run_learning_cycle(&self) -> Result<(), String> {
-        // Vérifier si l'apprentissage continu est activé
-        if !self.config.enable_continuous_learning {
-            return Err("L'apprentissage continu est désactivé".to_string());
+    #[test]
+    fn test_traffic_window_is_capped() {
+        let config = NeuroFireWallConfig { max_window_samples: 3, ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        for index in 0..5 {
+            let packet = NetworkPacket { source_ip: format!("10.0.0.{}", index), ..create_test_packet() };
+            firewall.analyze_packet(packet).unwrap();
         }
         
-        // Changer l'état en mode apprentissage
-        {
-            let mut state = self.state.lock().unwrap();
-            *state = NeuroFireWallState::Learning;
+        // Seuls les trois paquets les plus récents sont conservés
+        assert_eq!(firewall.traffic_window.lock().unwrap().len(), 3);
+        let stats = firewall.get_window_stats(10);
+        assert_eq!(stats.unique_sources, 3);
+        assert!(stats.top_talkers.iter().all(|(source, _)| source != "10.0.0.0" && source != "10.0.0.1"));
+    }
+    
+    #[test]
+    fn test_detection_events_export() {
+        let config = NeuroFireWallConfig { max_retained_events: 2, ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        
+        assert!(firewall.detection_events_ndjson().is_empty());
+        
+        force_score(&firewall, 0.99);
+        for _ in 0..3 {
+            firewall.analyze_packet(create_test_packet()).unwrap();
         }
         
-        // Cette fonction sera implémentée de manière plus sophistiquée dans les versions futures
-        // Pour l'instant, elle simule un cycle d'apprentissage
+        // Seuls les deux derniers événements sont conservés
+        let ndjson = firewall.detection_events_ndjson();
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
         
-        // Simuler un délai d'apprentissage
-        std::thread::sleep(Duration::from_millis(100));
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert!((event["anomaly_score"].as_f64().unwrap() - 0.99).abs() < 1e-3);
+        assert_eq!(event["decision"], "Block");
         
-        // Mettre à jour les statistiques
-        let mut stats = self.stats.lock().unwrap();
-        stats.learning_cycles += 1;
+        let cef = firewall.detection_events_cef();
+        assert_eq!(cef.lines().count(), 2);
+        assert!(cef.starts_with("CEF:0|ICARUS|NeuroFireWall|"));
+        assert!(cef.contains("|Block|") && cef.contains("|10|"));
+    }
+    
+    #[test]
+    fn test_packet_builder_validation() {
+        let packet = NetworkPacket::builder()
+            .id("packet-builder")
+            .source("2001:db8::1", 443)
+            .destination("10.0.0.1", 8443)
+            .protocol("HTTPS")
+            .metadata("interface", "eth0")
+            .build()
+            .unwrap();
+        assert_eq!(packet.id, "packet-builder");
+        assert_eq!(packet.destination_port, 8443);
+        assert_eq!(packet.metadata.get("interface").map(String::as_str), Some("eth0"));
         
-        // Restaurer l'état opérationnel
-        {
-            let mut state = self.state.lock().unwrap();
-            *state = NeuroFireWallState::Operational;
-        }
+        let error = NetworkPacket::builder()
+            .source("192.168.1.300", 1234)
+            .destination("10.0.0.1", 80)
+            .build()
+            .unwrap_err();
+        assert!(error.contains("source") && error.contains("192.168.1.300"), "{}", error);
         
-        Ok(())
+        let error = NetworkPacket::builder()
+            .source("192.168.1.1", 0)
+            .destination("10.0.0.1", 80)
+            .build()
+            .unwrap_err();
+        assert!(error.contains("Port source"), "{}", error);
+        
+        // ICMP n'utilise pas de ports
+        assert!(NetworkPacket::builder()
+            .source("192.168.1.1", 0)
+            .destination("10.0.0.1", 0)
+            .protocol("ICMP")
+            .build()
+            .is_ok());
     }
     
-    /// Obtient l'état actuel du système
-    pub fn get_state(&self) -> NeuroFireWallState {
-        self.state.lock().unwrap().clone()
+    #[test]
+    fn test_packet_builder_uses_firewall_sources() {
+        let clock = Arc::new(MockClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.set_clock(clock.clone());
+        firewall.set_id_generator(Arc::new(SequentialIdGenerator::new()));
+        
+        let build = || firewall.packet_builder().source("192.168.1.1", 1234).destination("10.0.0.1", 80).build().unwrap();
+        let first = build();
+        assert_eq!(first.id, "packet-0");
+        assert_eq!(first.timestamp, clock.now());
+        assert_eq!(build().id, "packet-1");
     }
     
-    /// Obtient les statistiques actuelles
-    pub fn get_stats(&self) -> NeuroFireWallStats {
-        self.stats.lock().unwrap().clone()
+    #[test]
+    fn test_detection_event_bincode_round_trip() {
+        let event = DetectionEvent {
+            id: "event-1".to_string(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+            anomaly_score: 0.87,
+            decision: FirewallDecision::Quarantine,
+            related_packets: vec!["packet-1".to_string(), "packet-2".to_string()],
+            trigger_features: vec!["payload_entropy".to_string(), "destination_port".to_string()],
+            feature_scores: vec![0.6, 0.2],
+            description: "Anomalie détectée avec un score de 0.87".to_string(),
+        };
+        
+        let decoded = DetectionEvent::from_bincode(&event.to_bincode().unwrap()).unwrap();
+        assert_eq!(decoded.id, event.id);
+        assert_eq!(decoded.timestamp, event.timestamp);
+        assert_eq!(decoded.anomaly_score, event.anomaly_score);
+        assert_eq!(decoded.decision, event.decision);
+        assert_eq!(decoded.related_packets, event.related_packets);
+        assert_eq!(decoded.trigger_features, event.trigger_features);
+        assert_eq!(decoded.feature_scores, event.feature_scores);
+        assert_eq!(decoded.description, event.description);
     }
     
-    /// Arrête le NeuroFireWall
-    pub fn shutdown(&self) -> Result<(), String> {
-        // Cette fonction sera implémentée dans les versions futures
-        // Pour l'instant, elle change simplement l'état
+    #[test]
+    fn test_buffered_payload_is_truncated() {
+        let config = NeuroFireWallConfig { max_payload_sample_bytes: 256, ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
         
-        let mut state = self.state.lock().unwrap();
-        *state = NeuroFireWallState::Shutdown;
+        let payload: Vec<u8> = (0..10 * 1024).map(|i| (i % 251) as u8).collect();
+        let packet = NetworkPacket { payload_sample: payload.clone(), ..create_test_packet() };
         
-        Ok(())
+        let (decision, _) = firewall.analyze_packet(packet.clone()).unwrap();
+        assert_eq!(decision, FirewallDecision::Allow);
+        
+        let buffer = firewall.packet_buffer.lock().unwrap();
+        let buffered = buffer.back().unwrap();
+        assert_eq!(buffered.id, packet.id);
+        assert_eq!(buffered.payload_sample, payload[..256]);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
     
-    fn create_test_packet() -> NetworkPacket {
-        NetworkPacket {
-            id: format!("packet-{}", uuid::Uuid::new_v4()),
-            source_ip: "192.168.1.100".to_string(),
-            destination_ip: "192.168.1.1".to_string(),
-            source_port: 12345,
-            destination_port: 80,
-            protocol: "TCP".to_string(),
-            size: 1024,
-            timestamp: SystemTime::now(),
-            traffic_type: TrafficType::Web,
-            payload_sample: vec![0, 1, 2, 3, 4],
-            metadata: HashMap::new(),
+    #[test]
+    fn test_export_import_model() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.initialize().unwrap();
+        
+        for _ in 0..10 {
+            let mut packet = create_test_packet();
+            packet.id = format!("packet-{}", uuid::Uuid::new_v4());
+            packet.destination_port = 4444;
+            firewall.analyze_packet(packet.clone()).unwrap();
+            firewall.feedback(&packet.id, true).unwrap();
+            firewall.run_learning_cycle().unwrap();
         }
+        
+        let snapshot = firewall.export_model();
+        let restored = NeuroFireWall::new(NeuroFireWallConfig::default());
+        restored.import_model(&snapshot).unwrap();
+        
+        let features = firewall.extract_features(&create_test_packet()).unwrap().features;
+        assert_eq!(
+            restored.model.read().unwrap().predict(&features).unwrap(),
+            firewall.model.read().unwrap().predict(&features).unwrap()
+        );
+        
+        // Un modèle d'une autre dimension d'entrée est refusé, le modèle courant est conservé
+        let calls = Arc::new(AtomicU64::new(0));
+        let other = NeuroFireWall::with_feature_extractor(NeuroFireWallConfig::default(), Box::new(CountingExtractor { calls }));
+        assert!(other.import_model(&snapshot).is_err());
+        assert_eq!(other.model.read().unwrap().input_size, 3);
+        
+        let mut corrupted = snapshot.clone();
+        corrupted[0] = corrupted[0].wrapping_add(1);
+        assert!(restored.import_model(&corrupted).is_err());
+        assert!(restored.import_model(&snapshot[..snapshot.len() / 2]).is_err());
     }
     
     #[test]
-    fn test_neurofirewall_initialization() {
-        let config = NeuroFireWallConfig::default();
+    fn test_score_histogram_and_threshold_suggestion() {
+        let config = NeuroFireWallConfig { packets_per_second: 10_000, ..Default::default() };
         let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        assert_eq!(firewall.suggest_threshold(0.1), firewall.get_current_threshold());
         
-        assert_eq!(firewall.get_state(), NeuroFireWallState::Initializing);
+        // Scores uniformes : cinq paquets par intervalle
+        for k in 0..100 {
+            force_score(&firewall, k as f32 / 100.0 + 0.005);
+            let mut packet = create_test_packet();
+            packet.id = format!("packet-{}", k);
+            firewall.analyze_packet(packet).unwrap();
+        }
         
-        let result = firewall.initialize();
-        assert!(result.is_ok());
+        assert_eq!(firewall.score_histogram(), [5; NeuroFireWall::SCORE_HISTOGRAM_BINS]);
+        
+        for (target, expected) in [(0.1, 0.9), (0.25, 0.75), (0.13, 0.87), (1.0, 0.0)] {
+            let suggested = firewall.suggest_threshold(target);
+            assert!((suggested - expected).abs() < 0.01, "{}: {} au lieu de {}", target, suggested, expected);
+        }
+        
+        firewall.reset_stats();
+        assert_eq!(firewall.score_histogram(), [0; NeuroFireWall::SCORE_HISTOGRAM_BINS]);
+    }
+    
+    /// Extracteur de test dont la sortie peut être rendue invalide (dimension incorrecte)
+    struct FaultyExtractor {
+        broken: Arc<std::sync::atomic::AtomicBool>,
+    }
+    
+    impl PacketFeatureExtractor for FaultyExtractor {
+        fn extract(&self, _packet: &NetworkPacket) -> Vec<f32> {
+            let dimension = if self.broken.load(Ordering::Relaxed) { 2 } else { 4 };
+            vec![0.1; dimension]
+        }
         
+        fn dimension(&self) -> usize {
+            4
+        }
+    }
+    
+    #[test]
+    fn test_degraded_mode_on_repeated_errors() {
+        let broken = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let extractor = FaultyExtractor { broken: Arc::clone(&broken) };
+        let config = NeuroFireWallConfig { max_consecutive_errors: 3, ..Default::default() };
+        let mut firewall = NeuroFireWall::with_feature_extractor(config, Box::new(extractor));
+        firewall.initialize().unwrap();
+        
+        assert!(firewall.analyze_packet(create_test_packet()).is_err());
+        assert!(firewall.analyze_packet(create_test_packet()).is_err());
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+        
+        // Troisième échec : mode dégradé et décision de repli
+        let (decision, event) = firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Degraded);
+        assert_eq!(decision, FirewallDecision::Allow);
+        assert!(event.is_none());
+        
+        let mut unknown = create_test_packet();
+        unknown.protocol = "GRE".to_string();
+        assert_eq!(firewall.analyze_packet(unknown).unwrap().0, FirewallDecision::Block);
+        
+        // Le modèle répond de nouveau : retour à l'état opérationnel
+        broken.store(false, Ordering::Relaxed);
+        assert!(firewall.analyze_packet(create_test_packet()).is_ok());
         assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+        assert!(firewall.analyze_packet(create_test_packet()).is_ok());
     }
     
     #[test]
-    fn test_analyze_normal_packet() {
-        let config = NeuroFireWallConfig::default();
+    fn test_model_errors_count_toward_degraded_mode() {
+        let config = NeuroFireWallConfig { max_consecutive_errors: 2, ..Default::default() };
         let mut firewall = NeuroFireWall::new(config);
         firewall.initialize().unwrap();
         
-        let packet = create_test_packet();
+        // Modèle dont la couche d'entrée ne correspond pas aux caractéristiques extraites
+        *firewall.model.write().unwrap() = NeuralModel::new(3, 8, 1, 0.05, None);
         
-        let result = firewall.analyze_packet(packet);
-        assert!(result.is_ok());
+        assert!(firewall.analyze_packet(create_test_packet()).is_err());
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Operational);
+        assert!(firewall.analyze_packet(create_test_packet()).is_ok());
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Degraded);
         
-        let (decision, event) = result.unwrap();
+        // L'apprentissage conserve le mode dégradé tant que les échecs persistent
+        firewall.run_learning_cycle().unwrap();
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Degraded);
+        
+        // Un pare-feu arrêté n'apprend pas et n'est pas relancé
+        firewall.shutdown().unwrap();
+        assert!(firewall.run_learning_cycle().is_err());
+        assert_eq!(firewall.get_state(), NeuroFireWallState::Shutdown);
+    }
+    
+    #[test]
+    fn test_trusted_sources_bypass_analysis() {
+        let config = NeuroFireWallConfig { trusted_sources: vec!["192.0.2.10".to_string()], ..Default::default() };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        firewall.add_trusted("10.0.0.0/8").unwrap();
+        
+        assert!(firewall.is_trusted("10.1.2.3"));
+        assert!(firewall.is_trusted("192.0.2.10"));
+        assert!(!firewall.is_trusted("11.1.2.3"));
+        assert!(!firewall.is_trusted("adresse-invalide"));
+        assert!(firewall.add_trusted("10.0.0.0/33").is_err());
+        
+        // Même bloqué et avec une charge malveillante, un paquet de confiance est autorisé
+        firewall.block_source("10.1.2.3");
+        force_score(&firewall, 0.99);
+        let packet = NetworkPacket {
+            source_ip: "10.1.2.3".to_string(),
+            payload_sample: vec![0x90, 0x90, 0xCC, 0xEB, 0xFE],
+            ..create_test_packet()
+        };
+        let (decision, event) = firewall.analyze_packet(packet).unwrap();
         assert_eq!(decision, FirewallDecision::Allow);
         assert!(event.is_none());
         
+        let (decision, _) = firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(decision, FirewallDecision::Block);
+        
         let stats = firewall.get_stats();
-        assert_eq!(stats.total_packets_analyzed, 1);
+        assert_eq!(stats.allowed_trusted, 1);
         assert_eq!(stats.packets_allowed, 1);
     }
     
     #[test]
-    fn test_extract_features() {
-        let config = NeuroFireWallConfig::default();
-        let mut firewall = NeuroFireWall::new(config);
+    fn test_model_seed_makes_initialization_reproducible() {
+        let seeded = |seed| NeuroFireWall::new(NeuroFireWallConfig { model_seed: Some(seed), ..Default::default() });
+        let first = seeded(42);
+        let second = seeded(42);
+        let other = seeded(43);
+        
+        let features = first.extract_features(&create_test_packet()).unwrap().features;
+        let predict = |firewall: &NeuroFireWall| firewall.model.read().unwrap().predict(&features).unwrap();
+        
+        assert_eq!(predict(&first), predict(&second));
+        assert_ne!(predict(&first), predict(&other));
+    }
+    
+    #[test]
+    fn test_flow_reassembly_detects_split_pattern() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
         firewall.initialize().unwrap();
+        firewall.load_signatures(vec![AttackSignature {
+            id: "sig-sqli".to_string(),
+            name: "Injection SQL".to_string(),
+            description: "Injection SQL par UNION".to_string(),
+            patterns: vec!["payload:UNION SELECT".to_string()],
+            confidence: 0.9,
+            created_at: SystemTime::now(),
+            related_attack_events: vec![],
+            recommended_countermeasures: vec![],
+        }]);
         
-        let packet = create_test_packet();
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let fragment = |id: &str, offset_ms: u64, payload: &[u8]| NetworkPacket {
+            id: id.to_string(),
+            timestamp: start + Duration::from_millis(offset_ms),
+            payload_sample: payload.to_vec(),
+            ..create_test_packet()
+        };
+        let first = fragment("fragment-1", 0, b"GET /items?id=1 UNI");
+        let second = fragment("fragment-2", 100, b"ON SELECT password FROM users");
         
-        let result = firewall.extract_features(&packet);
-        assert!(result.is_ok());
+        // Aucun fragment ne correspond seul à la signature
+        for packet in [first.clone(), second.clone()] {
+            let (_, event) = firewall.analyze_packet(packet).unwrap();
+            assert!(event.is_none_or(|event| !event.description.contains("sig-sqli")));
+        }
         
-        let features = result.unwrap();
-        assert_eq!(features.packet_id, packet.id);
-        assert_eq!(features.features.len(), 10);
-        assert_eq!(features.feature_labels.len(), 10);
+        let mut reassembler = FlowReassembler::new(Duration::from_secs(1), FlowReassembler::DEFAULT_MAX_FLOWS);
+        assert!(reassembler.push(first).is_empty());
+        assert!(reassembler.push(second).is_empty());
+        assert!(reassembler.flush_expired(start + Duration::from_millis(500)).is_empty());
+        
+        let flows = reassembler.flush_expired(start + Duration::from_secs(5));
+        assert_eq!(flows.len(), 1);
+        assert_eq!(reassembler.pending_flows(), 0);
+        
+        let before = firewall.get_stats();
+        let (decision, event) = firewall.analyze_flow(flows.into_iter().next().unwrap()).unwrap();
+        assert_eq!(decision, FirewallDecision::Block);
+        let event = event.unwrap();
+        assert!(event.description.contains("sig-sqli"));
+        assert_eq!(event.related_packets, vec!["fragment-1".to_string(), "fragment-2".to_string()]);
+        assert_eq!(firewall.recent_events(1)[0].related_packets, event.related_packets);
+        
+        // Les paquets du flux, déjà analysés, ne sont pas comptabilisés une seconde fois
+        let after = firewall.get_stats();
+        assert_eq!(after.total_packets_analyzed, before.total_packets_analyzed);
+        assert_eq!(after.packets_blocked, before.packets_blocked);
+        assert_eq!(after.detection_events, before.detection_events + 1);
+        
+        // Un paquet arrivant après l'expiration termine le flux précédent
+        let late = fragment("fragment-3", 10_000, b"late");
+        let mut reassembler = FlowReassembler::new(Duration::from_secs(1), FlowReassembler::DEFAULT_MAX_FLOWS);
+        reassembler.push(fragment("fragment-4", 0, b"early"));
+        let completed = reassembler.push(late);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].packet_ids(), vec!["fragment-4".to_string()]);
+        assert_eq!(reassembler.pending_flows(), 1);
     }
     
     #[test]
-    fn test_learning_cycle() {
-        let mut config = NeuroFireWallConfig::default();
-        config.enable_continuous_learning = true;
+    fn test_flow_table_evicts_oldest_flow_at_capacity() {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let syn = |port: u16, offset_ms: u64| NetworkPacket {
+            id: format!("syn-{}", port),
+            source_port: port,
+            timestamp: start + Duration::from_millis(offset_ms),
+            payload_sample: Vec::new(),
+            ..create_test_packet()
+        };
+        
+        let mut reassembler = FlowReassembler::new(Duration::from_secs(60), 3);
+        for port in 40_000..40_003 {
+            assert!(reassembler.push(syn(port, u64::from(port - 40_000))).is_empty());
+        }
+        
+        // Un paquet d'un flux existant ne provoque pas d'éviction
+        assert!(reassembler.push(syn(40_000, 10)).is_empty());
         
+        // Chaque nouveau flux au-delà de la limite restitue le plus ancien
+        let evicted = reassembler.push(syn(40_003, 20));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].packet_ids(), vec!["syn-40000".to_string(), "syn-40000".to_string()]);
+        let evicted = reassembler.push(syn(40_004, 30));
+        assert_eq!(evicted[0].key.source_port, 40_001);
+        assert_eq!(reassembler.pending_flows(), 3);
+        
+        // Un balayage ne dépasse jamais la limite
+        for port in 50_000..51_000 {
+            reassembler.push(syn(port, 100));
+        }
+        assert_eq!(reassembler.pending_flows(), 3);
+        assert_eq!(reassembler.order.len(), 3);
+    }
+    
+    #[test]
+    fn test_dns_tunneling_features() {
+        let config = NeuroFireWallConfig { model_seed: Some(7), ..Default::default() };
         let mut firewall = NeuroFireWall::new(config);
         firewall.initialize().unwrap();
         
-        let result = firewall.run_learning_cycle();
-        assert!(result.is_ok());
+        // Requête au format filaire : en-tête de 12 octets puis labels préfixés par leur longueur
+        let dns_query = |name: &str| {
+            let mut payload = vec![0x12, 0x34, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+            for label in name.split('.') {
+                payload.push(label.len() as u8);
+                payload.extend_from_slice(label.as_bytes());
+            }
+            payload.extend_from_slice(&[0x00, 0x00, 0x01, 0x00, 0x01]);
+            NetworkPacket {
+                id: format!("packet-{}", uuid::Uuid::new_v4()),
+                protocol: "UDP".to_string(),
+                destination_port: 53,
+                traffic_type: TrafficType::Dns,
+                payload_sample: payload,
+                ..create_test_packet()
+            }
+        };
+        let tunnel_name = "4a6f686e20446f65.3a70617373776f72643d7a.9f3c1be07a2d5e48c6.b1f0d7e2a9c3.x7.exfil.example.com";
+        let normal = dns_query("www.example.com");
+        let tunneling = dns_query(tunnel_name);
+        
+        let dns_range = DefaultFeatureExtractor::FEATURE_COUNT - DefaultFeatureExtractor::DNS_FEATURES..;
+        let normal_features = firewall.extract_features(&normal).unwrap();
+        let tunneling_features = firewall.extract_features(&tunneling).unwrap();
+        assert_eq!(normal_features.feature_labels[dns_range.clone()][0], "dns_query_length");
+        for (normal_value, tunneling_value) in
+            normal_features.features[dns_range.clone()].iter().zip(&tunneling_features.features[dns_range.clone()])
+        {
+            assert!(tunneling_value > normal_value, "{} <= {}", tunneling_value, normal_value);
+        }
+        
+        // Les indicateurs DNS ne s'appliquent qu'au trafic DNS
+        let web = NetworkPacket { traffic_type: TrafficType::Web, ..tunneling.clone() };
+        assert!(firewall.extract_features(&web).unwrap().features[dns_range].iter().all(|&value| value == 0.0));
+        
+        // Les indicateurs permettent au modèle de séparer le tunnel de la requête légitime
+        for _ in 0..30 {
+            for (packet, malicious) in [(dns_query("www.example.com"), false), (dns_query(tunnel_name), true)] {
+                let id = packet.id.clone();
+                firewall.analyze_packet(packet).unwrap();
+                firewall.feedback(&id, malicious).unwrap();
+            }
+            firewall.run_learning_cycle().unwrap();
+        }
+        
+        let model = firewall.model.read().unwrap();
+        let normal_score = model.predict(&normal_features.features).unwrap();
+        let tunneling_score = model.predict(&tunneling_features.features).unwrap();
+        assert!(tunneling_score > normal_score, "{} <= {}", tunneling_score, normal_score);
+    }
+    
+    #[test]
+    fn test_retained_events_overflow_policy() {
+        let detect = |policy| {
+            let config = NeuroFireWallConfig {
+                max_retained_events: 3,
+                event_overflow_policy: policy,
+                ..Default::default()
+            };
+            let mut firewall = NeuroFireWall::new(config);
+            firewall.initialize().unwrap();
+            force_score(&firewall, 0.99);
+            
+            let ids: Vec<String> = (0..5)
+                .map(|_| firewall.analyze_packet(create_test_packet()).unwrap().1.unwrap().id)
+                .collect();
+            (firewall, ids)
+        };
+        let retained_ids = |firewall: &NeuroFireWall, limit| -> Vec<String> {
+            firewall.recent_events(limit).into_iter().map(|event| event.id).collect()
+        };
+        
+        let (firewall, ids) = detect(EventOverflowPolicy::DropOldest);
+        assert_eq!(retained_ids(&firewall, 10), ids[2..]);
+        assert_eq!(retained_ids(&firewall, 1), ids[4..]);
+        assert_eq!(firewall.get_stats().events_dropped, 2);
+        assert_eq!(firewall.get_stats().detection_events, 5);
+        
+        let (firewall, ids) = detect(EventOverflowPolicy::DropNewest);
+        assert_eq!(retained_ids(&firewall, 10), ids[..3]);
+        assert_eq!(firewall.get_stats().events_dropped, 2);
+        
+        firewall.reset_stats();
+        assert_eq!(firewall.get_stats().events_dropped, 0);
+    }
+    
+    #[test]
+    fn test_error_rates_from_operator_feedback() {
+        let config = NeuroFireWallConfig { feedback_window_size: 8, ..Default::default() };
+        let firewall = NeuroFireWall::new(config);
+        assert_eq!(firewall.error_rates(), ErrorRates::default());
         
+        // Trafic légitime : 1 faux positif sur 4 ; trafic malveillant : 2 faux négatifs sur 4
+        let feedback = [
+            (true, false),
+            (true, false),
+            (true, false),
+            (false, false),
+            (true, true),
+            (true, true),
+            (false, true),
+            (false, true),
+        ];
+        for (index, (was_correct, was_malicious)) in feedback.into_iter().enumerate() {
+            firewall.record_feedback(&format!("event-{}", index), was_correct, was_malicious).unwrap();
+        }
         let stats = firewall.get_stats();
-        assert_eq!(stats.learning_cycles, 1);
+        assert!((stats.false_positive_rate - 0.25).abs() < 1e-6);
+        assert!((stats.false_negative_rate - 0.5).abs() < 1e-6);
+        
+        // Une nouvelle évaluation remplace la précédente
+        firewall.record_feedback("event-3", true, false).unwrap();
+        assert_eq!(firewall.error_rates().false_positive_rate, 0.0);
+        
+        // Les plus anciens retours sortent de la fenêtre : event-0 à event-2 (légitimes) sont
+        // remplacés par deux faux positifs et un vrai négatif
+        firewall.record_feedback("event-8", false, false).unwrap();
+        firewall.record_feedback("event-9", false, false).unwrap();
+        firewall.record_feedback("event-10", true, false).unwrap();
+        let rates = firewall.error_rates();
+        assert!((rates.false_positive_rate - 2.0 / 4.0).abs() < 1e-6);
+        assert!((rates.false_negative_rate - 0.5).abs() < 1e-6);
+        
+        assert!(firewall.record_feedback("", true, true).is_err());
+        firewall.reset_stats();
+        assert_eq!(firewall.error_rates(), ErrorRates::default());
+    }
+    
+    #[tokio::test]
+    async fn test_operator_feedback_adjusts_neural_thresholds() {
+        let engine = Arc::new(NeuralNetEngine::new(Default::default()).await.unwrap());
+        engine.warmup().await.unwrap();
+        let initial = engine.anomaly_threshold().await.unwrap();
+        
+        let config = NeuroFireWallConfig {
+            feedback_window_size: 3,
+            threshold_adjustment_interval: 3,
+            ..Default::default()
+        };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.set_neural_engine(engine.clone());
+        
+        // Trafic légitime signalé à tort : le seuil ne monte qu'au troisième retour
+        firewall.record_feedback("event-0", false, false).unwrap();
+        firewall.record_feedback("event-1", false, false).unwrap();
+        assert_eq!(engine.anomaly_threshold().await.unwrap(), initial);
+        firewall.record_feedback("event-2", false, false).unwrap();
+        let raised = engine.anomaly_threshold().await.unwrap();
+        assert!(raised > initial);
+        
+        // La fenêtre ne contient ensuite que des faux négatifs : le seuil redescend
+        for index in 3..6 {
+            firewall.record_feedback(&format!("event-{}", index), false, true).unwrap();
+        }
+        assert!(engine.anomaly_threshold().await.unwrap() < raised);
+    }
+    
+    #[test]
+    fn test_chunked_dot_product_matches_scalar() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for length in [0, 1, 7, 8, 9, 16, 20, 256, 1000] {
+            let a: Vec<f32> = (0..length).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            let b: Vec<f32> = (0..length).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            
+            let scalar = dot_product_scalar(&a, &b);
+            let chunked = dot_product_chunked(&a, &b);
+            let tolerance = 1e-5 * (length.max(1) as f32).sqrt();
+            assert!((scalar - chunked).abs() <= tolerance, "longueur {}: {} != {}", length, scalar, chunked);
+        }
+        
+        // Vecteurs de longueurs différentes : seuls les éléments communs comptent
+        assert_eq!(dot_product_chunked(&[1.0; 10], &[2.0; 9]), 18.0);
+    }
+    
+    /// Nombre de threads des benchmarks concurrents (test de charge à 8 threads)
+    const BENCH_THREADS: usize = 8;
+    
+    /// Débit (opérations par seconde) de `BENCH_THREADS` threads exécutant chacun `per_thread`
+    /// fois `operation(thread, index)`, d'après la durée médiane de 5 mesures
+    fn concurrent_throughput<F>(name: &str, per_thread: usize, operation: F) -> f64
+    where
+        F: Fn(usize, usize) + Sync,
+    {
+        let result = Benchmark::new(name)
+            .warmup(1)
+            .iterations(5)
+            .with_metadata("threads", &BENCH_THREADS.to_string())
+            .with_metadata("opérations par thread", &per_thread.to_string())
+            .run(|| {
+                std::thread::scope(|scope| {
+                    for worker in 0..BENCH_THREADS {
+                        let operation = &operation;
+                        scope.spawn(move || (0..per_thread).for_each(|index| operation(worker, index)));
+                    }
+                });
+            })
+            .unwrap();
+        println!("{}", result);
+        (BENCH_THREADS * per_thread) as f64 / (result.p50_duration_us / 1e6)
+    }
+    
+    /// Mesure le débit d'analyse sous 8 threads et compare la comptabilisation des statistiques
+    /// par compteurs atomiques à l'ancien verrou unique (à lancer en mode release) :
+    /// `cargo test --release bench_stats_counters -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_stats_counters() {
+        let config = NeuroFireWallConfig {
+            packets_per_second: 1_000_000,
+            classification_cache_size: 0,
+            ..Default::default()
+        };
+        let mut firewall = NeuroFireWall::new(config);
+        firewall.initialize().unwrap();
+        force_score(&firewall, 0.1);
+        
+        let packets: Vec<Vec<NetworkPacket>> = (0..BENCH_THREADS)
+            .map(|worker| {
+                (0..1250)
+                    .map(|i| NetworkPacket {
+                        source_ip: format!("192.168.{}.{}", worker, i % 255),
+                        source_port: 10000 + i as u16,
+                        ..create_test_packet()
+                    })
+                    .collect()
+            })
+            .collect();
+        let analysis = concurrent_throughput("Analyse concurrente", 1250, |worker, index| {
+            firewall.analyze_packet(packets[worker][index].clone()).unwrap();
+        });
+        
+        // Comptabilisation d'un paquet autorisé : sous un verrou unique (avant) et par
+        // compteurs atomiques (après)
+        let locked = Mutex::new(NeuroFireWallStats::default());
+        let before = concurrent_throughput("Compteurs sous verrou", 125_000, |_, _| {
+            let mut stats = locked.lock().unwrap();
+            stats.total_packets_analyzed += 1;
+            stats.packets_allowed += 1;
+        });
+        let counters = StatsCounters::default();
+        let after = concurrent_throughput("Compteurs atomiques", 125_000, |_, _| {
+            counters.total_packets_analyzed.fetch_add(1, Ordering::Relaxed);
+            counters.packets_allowed.fetch_add(1, Ordering::Relaxed);
+        });
+        
+        println!(
+            "analyse : {:.0} paquets/s ; comptabilisation : {:.0}/s sous verrou, {:.0}/s atomique (x{:.2})",
+            analysis,
+            before,
+            after,
+            after / before
+        );
+    }
+    
+    /// Compare le débit de prédiction sous 8 threads du modèle partagé derrière un `Mutex`
+    /// (avant) et un `RwLock` (après) ; à lancer en mode release :
+    /// `cargo test --release bench_shared_model_lock -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_shared_model_lock() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.initialize().unwrap();
+        let features = firewall.extract_features(&create_test_packet()).unwrap().features;
+        let model = firewall.model.read().unwrap().clone();
+        
+        let locked = Mutex::new(model.clone());
+        let before = concurrent_throughput("Modèle sous Mutex", 1250, |_, _| {
+            std::hint::black_box(locked.lock().unwrap().predict(&features).unwrap());
+        });
+        let shared = RwLock::new(model);
+        let after = concurrent_throughput("Modèle sous RwLock", 1250, |_, _| {
+            std::hint::black_box(shared.read().unwrap().predict(&features).unwrap());
+        });
+        
+        println!("prédictions : {:.0}/s sous Mutex, {:.0}/s sous RwLock (x{:.2})", before, after, after / before);
+    }
+    
+    /// Mesure l'accélération du produit scalaire par blocs (à lancer en mode release) :
+    /// `cargo test --release bench_dot_product -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_dot_product() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let a: Vec<f32> = (0..256).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let b: Vec<f32> = (0..256).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        
+        // Chaque itération mesurée enchaîne 10 000 produits scalaires de 256 éléments
+        let measure = |name: &str, dot: fn(&[f32], &[f32]) -> f32| {
+            let result = Benchmark::new(name)
+                .warmup(10)
+                .iterations(100)
+                .with_metadata("éléments", "256")
+                .run(|| {
+                    for _ in 0..10_000 {
+                        std::hint::black_box(dot(std::hint::black_box(&a), std::hint::black_box(&b)));
+                    }
+                })
+                .unwrap();
+            println!("{}", result);
+            result.p50_duration_us
+        };
+        
+        let scalar = measure("Produit scalaire séquentiel", dot_product_scalar);
+        let chunked = measure("Produit scalaire par blocs", dot_product_chunked);
+        println!(
+            "produit scalaire (256 éléments) : séquentiel {:.1} ns, par blocs {:.1} ns, accélération x{:.2}",
+            scalar * 1e3 / 10_000.0,
+            chunked * 1e3 / 10_000.0,
+            scalar / chunked
+        );
+    }
+    
+    #[test]
+    fn test_identical_packets_served_from_classification_cache() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.initialize().unwrap();
+        force_score(&firewall, 0.1);
+        
+        let (first, _) = firewall.analyze_packet(NetworkPacket { id: "packet-c1".to_string(), ..create_test_packet() }).unwrap();
+        assert_eq!(firewall.get_stats().cache_hits, 0);
+        
+        let (second, event) = firewall.analyze_packet(NetworkPacket { id: "packet-c2".to_string(), ..create_test_packet() }).unwrap();
+        assert_eq!(second, first);
+        assert!(event.is_none());
+        assert_eq!(firewall.get_stats().cache_hits, 1);
+        
+        // Un paquet servi par le cache reste comptabilisé et disponible pour l'apprentissage
+        assert_eq!(firewall.score_histogram().iter().sum::<u64>(), 2);
+        assert_eq!(firewall.get_stats().total_packets_analyzed, 2);
+        firewall.feedback("packet-c2", false).unwrap();
+        
+        // Tout champ dont dépendent les caractéristiques distingue les paquets
+        let variants = [
+            NetworkPacket { payload_sample: b"GET /autre HTTP/1.1".to_vec(), ..create_test_packet() },
+            NetworkPacket { size: 1400, ..create_test_packet() },
+            NetworkPacket { traffic_type: TrafficType::Api, ..create_test_packet() },
+            NetworkPacket { metadata: HashMap::from([("user_agent".to_string(), "curl".to_string())]), ..create_test_packet() },
+        ];
+        for packet in variants {
+            firewall.analyze_packet(packet).unwrap();
+        }
+        assert_eq!(firewall.get_stats().cache_hits, 1);
+        
+        // Un cycle d'apprentissage invalide le cache
+        firewall.run_learning_cycle().unwrap();
+        firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(firewall.get_stats().cache_hits, 1);
+        firewall.analyze_packet(create_test_packet()).unwrap();
+        assert_eq!(firewall.get_stats().cache_hits, 2);
+        
+        // Les copies d'une menace sont toujours analysées
+        force_score(&firewall, 0.99);
+        for _ in 0..2 {
+            assert!(firewall.analyze_packet(create_test_packet()).unwrap().1.is_some());
+        }
+        assert_eq!(firewall.get_stats().cache_hits, 2);
+    }
+    
+    #[test]
+    fn test_classification_cache_evicts_least_recently_used() {
+        let classification = |anomaly_score: f32| CachedClassification { anomaly_score, decision: FirewallDecision::Allow };
+        let mut cache = ClassificationCache::default();
+        
+        cache.insert(1, classification(0.1), 2);
+        cache.insert(2, classification(0.2), 2);
+        // Des lectures répétées ne font pas croître l'ordre d'utilisation au-delà du double de la capacité
+        for _ in 0..100 {
+            assert!(cache.get(1, 2).is_some());
+        }
+        assert!(cache.recency.len() <= 4);
+        
+        // L'entrée 2, la moins récemment utilisée, est évincée
+        cache.insert(3, classification(0.3), 2);
+        assert!(cache.get(2, 2).is_none());
+        assert_eq!(cache.get(1, 2).unwrap().anomaly_score, 0.1);
+        assert_eq!(cache.get(3, 2).unwrap().anomaly_score, 0.3);
+        assert_eq!(cache.entries.len(), 2);
+    }
+    
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_async_analyses() {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig { classification_cache_size: 0, ..Default::default() });
+        firewall.initialize().unwrap();
+        firewall.block_source("198.51.100.23");
+        let firewall = Arc::new(firewall);
+        
+        let analyses: Vec<_> = (0..16)
+            .map(|i| {
+                let source_ip = if i % 2 == 0 { "198.51.100.23" } else { "192.168.1.100" };
+                let packet = NetworkPacket {
+                    id: format!("packet-async-{}", i),
+                    source_ip: source_ip.to_string(),
+                    ..create_test_packet()
+                };
+                tokio::spawn(Arc::clone(&firewall).analyze_packet_async(packet))
+            })
+            .collect();
+        
+        for (i, analysis) in analyses.into_iter().enumerate() {
+            let (decision, _) = analysis.await.expect("l'analyse ne doit pas paniquer").unwrap();
+            if i % 2 == 0 {
+                assert_eq!(decision, FirewallDecision::Block);
+            } else {
+                assert_ne!(decision, FirewallDecision::Block);
+            }
+        }
+        assert_eq!(firewall.get_stats().total_packets_analyzed, 16);
     }
 }
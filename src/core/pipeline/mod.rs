@@ -0,0 +1,511 @@
+//! # Pipeline ICARUS
+//! 
+//! `AnalysisPipeline` fait traverser à chaque paquet une suite d'étapes (`PipelineStage`)
+//! qui enrichissent un `PipelineContext` commun. Les étapes fournies sont :
+//! 
+//! - `FirewallStage` : analyse du paquet par le NeuroFireWall ;
+//! - `WarpShieldStage` : enregistrement des paquets visant un leurre actif ;
+//! - `AegisStage` : lorsque la décision l'exige (`Block`, `Quarantine` ou `Alert`),
+//!   synthèse d'un événement de menace transmis à AEGIS pour obtenir un plan de réponse ;
+//! - `DashboardStage` : diffusion en direct des détections et des menaces.
+//! 
+//! L'ordre des étapes est libre : elles peuvent être réordonnées, retirées (pour se passer
+//! de WarpShield par exemple) ou complétées par des étapes propres à l'utilisateur.
+//! 
+//! ```ignore
+//! let pipeline = AnalysisPipeline::new()
+//!     .with_stage(FirewallStage::new(firewall))
+//!     .with_stage(AegisStage::new(aegis));
+//! let context = pipeline.run(packet)?;
+//! ```
+//! 
+//! `Pipeline` assemble la chaîne habituelle NeuroFireWall -> AEGIS, avec un dashboard
+//! optionnel.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::aegis::{AegisOrchestrator, ResponsePlan, ThreatEvent, ThreatSeverity, ThreatType};
+use crate::clock::SharedClock;
+use crate::dashboard::{Dashboard, LiveEvent};
+use crate::error::IcarusError;
+use crate::id::SharedIdGenerator;
+use crate::neurofirewall::{DetectionEvent, FirewallDecision, NetworkPacket, NeuroFireWall};
+use crate::warpshield::{AttackEvent, VirtualEnvironmentState, WarpShield};
+
+/// État partagé par les étapes d'un pipeline pour un paquet
+#[derive(Debug, Clone)]
+pub struct PipelineContext {
+    /// Paquet analysé
+    pub packet: NetworkPacket,
+    /// Décision du pare-feu, si une étape de pare-feu a été exécutée
+    pub decision: Option<FirewallDecision>,
+    /// Événement de détection du pare-feu
+    pub detection_event: Option<DetectionEvent>,
+    /// Attaques enregistrées dans les environnements WarpShield
+    pub attack_events: Vec<AttackEvent>,
+    /// Plans de réponse générés par AEGIS
+    pub plans: Vec<ResponsePlan>,
+}
+
+impl PipelineContext {
+    /// Crée le contexte initial d'un paquet
+    pub fn new(packet: NetworkPacket) -> Self {
+        Self {
+            packet,
+            decision: None,
+            detection_event: None,
+            attack_events: Vec::new(),
+            plans: Vec::new(),
+        }
+    }
+}
+
+/// Étape d'un pipeline d'analyse
+pub trait PipelineStage: Send + Sync {
+    /// Nom de l'étape (utilisé pour la retirer du pipeline)
+    fn name(&self) -> &str;
+    
+    /// Traite le contexte et le transmet à l'étape suivante
+    fn process(&self, context: PipelineContext) -> Result<PipelineContext, IcarusError>;
+}
+
+/// Suite ordonnée d'étapes d'analyse
+#[derive(Default)]
+pub struct AnalysisPipeline {
+    stages: Vec<Box<dyn PipelineStage>>,
+}
+
+impl AnalysisPipeline {
+    /// Crée un pipeline sans étape
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Ajoute une étape en fin de pipeline
+    pub fn with_stage<S: PipelineStage + 'static>(mut self, stage: S) -> Self {
+        self.push_stage(Box::new(stage));
+        self
+    }
+    
+    /// Ajoute une étape en fin de pipeline
+    pub fn push_stage(&mut self, stage: Box<dyn PipelineStage>) {
+        self.stages.push(stage);
+    }
+    
+    /// Insère une étape à la position donnée (en fin de pipeline au-delà du nombre d'étapes)
+    pub fn insert_stage(&mut self, index: usize, stage: Box<dyn PipelineStage>) {
+        let index = index.min(self.stages.len());
+        self.stages.insert(index, stage);
+    }
+    
+    /// Retire la première étape portant ce nom et la renvoie
+    pub fn remove_stage(&mut self, name: &str) -> Option<Box<dyn PipelineStage>> {
+        let index = self.stages.iter().position(|stage| stage.name() == name)?;
+        Some(self.stages.remove(index))
+    }
+    
+    /// Noms des étapes, dans l'ordre d'exécution
+    pub fn stage_names(&self) -> Vec<&str> {
+        self.stages.iter().map(|stage| stage.name()).collect()
+    }
+    
+    /// Fait traverser toutes les étapes au paquet ; la première erreur interrompt le traitement
+    pub fn run(&self, packet: NetworkPacket) -> Result<PipelineContext, IcarusError> {
+        self.stages
+            .iter()
+            .try_fold(PipelineContext::new(packet), |context, stage| stage.process(context))
+    }
+}
+
+/// Analyse du paquet par le NeuroFireWall
+pub struct FirewallStage {
+    firewall: Arc<NeuroFireWall>,
+}
+
+impl FirewallStage {
+    /// Crée l'étape à partir d'un pare-feu initialisé
+    pub fn new(firewall: Arc<NeuroFireWall>) -> Self {
+        Self { firewall }
+    }
+}
+
+impl PipelineStage for FirewallStage {
+    fn name(&self) -> &str {
+        "neurofirewall"
+    }
+    
+    fn process(&self, mut context: PipelineContext) -> Result<PipelineContext, IcarusError> {
+        let (decision, detection_event) = self
+            .firewall
+            .analyze_packet(context.packet.clone())
+            .map_err(IcarusError::Firewall)?;
+        context.decision = Some(decision);
+        context.detection_event = detection_event;
+        Ok(context)
+    }
+}
+
+/// Enregistrement dans WarpShield des paquets visant un environnement virtuel actif
+///
+/// Tout trafic atteignant un leurre actif est une attaque : il est enregistré quelle que
+/// soit la décision du pare-feu.
+pub struct WarpShieldStage {
+    warpshield: Arc<WarpShield>,
+}
+
+impl WarpShieldStage {
+    /// Crée l'étape à partir d'une instance WarpShield initialisée
+    pub fn new(warpshield: Arc<WarpShield>) -> Self {
+        Self { warpshield }
+    }
+}
+
+impl PipelineStage for WarpShieldStage {
+    fn name(&self) -> &str {
+        "warpshield"
+    }
+    
+    fn process(&self, mut context: PipelineContext) -> Result<PipelineContext, IcarusError> {
+        let packet = &context.packet;
+        let Some(environment) = self.warpshield.get_environments().into_iter().find(|environment| {
+            environment.state == VirtualEnvironmentState::Active && environment.virtual_ip == packet.destination_ip
+        }) else {
+            return Ok(context);
+        };
+        
+        let mut data = packet_metadata(packet);
+        if let Some(decision) = &context.decision {
+            data.insert("decision".to_string(), format!("{:?}", decision));
+        }
+        let attack_type = format!("{:?}", packet.traffic_type).to_lowercase();
+        let attack_event = self
+            .warpshield
+            .record_attack_event(&environment.id, &attack_type, data)
+            .map_err(IcarusError::WarpShield)?;
+        context.attack_events.push(attack_event);
+        Ok(context)
+    }
+}
+
+/// Génération d'un plan de réponse AEGIS pour les décisions du pare-feu qui l'exigent
+///
+/// Les événements de menace sont horodatés et identifiés avec l'horloge et le générateur
+/// d'identifiants de l'orchestrateur.
+pub struct AegisStage {
+    aegis: Arc<AegisOrchestrator>,
+    clock: SharedClock,
+    ids: SharedIdGenerator,
+}
+
+impl AegisStage {
+    /// Score au-delà duquel un blocage est considéré critique
+    const CRITICAL_SCORE: f32 = 0.95;
+    
+    /// Crée l'étape à partir d'un orchestrateur initialisé
+    pub fn new(aegis: Arc<AegisOrchestrator>) -> Self {
+        Self {
+            clock: aegis.clock(),
+            ids: aegis.id_generator(),
+            aegis,
+        }
+    }
+    
+    /// Déduit la gravité et la confiance d'une décision du pare-feu
+    ///
+    /// Sans événement de détection (source déjà bloquée, alerte sous le seuil), la confiance
+    /// reflète uniquement la décision.
+    fn assess(decision: &FirewallDecision, event: Option<&DetectionEvent>) -> Option<(ThreatSeverity, f32)> {
+        let score = event.map(|event| event.anomaly_score);
+        
+        match decision {
+            FirewallDecision::Block => {
+                let score = score.unwrap_or(1.0);
+                let severity = if score >= Self::CRITICAL_SCORE {
+                    ThreatSeverity::Critical
+                } else {
+                    ThreatSeverity::High
+                };
+                Some((severity, score))
+            }
+            FirewallDecision::Quarantine => Some((ThreatSeverity::Medium, score.unwrap_or(0.85))),
+            FirewallDecision::Alert => Some((ThreatSeverity::Low, score.unwrap_or(0.7))),
+            FirewallDecision::Allow | FirewallDecision::Redirect | FirewallDecision::RateLimit => None,
+        }
+    }
+}
+
+impl PipelineStage for AegisStage {
+    fn name(&self) -> &str {
+        "aegis"
+    }
+    
+    fn process(&self, mut context: PipelineContext) -> Result<PipelineContext, IcarusError> {
+        let Some(decision) = &context.decision else {
+            return Ok(context);
+        };
+        let Some((severity, confidence)) = Self::assess(decision, context.detection_event.as_ref()) else {
+            return Ok(context);
+        };
+        
+        let packet = &context.packet;
+        let mut metadata = packet_metadata(packet);
+        metadata.insert("decision".to_string(), format!("{:?}", decision));
+        if let Some(event) = &context.detection_event {
+            metadata.insert("detection_event".to_string(), event.id.clone());
+        }
+        
+        let threat_event = ThreatEvent {
+            id: self.ids.next_id("threat"),
+            threat_type: ThreatType::UnknownZeroDay,
+            severity,
+            confidence,
+            source: packet.source_ip.clone(),
+            target: packet.destination_ip.clone(),
+            timestamp: self.clock.now(),
+            metadata,
+        };
+        
+        let plan = self
+            .aegis
+            .process_threat_event(threat_event)
+            .map_err(IcarusError::Aegis)?;
+        context.plans.push(plan);
+        Ok(context)
+    }
+}
+
+/// Diffusion sur le flux en direct du dashboard des détections, attaques et menaces du contexte
+///
+/// Les menaces dérivées des attaques sont identifiées par le générateur du dashboard.
+pub struct DashboardStage {
+    dashboard: Arc<Dashboard>,
+    ids: SharedIdGenerator,
+}
+
+impl DashboardStage {
+    /// Crée l'étape à partir d'un dashboard démarré
+    pub fn new(dashboard: Arc<Dashboard>) -> Self {
+        Self {
+            ids: dashboard.id_generator(),
+            dashboard,
+        }
+    }
+}
+
+impl PipelineStage for DashboardStage {
+    fn name(&self) -> &str {
+        "dashboard"
+    }
+    
+    fn process(&self, context: PipelineContext) -> Result<PipelineContext, IcarusError> {
+        if let Some(event) = &context.detection_event {
+            self.dashboard.publish_event(LiveEvent::Detection(event.clone()));
+        }
+        for attack in &context.attack_events {
+            self.dashboard.publish_event(LiveEvent::Threat(attack.to_threat_event(self.ids.as_ref())));
+        }
+        for plan in &context.plans {
+            self.dashboard.publish_event(LiveEvent::Threat(plan.threat_event.clone()));
+        }
+        Ok(context)
+    }
+}
+
+/// Métadonnées d'un paquet reportées dans les événements de menace et d'attaque
+fn packet_metadata(packet: &NetworkPacket) -> HashMap<String, String> {
+    let mut metadata = HashMap::new();
+    metadata.insert("packet_id".to_string(), packet.id.clone());
+    metadata.insert("traffic_type".to_string(), format!("{:?}", packet.traffic_type));
+    metadata.insert("destination_port".to_string(), packet.destination_port.to_string());
+    metadata
+}
+
+/// Chaîne de traitement reliant le NeuroFireWall à AEGIS
+pub struct Pipeline {
+    /// Étapes pare-feu, AEGIS et, le cas échéant, dashboard
+    stages: AnalysisPipeline,
+}
+
+impl Pipeline {
+    /// Crée un pipeline à partir de modules déjà initialisés
+    pub fn new(firewall: Arc<NeuroFireWall>, aegis: Arc<AegisOrchestrator>) -> Self {
+        let stages = AnalysisPipeline::new()
+            .with_stage(FirewallStage::new(firewall))
+            .with_stage(AegisStage::new(aegis));
+        Self { stages }
+    }
+    
+    /// Diffuse les événements traités sur le flux en direct du dashboard
+    pub fn with_dashboard(mut self, dashboard: Arc<Dashboard>) -> Self {
+        self.stages.push_stage(Box::new(DashboardStage::new(dashboard)));
+        self
+    }
+    
+    /// Analyse un paquet et renvoie le plan de réponse éventuel
+    ///
+    /// Renvoie `None` lorsque la décision du pare-feu ne nécessite pas l'intervention d'AEGIS.
+    pub fn process(&self, packet: NetworkPacket) -> Result<Option<ResponsePlan>, IcarusError> {
+        Ok(self.stages.run(packet)?.plans.pop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::time::{Duration, SystemTime};
+    
+    use crate::aegis::{AegisConfig, ResponseAction};
+    use crate::clock::{Clock, MockClock};
+    use crate::dashboard::DashboardConfig;
+    use crate::id::SequentialIdGenerator;
+    use crate::neurofirewall::{NeuroFireWallConfig, TrafficType};
+    use crate::warpshield::{VirtualEnvironmentType, WarpShieldConfig};
+    
+    fn create_pipeline() -> (Pipeline, Arc<NeuroFireWall>) {
+        let mut firewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        firewall.initialize().unwrap();
+        let firewall = Arc::new(firewall);
+        
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        
+        (Pipeline::new(Arc::clone(&firewall), Arc::new(aegis)), firewall)
+    }
+    
+    fn create_test_packet(source_ip: &str) -> NetworkPacket {
+        NetworkPacket {
+            id: "packet-pipeline".to_string(),
+            source_ip: source_ip.to_string(),
+            destination_ip: "192.168.1.1".to_string(),
+            source_port: 12345,
+            destination_port: 80,
+            protocol: "TCP".to_string(),
+            size: 1024,
+            timestamp: SystemTime::now(),
+            traffic_type: TrafficType::Web,
+            payload_sample: vec![0; 64],
+            metadata: HashMap::new(),
+        }
+    }
+    
+    #[test]
+    fn test_benign_packet_produces_no_plan() {
+        let (pipeline, _) = create_pipeline();
+        
+        let plan = pipeline.process(create_test_packet("192.168.1.100")).unwrap();
+        assert!(plan.is_none());
+    }
+    
+    #[test]
+    fn test_blocked_packet_produces_plan() {
+        let (pipeline, firewall) = create_pipeline();
+        firewall.block_source("198.51.100.23");
+        
+        let plan = pipeline
+            .process(create_test_packet("198.51.100.23"))
+            .unwrap()
+            .expect("Un paquet bloqué doit produire un plan de réponse");
+        
+        assert_eq!(plan.threat_event.source, "198.51.100.23");
+        assert_eq!(plan.threat_event.severity, ThreatSeverity::Critical);
+        assert_eq!(plan.threat_event.metadata.get("decision").unwrap(), "Block");
+        assert!(plan.actions.contains(&ResponseAction::IsolateSystem));
+    }
+    
+    #[test]
+    fn test_threat_events_use_aegis_clock_and_ids() {
+        let (_, firewall) = create_pipeline();
+        let clock = Arc::new(MockClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.set_clock(clock.clone());
+        aegis.set_id_generator(Arc::new(SequentialIdGenerator::new()));
+        aegis.initialize().unwrap();
+        let pipeline = Pipeline::new(Arc::clone(&firewall), Arc::new(aegis));
+        
+        firewall.block_source("198.51.100.23");
+        let plan = pipeline.process(create_test_packet("198.51.100.23")).unwrap().unwrap();
+        assert_eq!(plan.threat_event.id, "threat-0");
+        assert_eq!(plan.threat_event.timestamp, clock.now());
+    }
+    
+    #[test]
+    fn test_processed_threats_are_published() {
+        let (pipeline, firewall) = create_pipeline();
+        let mut dashboard = Dashboard::new(DashboardConfig::default());
+        dashboard.start().unwrap();
+        let dashboard = Arc::new(dashboard);
+        let pipeline = pipeline.with_dashboard(Arc::clone(&dashboard));
+        let mut events = dashboard.subscribe_events().unwrap();
+        
+        firewall.block_source("198.51.100.23");
+        pipeline.process(create_test_packet("198.51.100.23")).unwrap();
+        
+        match events.try_recv().unwrap() {
+            LiveEvent::Threat(threat) => assert_eq!(threat.source, "198.51.100.23"),
+            other => panic!("Événement inattendu: {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_firewall_errors_are_reported() {
+        let firewall = Arc::new(NeuroFireWall::new(NeuroFireWallConfig::default()));
+        let aegis = Arc::new(AegisOrchestrator::new(AegisConfig::default()));
+        let pipeline = Pipeline::new(firewall, aegis);
+        
+        let error = pipeline.process(create_test_packet("192.168.1.100")).unwrap_err();
+        assert!(matches!(error, IcarusError::Firewall(_)));
+    }
+    
+    #[test]
+    fn test_pipeline_without_warpshield_still_plans_response() {
+        let (_, firewall) = create_pipeline();
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        
+        let pipeline = AnalysisPipeline::new()
+            .with_stage(FirewallStage::new(Arc::clone(&firewall)))
+            .with_stage(AegisStage::new(Arc::new(aegis)));
+        assert_eq!(pipeline.stage_names(), vec!["neurofirewall", "aegis"]);
+        
+        firewall.block_source("198.51.100.23");
+        let context = pipeline.run(create_test_packet("198.51.100.23")).unwrap();
+        
+        assert_eq!(context.decision, Some(FirewallDecision::Block));
+        assert!(context.attack_events.is_empty());
+        assert_eq!(context.plans.len(), 1);
+        assert_eq!(context.plans[0].threat_event.source, "198.51.100.23");
+    }
+    
+    #[test]
+    fn test_stages_can_be_reordered_and_removed() {
+        let (_, firewall) = create_pipeline();
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        aegis.initialize().unwrap();
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.initialize().unwrap();
+        let warpshield = Arc::new(warpshield);
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&env.id, "198.51.100.23").unwrap();
+        
+        let mut pipeline = AnalysisPipeline::new()
+            .with_stage(WarpShieldStage::new(Arc::clone(&warpshield)))
+            .with_stage(AegisStage::new(Arc::new(aegis)));
+        pipeline.insert_stage(0, Box::new(FirewallStage::new(firewall)));
+        assert_eq!(pipeline.stage_names(), vec!["neurofirewall", "warpshield", "aegis"]);
+        
+        // Le trafic visant le leurre actif est enregistré comme attaque
+        let mut packet = create_test_packet("198.51.100.23");
+        packet.destination_ip = env.virtual_ip.clone();
+        let context = pipeline.run(packet.clone()).unwrap();
+        assert_eq!(context.attack_events.len(), 1);
+        assert_eq!(context.attack_events[0].environment_id, env.id);
+        assert_eq!(context.attack_events[0].attack_type, "web");
+        
+        assert_eq!(pipeline.remove_stage("warpshield").unwrap().name(), "warpshield");
+        assert!(pipeline.remove_stage("warpshield").is_none());
+        assert_eq!(pipeline.stage_names(), vec!["neurofirewall", "aegis"]);
+        assert!(pipeline.run(packet).unwrap().attack_events.is_empty());
+    }
+}
@@ -8,21 +8,60 @@
 // Assurez-vous que les noms des modules correspondent à vos fichiers.
 #[path = "../aegis/mod.rs"]
 mod aegis;
+#[path = "../bench/mod.rs"]
+mod bench;
+#[path = "../bus/mod.rs"]
+mod bus;
+#[path = "../clock/mod.rs"]
+mod clock;
+#[path = "../config/mod.rs"]
+mod config;
 #[path = "../crypto/mod.rs"]
 mod crypto;
 #[path = "../dashboard/mod.rs"]
 mod dashboard;
+#[path = "../error/mod.rs"]
+mod error;
+#[path = "../id/mod.rs"]
+mod id;
+#[path = "../logging/mod.rs"]
+mod logging;
 #[path = "../neural_net/mod.rs"]
 mod neural_net;
 #[path = "../neurofirewall/mod.rs"]
 mod neurofirewall;
+#[path = "../pipeline/mod.rs"]
+mod pipeline;
+#[path = "../system/mod.rs"]
+mod system;
 #[path = "../warpshield/mod.rs"]
 mod warpshield;
 
-use std::fs;
-use std::io::{Read, Write};
-use std::net::{TcpListener, TcpStream};
-use std::thread;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use rocket::data::{IoHandler, IoStream};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
+use rocket::serde::json::{self, Json};
+use rocket::tokio::sync::broadcast::{self, error::RecvError};
+use rocket::{Build, Rocket, State};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use aegis::{AegisState, ResponsePlan, ThreatEvent};
+use config::{IcarusConfig, CONFIG_PATH_ENV, DEFAULT_CONFIG_PATH};
+use dashboard::LiveEvent;
+use error::IcarusError;
+use neurofirewall::{DetectionEvent, FirewallDecision, NetworkPacket, NeuroFireWallState};
+use pipeline::AnalysisPipeline;
+use system::{HealthReport, HealthStatus, IcarusSystem};
+use warpshield::AttackEvent;
 
 // Route de base qui répond par "Hello, world!"
 #[get("/")]
@@ -30,58 +69,407 @@ fn index() -> &'static str {
     "Hello from Icare Backend!"
 }
 
+// Soumission d'un événement de menace externe à AEGIS.
+// Renvoie 400 si le corps est invalide et 503 si AEGIS n'est pas opérationnel.
+#[post("/api/aegis/threat", format = "json", data = "<event>")]
+fn submit_threat(
+    event: Result<Json<ThreatEvent>, json::Error<'_>>,
+    system: &State<IcarusSystem>,
+) -> Result<Json<ResponsePlan>, Status> {
+    let event = event.map_err(|_| Status::BadRequest)?;
+    let aegis = system.aegis();
+
+    if aegis.get_state() != AegisState::Operational {
+        return Err(Status::ServiceUnavailable);
+    }
+
+    let plan = aegis
+        .process_threat_event(event.into_inner())
+        .map_err(|_| Status::InternalServerError)?;
+    system.dashboard().publish_event(LiveEvent::Threat(plan.threat_event.clone()));
+
+    Ok(Json(plan))
+}
+
+// Résultat du traitement d'un paquet par le pipeline d'analyse.
+#[derive(Debug, Serialize, Deserialize)]
+struct PacketAnalysis {
+    decision: Option<FirewallDecision>,
+    detection_event: Option<DetectionEvent>,
+    attack_events: Vec<AttackEvent>,
+    plans: Vec<ResponsePlan>,
+}
+
+// Statut HTTP d'un échec du pipeline : 503 si le module en cause n'est pas démarré, 500 sinon.
+fn pipeline_error_status(error: &IcarusError, system: &IcarusSystem) -> Status {
+    let available = match error {
+        IcarusError::Firewall(_) => matches!(
+            system.neurofirewall().get_state(),
+            NeuroFireWallState::Operational | NeuroFireWallState::Degraded
+        ),
+        IcarusError::Aegis(_) => system.aegis().get_state() == AegisState::Operational,
+        _ => true,
+    };
+
+    if available {
+        Status::InternalServerError
+    } else {
+        Status::ServiceUnavailable
+    }
+}
+
+// Traitement d'un paquet réseau par le pipeline du système (NeuroFireWall, WarpShield, AEGIS,
+// dashboard), hors de l'exécuteur asynchrone.
+// Renvoie 400 si le corps est invalide et 503 si un module requis n'est pas opérationnel.
+#[post("/api/neurofirewall/packet", format = "json", data = "<packet>")]
+async fn analyze_packet(
+    packet: Result<Json<NetworkPacket>, json::Error<'_>>,
+    system: &State<IcarusSystem>,
+    pipeline: &State<Arc<AnalysisPipeline>>,
+) -> Result<Json<PacketAnalysis>, Status> {
+    let packet = packet.map_err(|_| Status::BadRequest)?.into_inner();
+    let pipeline = Arc::clone(pipeline);
+
+    let context = rocket::tokio::task::spawn_blocking(move || pipeline.run(packet))
+        .await
+        .map_err(|_| Status::InternalServerError)?
+        .map_err(|e| pipeline_error_status(&e, system))?;
+
+    Ok(Json(PacketAnalysis {
+        decision: context.decision,
+        detection_event: context.detection_event,
+        attack_events: context.attack_events,
+        plans: context.plans,
+    }))
+}
+
+// Demande de passage au protocole WebSocket (RFC 6455).
+// Rejette avec 400 les requêtes qui ne demandent pas de mise à niveau.
+struct WebSocketUpgrade {
+    key: String,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebSocketUpgrade {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = request.headers();
+        let upgrade = headers.get_one("Upgrade").is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+        let version = headers.get_one("Sec-WebSocket-Version") == Some("13");
+
+        match headers.get_one("Sec-WebSocket-Key") {
+            Some(key) if upgrade && version => Outcome::Success(Self { key: key.to_string() }),
+            _ => Outcome::Error((Status::BadRequest, ())),
+        }
+    }
+}
+
+// Connexion WebSocket relayant les événements en direct du dashboard à un client.
+struct EventSocket {
+    key: String,
+    events: broadcast::Receiver<LiveEvent>,
+}
+
+impl<'r> Responder<'r, 'static> for EventSocket {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .raw_header("Sec-WebSocket-Accept", derive_accept_key(self.key.as_bytes()))
+            .upgrade("websocket", self)
+            .ok()
+    }
+}
+
+#[rocket::async_trait]
+impl IoHandler for EventSocket {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> std::io::Result<()> {
+        let mut events = Pin::into_inner(self).events;
+        let (mut sink, mut stream) = WebSocketStream::from_raw_socket(io, Role::Server, None).await.split();
+
+        // S'arrête à la déconnexion du client ; un client trop lent perd les événements les plus anciens
+        loop {
+            rocket::tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let Ok(text) = json::to_string(&event) else { continue };
+                        if sink.send(Message::Text(text)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+                message = stream.next() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Flux WebSocket des événements traités (détections du pare-feu, menaces transmises à AEGIS),
+// un message JSON par événement.
+// Renvoie 503 si les notifications en temps réel sont désactivées ou si le nombre maximal
+// d'abonnés est atteint.
+#[get("/ws/events")]
+fn events_socket(upgrade: WebSocketUpgrade, system: &State<IcarusSystem>) -> Result<EventSocket, Status> {
+    let events = system
+        .dashboard()
+        .subscribe_events()
+        .map_err(|_| Status::ServiceUnavailable)?;
+
+    Ok(EventSocket { key: upgrade.key, events })
+}
+
+// État de santé agrégé de tous les modules.
+// Renvoie 200 si le système est sain et 503 sinon, avec le rapport détaillé dans les deux cas.
+#[get("/health")]
+fn health(system: &State<IcarusSystem>) -> (Status, Json<HealthReport>) {
+    let report = system.health();
+    let status = if report.status == HealthStatus::Healthy {
+        Status::Ok
+    } else {
+        Status::ServiceUnavailable
+    };
+
+    (status, Json(report))
+}
+
+// Construit l'instance Rocket autour d'un système ICARUS donné et de son pipeline d'analyse.
+fn build_rocket(system: IcarusSystem) -> Rocket<Build> {
+    let pipeline = Arc::new(system.analysis_pipeline());
+
+    rocket::build()
+        .manage(system)
+        .manage(pipeline)
+        .mount("/", routes![index, submit_threat, analyze_packet, health, events_socket])
+        // Vous pouvez ajouter ici d'autres routes et configurations.
+}
+
 // Fonction principale qui configure et lance le serveur Rocket.
+// La configuration est lue depuis le fichier désigné par ICARUS_CONFIG (icarus.toml par défaut) ;
+// les valeurs par défaut ne s'appliquent que si ce fichier n'existe pas.
 #[launch]
 fn rocket() -> _ {
-    rocket::build()
-        .mount("/", routes![index])
-        // Vous pouvez ajouter ici d'autres routes et configurations.
+    let path = std::env::var(CONFIG_PATH_ENV).unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+    let config = IcarusConfig::load_or_default(&path).unwrap_or_else(|e| {
+        eprintln!("Configuration invalide ({}): {}", path, e);
+        std::process::exit(1);
+    });
+    logging::init_logging(config.aegis.log_level);
+
+    let system = IcarusSystem::from_config(&config).expect("Échec de l'initialisation d'ICARUS");
+
+    build_rocket(system)
 }
 
-/// Gère une seule connexion client.
-fn handle_client(mut stream: TcpStream) {
-    let mut buffer = [0; 1024];
-    match stream.read(&mut buffer) {
-        Ok(size) => {
-            // Ne traite que s'il y a quelque chose à lire
-            if size > 0 {
-                let request = String::from_utf8_lossy(&buffer[..]);
-                println!("Requête reçue: {}", request);
-
-                let response = "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\n\r\nHello from Icare Backend!\r\n";
-                
-                if let Err(e) = stream.write(response.as_bytes()) {
-                    eprintln!("Échec de l'écriture dans le flux: {}", e);
-                }
-            }
-        },
-        Err(e) => {
-            eprintln!("Échec de la lecture du flux: {}", e);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocket::http::ContentType;
+    use rocket::local::blocking::Client;
+
+    use aegis::{AegisConfig, AegisOrchestrator};
+    use crypto::quantum_vault::{QuantumVault, QuantumVaultConfig};
+    use dashboard::{Dashboard, DashboardConfig};
+    use neural_net::{NeuralNet, NeuralNetConfig};
+    use neurofirewall::{NeuroFireWall, NeuroFireWallConfig};
+    use warpshield::{WarpShield, WarpShieldConfig};
+
+    // Construit un système dont seuls les modules indiqués sont démarrés.
+    fn create_system(aegis_ready: bool, dashboard_ready: bool) -> IcarusSystem {
+        let mut aegis = AegisOrchestrator::new(AegisConfig::default());
+        if aegis_ready {
+            aegis.initialize().unwrap();
+        }
+        let mut neurofirewall = NeuroFireWall::new(NeuroFireWallConfig::default());
+        neurofirewall.initialize().unwrap();
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.initialize().unwrap();
+        let mut dashboard = Dashboard::new(DashboardConfig::default());
+        if dashboard_ready {
+            dashboard.start().unwrap();
         }
+
+        IcarusSystem::new(
+            aegis,
+            neurofirewall,
+            warpshield,
+            dashboard,
+            QuantumVault::new(QuantumVaultConfig::default()),
+            NeuralNet::new(NeuralNetConfig::default()),
+        )
     }
-    if let Err(e) = stream.flush() {
-        eprintln!("Échec du vidage du flux: {}", e);
+
+    const PORT_SCAN_EVENT: &str = r#"{
+        "id": "threat-api-1",
+        "threat_type": "PortScan",
+        "severity": "Medium",
+        "confidence": 0.85,
+        "source": "192.168.1.100",
+        "target": "192.168.1.1"
+    }"#;
+
+    #[test]
+    fn test_index_route() {
+        let client = Client::tracked(rocket()).expect("Instance Rocket invalide");
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "Hello from Icare Backend!");
     }
-}
+    
+    #[test]
+    fn test_submit_threat_returns_plan() {
+        let client = Client::tracked(rocket()).expect("Instance Rocket invalide");
 
-/// Point d'entrée principal de l'application backend.
-fn main() {
-    let listener = TcpListener::bind("0.0.0.0:8080").expect("Échec de la liaison au port 8080");
-    println!("Serveur backend démarré sur 0.0.0.0:8080");
-
-    // Accepte les connexions entrantes
-    for stream in listener.incoming() {
-        match stream {
-            Ok(stream) => {
-                println!("Nouvelle connexion: {}", stream.peer_addr().unwrap());
-                // Crée un nouveau thread pour chaque connexion
-                thread::spawn(move || {
-                    handle_client(stream);
-                });
-            }
-            Err(e) => {
-                eprintln!("Erreur: {}", e);
+        let response = client
+            .post("/api/aegis/threat")
+            .header(ContentType::JSON)
+            .body(PORT_SCAN_EVENT)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let plan: ResponsePlan = response.into_json().expect("Plan de réponse invalide");
+        assert_eq!(plan.threat_event.id, "threat-api-1");
+        assert!(plan.actions.contains(&aegis::ResponseAction::BlockIp));
+    }
+    
+    #[test]
+    fn test_submit_malformed_threat() {
+        let client = Client::tracked(rocket()).expect("Instance Rocket invalide");
+
+        let response = client
+            .post("/api/aegis/threat")
+            .header(ContentType::JSON)
+            .body(r#"{"id": "threat-api-2", "threat_type": "Inconnu"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+    
+    #[test]
+    fn test_submit_threat_when_aegis_not_operational() {
+        let client = Client::tracked(build_rocket(create_system(false, true))).expect("Instance Rocket invalide");
+
+        let response = client
+            .post("/api/aegis/threat")
+            .header(ContentType::JSON)
+            .body(PORT_SCAN_EVENT)
+            .dispatch();
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+    }
+    
+    const BLOCKED_PACKET: &str = r#"{
+        "id": "packet-api-1",
+        "source_ip": "198.51.100.23",
+        "destination_ip": "192.168.1.1",
+        "source_port": 12345,
+        "destination_port": 80,
+        "protocol": "TCP",
+        "size": 512,
+        "timestamp": { "secs_since_epoch": 1700000000, "nanos_since_epoch": 0 },
+        "traffic_type": "Web"
+    }"#;
+
+    #[test]
+    fn test_analyze_packet_route() {
+        let system = create_system(true, true);
+        system.neurofirewall().block_source("198.51.100.23");
+        let client = Client::tracked(build_rocket(system)).expect("Instance Rocket invalide");
+
+        let response = client
+            .post("/api/neurofirewall/packet")
+            .header(ContentType::JSON)
+            .body(BLOCKED_PACKET)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let analysis: PacketAnalysis = response.into_json().expect("Analyse invalide");
+        assert_eq!(analysis.decision, Some(FirewallDecision::Block));
+        assert_eq!(analysis.plans.len(), 1);
+
+        let response = client
+            .post("/api/neurofirewall/packet")
+            .header(ContentType::JSON)
+            .body(r#"{"id": "packet-api-2"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_health_endpoint() {
+        let client = Client::tracked(rocket()).expect("Instance Rocket invalide");
+        let response = client.get("/health").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let report: HealthReport = response.into_json().expect("Rapport de santé invalide");
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        // Dashboard non démarré : système dégradé
+        let client = Client::tracked(build_rocket(create_system(true, false))).expect("Instance Rocket invalide");
+        let response = client.get("/health").dispatch();
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        let report: HealthReport = response.into_json().expect("Rapport de santé invalide");
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.dashboard, dashboard::DashboardState::Initializing);
+    }
+
+    #[test]
+    fn test_events_socket_requires_upgrade() {
+        let client = Client::tracked(rocket()).expect("Instance Rocket invalide");
+
+        let response = client.get("/ws/events").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[rocket::async_test]
+    async fn test_events_socket_streams_submitted_threats() {
+        use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use rocket::tokio::net::TcpStream;
+        use std::time::Duration;
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let figment = rocket::Config::figment()
+            .merge(("address", "127.0.0.1"))
+            .merge(("port", port))
+            .merge(("log_level", "off"));
+        let server = build_rocket(create_system(true, true)).configure(figment).ignite().await.unwrap();
+        let shutdown = server.shutdown();
+        rocket::tokio::spawn(server.launch());
+
+        // Attendre que le serveur accepte les connexions
+        let url = format!("ws://127.0.0.1:{}/ws/events", port);
+        let mut socket = None;
+        for _ in 0..100 {
+            if let Ok((connected, _)) = tokio_tungstenite::connect_async(url.as_str()).await {
+                socket = Some(connected);
+                break;
             }
+            rocket::tokio::time::sleep(Duration::from_millis(20)).await;
         }
+        let mut socket = socket.expect("Connexion WebSocket impossible");
+
+        let mut http = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let request = format!(
+            "POST /api/aegis/threat HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            PORT_SCAN_EVENT.len(),
+            PORT_SCAN_EVENT
+        );
+        http.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        http.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "réponse inattendue: {}", response);
+
+        let message = rocket::tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("Aucun événement reçu")
+            .unwrap()
+            .unwrap();
+        let event: LiveEvent = json::from_str(message.to_text().unwrap()).unwrap();
+        assert!(matches!(event, LiveEvent::Threat(threat) if threat.id == "threat-api-1"));
+
+        socket.close(None).await.unwrap();
+        shutdown.notify();
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,463 @@
+//! # Système ICARUS
+//! 
+//! `IcarusSystem` regroupe les modules opérationnels (AEGIS, NeuroFireWall, WarpShield,
+//! dashboard) et agrège leurs états en un rapport de santé unique :
+//! 
+//! - `Healthy` : tous les modules sont opérationnels ;
+//! - `Down` : AEGIS ou le NeuroFireWall n'est pas démarré (initialisation ou arrêt),
+//!   le système ne peut plus traiter de menaces ;
+//! - `Degraded` : dans tous les autres cas (module en erreur, en maintenance, dégradé,
+//!   ou WarpShield/dashboard arrêté).
+//! 
+//! `IcarusSystemBuilder` est le point d'entrée pour construire un système complet : chaque
+//! configuration de module peut être remplacée, les autres gardent leurs valeurs par défaut.
+//! 
+//! ```ignore
+//! let system = IcarusSystemBuilder::new()
+//!     .with_firewall_config(NeuroFireWallConfig { anomaly_threshold: 0.9, ..Default::default() })
+//!     .build()?;
+//! ```
+//! 
+//! Les modules sont partagés (`Arc`) : `analysis_pipeline` en tire la chaîne de traitement
+//! complète NeuroFireWall -> WarpShield -> AEGIS -> dashboard.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::aegis::{AegisConfig, AegisOrchestrator, AegisState};
+use crate::clock::{system_clock, SharedClock};
+use crate::config::IcarusConfig;
+use crate::crypto::quantum_vault::{QuantumVault, QuantumVaultConfig};
+use crate::dashboard::{Dashboard, DashboardConfig, DashboardState};
+use crate::error::IcarusError;
+use crate::id::{random_ids, SharedIdGenerator};
+use crate::neural_net::{NeuralNet, NeuralNetConfig};
+use crate::neurofirewall::{NeuroFireWall, NeuroFireWallConfig, NeuroFireWallState};
+use crate::pipeline::{AegisStage, AnalysisPipeline, DashboardStage, FirewallStage, WarpShieldStage};
+use crate::warpshield::{WarpShield, WarpShieldConfig, WarpShieldState};
+
+#[cfg(test)]
+use crate::clock::MockClock;
+#[cfg(test)]
+use std::time::{Duration, SystemTime};
+
+/// Graine des générateurs aléatoires d'un système de test
+#[cfg(test)]
+const TEST_RNG_SEED: u64 = 42;
+/// Instant initial de l'horloge simulée d'un système de test
+#[cfg(test)]
+const TEST_CLOCK_START: Duration = Duration::from_secs(1_700_000_000);
+
+/// État de santé global
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// Tous les modules sont opérationnels
+    Healthy,
+    /// Le système fonctionne avec des capacités réduites
+    Degraded,
+    /// Le système ne peut plus traiter de menaces
+    Down,
+}
+
+/// Rapport de santé de l'ensemble des modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    /// État global
+    pub status: HealthStatus,
+    /// État d'AEGIS
+    pub aegis: AegisState,
+    /// État du NeuroFireWall
+    pub neurofirewall: NeuroFireWallState,
+    /// État de WarpShield
+    pub warpshield: WarpShieldState,
+    /// État du dashboard
+    pub dashboard: DashboardState,
+}
+
+impl HealthReport {
+    /// Construit un rapport et en déduit l'état global
+    pub fn new(
+        aegis: AegisState,
+        neurofirewall: NeuroFireWallState,
+        warpshield: WarpShieldState,
+        dashboard: DashboardState,
+    ) -> Self {
+        let core_down = matches!(aegis, AegisState::Initializing | AegisState::Shutdown)
+            || matches!(neurofirewall, NeuroFireWallState::Initializing | NeuroFireWallState::Shutdown);
+        let all_operational = aegis == AegisState::Operational
+            && matches!(neurofirewall, NeuroFireWallState::Operational | NeuroFireWallState::Learning)
+            && warpshield == WarpShieldState::Operational
+            && dashboard == DashboardState::Running;
+        
+        let status = if core_down {
+            HealthStatus::Down
+        } else if all_operational {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        };
+        
+        Self {
+            status,
+            aegis,
+            neurofirewall,
+            warpshield,
+            dashboard,
+        }
+    }
+}
+
+/// Ensemble des modules ICARUS en cours d'exécution
+pub struct IcarusSystem {
+    aegis: Arc<AegisOrchestrator>,
+    neurofirewall: Arc<NeuroFireWall>,
+    warpshield: Arc<WarpShield>,
+    dashboard: Arc<Dashboard>,
+    quantum_vault: QuantumVault,
+    neural_net: NeuralNet,
+}
+
+impl IcarusSystem {
+    /// Regroupe des modules déjà construits (et éventuellement initialisés)
+    pub fn new(
+        aegis: AegisOrchestrator,
+        neurofirewall: NeuroFireWall,
+        warpshield: WarpShield,
+        dashboard: Dashboard,
+        quantum_vault: QuantumVault,
+        neural_net: NeuralNet,
+    ) -> Self {
+        Self {
+            aegis: Arc::new(aegis),
+            neurofirewall: Arc::new(neurofirewall),
+            warpshield: Arc::new(warpshield),
+            dashboard: Arc::new(dashboard),
+            quantum_vault,
+            neural_net,
+        }
+    }
+    
+    /// Construit et démarre tous les modules à partir de la configuration
+    pub fn from_config(config: &IcarusConfig) -> Result<Self, IcarusError> {
+        IcarusSystemBuilder::new().with_config(config.clone()).build()
+    }
+    
+    /// Orchestrateur AEGIS
+    pub fn aegis(&self) -> &AegisOrchestrator {
+        &self.aegis
+    }
+    
+    /// Orchestrateur AEGIS partagé, pour les étapes de pipeline
+    pub fn aegis_handle(&self) -> Arc<AegisOrchestrator> {
+        Arc::clone(&self.aegis)
+    }
+    
+    /// NeuroFireWall
+    pub fn neurofirewall(&self) -> &NeuroFireWall {
+        &self.neurofirewall
+    }
+    
+    /// NeuroFireWall partagé, pour les analyses asynchrones (`analyze_packet_async`)
+    pub fn neurofirewall_handle(&self) -> Arc<NeuroFireWall> {
+        Arc::clone(&self.neurofirewall)
+    }
+    
+    /// WarpShield
+    pub fn warpshield(&self) -> &WarpShield {
+        &self.warpshield
+    }
+    
+    /// WarpShield partagé, pour les étapes de pipeline
+    pub fn warpshield_handle(&self) -> Arc<WarpShield> {
+        Arc::clone(&self.warpshield)
+    }
+    
+    /// Dashboard (flux d'événements en direct)
+    pub fn dashboard(&self) -> &Dashboard {
+        &self.dashboard
+    }
+    
+    /// Dashboard partagé, pour les étapes de pipeline
+    pub fn dashboard_handle(&self) -> Arc<Dashboard> {
+        Arc::clone(&self.dashboard)
+    }
+    
+    /// Coffre cryptographique QuantumVault
+    pub fn quantum_vault(&self) -> &QuantumVault {
+        &self.quantum_vault
+    }
+    
+    /// Moteur de prédiction neuronal
+    pub fn neural_net(&self) -> &NeuralNet {
+        &self.neural_net
+    }
+    
+    /// Chaîne de traitement complète des paquets sur les modules du système : analyse par le
+    /// NeuroFireWall, enregistrement des attaques visant un leurre WarpShield, plan de réponse
+    /// AEGIS et diffusion sur le dashboard
+    pub fn analysis_pipeline(&self) -> AnalysisPipeline {
+        AnalysisPipeline::new()
+            .with_stage(FirewallStage::new(self.neurofirewall_handle()))
+            .with_stage(WarpShieldStage::new(self.warpshield_handle()))
+            .with_stage(AegisStage::new(self.aegis_handle()))
+            .with_stage(DashboardStage::new(self.dashboard_handle()))
+    }
+    
+    /// Rapport de santé courant
+    pub fn health(&self) -> HealthReport {
+        HealthReport::new(
+            self.aegis.get_state(),
+            self.neurofirewall.get_state(),
+            self.warpshield.get_state(),
+            self.dashboard.get_state(),
+        )
+    }
+}
+
+/// Constructeur d'un `IcarusSystem` complet
+///
+/// Les modules sont construits avec les configurations fournies (valeurs par défaut sinon),
+/// reçoivent l'horloge et le générateur d'identifiants communs puis sont démarrés.
+pub struct IcarusSystemBuilder {
+    config: IcarusConfig,
+    clock: SharedClock,
+    ids: SharedIdGenerator,
+}
+
+impl Default for IcarusSystemBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IcarusSystemBuilder {
+    /// Crée un constructeur avec la configuration par défaut, l'horloge système et des
+    /// identifiants aléatoires
+    pub fn new() -> Self {
+        Self {
+            config: IcarusConfig::default(),
+            clock: system_clock(),
+            ids: random_ids(),
+        }
+    }
+    
+    /// Remplace la configuration de l'ensemble des modules
+    pub fn with_config(mut self, config: IcarusConfig) -> Self {
+        self.config = config;
+        self
+    }
+    
+    /// Configuration d'AEGIS
+    pub fn with_aegis_config(mut self, config: AegisConfig) -> Self {
+        self.config.aegis = config;
+        self
+    }
+    
+    /// Configuration du NeuroFireWall
+    pub fn with_firewall_config(mut self, config: NeuroFireWallConfig) -> Self {
+        self.config.neurofirewall = config;
+        self
+    }
+    
+    /// Configuration de WarpShield
+    pub fn with_warpshield_config(mut self, config: WarpShieldConfig) -> Self {
+        self.config.warpshield = config;
+        self
+    }
+    
+    /// Configuration du dashboard
+    pub fn with_dashboard_config(mut self, config: DashboardConfig) -> Self {
+        self.config.dashboard = config;
+        self
+    }
+    
+    /// Configuration de QuantumVault
+    pub fn with_quantum_vault_config(mut self, config: QuantumVaultConfig) -> Self {
+        self.config.quantum_vault = config;
+        self
+    }
+    
+    /// Configuration du moteur neuronal
+    pub fn with_neural_net_config(mut self, config: NeuralNetConfig) -> Self {
+        self.config.neural_net = config;
+        self
+    }
+    
+    /// Source de temps partagée par AEGIS, le NeuroFireWall, WarpShield et le dashboard
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+    
+    /// Générateur d'identifiants partagé par AEGIS, le NeuroFireWall, WarpShield et le dashboard
+    pub fn with_id_generator(mut self, ids: SharedIdGenerator) -> Self {
+        self.ids = ids;
+        self
+    }
+    
+    /// Valide la configuration, construit et démarre tous les modules
+    pub fn build(self) -> Result<IcarusSystem, IcarusError> {
+        let config = self.config;
+        config.validate().map_err(IcarusError::Config)?;
+        
+        let mut aegis = AegisOrchestrator::new(config.aegis);
+        aegis.set_clock(self.clock.clone());
+        aegis.set_id_generator(self.ids.clone());
+        aegis.initialize().map_err(IcarusError::Aegis)?;
+        
+        let mut neurofirewall = NeuroFireWall::new(config.neurofirewall);
+        neurofirewall.set_clock(self.clock.clone());
+        neurofirewall.set_id_generator(self.ids.clone());
+        neurofirewall.initialize().map_err(IcarusError::Firewall)?;
+        
+        let mut warpshield = WarpShield::new(config.warpshield);
+        warpshield.set_clock(self.clock.clone());
+        warpshield.set_id_generator(self.ids.clone());
+        warpshield.initialize().map_err(IcarusError::WarpShield)?;
+        
+        let mut dashboard = Dashboard::new(config.dashboard);
+        dashboard.set_clock(self.clock);
+        dashboard.set_id_generator(self.ids);
+        dashboard.start().map_err(IcarusError::Dashboard)?;
+        
+        let quantum_vault = QuantumVault::new(config.quantum_vault);
+        let neural_net = NeuralNet::new(config.neural_net);
+        
+        Ok(IcarusSystem::new(aegis, neurofirewall, warpshield, dashboard, quantum_vault, neural_net))
+    }
+    
+    /// Construit un système reproductible : générateurs aléatoires à graine fixe (sauf graine
+    /// déjà configurée) et horloge simulée, renvoyée pour être avancée par le test
+    #[cfg(test)]
+    pub fn build_for_test(mut self) -> Result<(IcarusSystem, Arc<MockClock>), IcarusError> {
+        self.config.neurofirewall.model_seed.get_or_insert(TEST_RNG_SEED);
+        self.config.warpshield.rng_seed.get_or_insert(TEST_RNG_SEED);
+        
+        let clock = Arc::new(MockClock::new(SystemTime::UNIX_EPOCH + TEST_CLOCK_START));
+        let system = self.with_clock(clock.clone()).build()?;
+        Ok((system, clock))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    use std::collections::HashMap;
+    
+    use crate::aegis::{ThreatEvent, ThreatSeverity, ThreatType};
+    use crate::clock::Clock;
+    use crate::crypto::quantum_vault::PostQuantumAlgorithm;
+    use crate::dashboard::LiveEvent;
+    use crate::neurofirewall::{FirewallDecision, NetworkPacket};
+    
+    #[test]
+    fn test_health_rules() {
+        let healthy = HealthReport::new(
+            AegisState::Operational,
+            NeuroFireWallState::Learning,
+            WarpShieldState::Operational,
+            DashboardState::Running,
+        );
+        assert_eq!(healthy.status, HealthStatus::Healthy);
+        
+        let degraded = HealthReport::new(
+            AegisState::Operational,
+            NeuroFireWallState::Degraded,
+            WarpShieldState::Operational,
+            DashboardState::Running,
+        );
+        assert_eq!(degraded.status, HealthStatus::Degraded);
+        
+        let error = HealthReport::new(
+            AegisState::Operational,
+            NeuroFireWallState::Operational,
+            WarpShieldState::Error("hyperviseur indisponible".to_string()),
+            DashboardState::Running,
+        );
+        assert_eq!(error.status, HealthStatus::Degraded);
+        
+        let down = HealthReport::new(
+            AegisState::Shutdown,
+            NeuroFireWallState::Operational,
+            WarpShieldState::Operational,
+            DashboardState::Running,
+        );
+        assert_eq!(down.status, HealthStatus::Down);
+    }
+    
+    #[test]
+    fn test_system_from_config_is_healthy() {
+        let system = IcarusSystem::from_config(&IcarusConfig::default()).unwrap();
+        assert_eq!(system.health().status, HealthStatus::Healthy);
+    }
+    
+    #[test]
+    fn test_builder_applies_module_configs() {
+        let (system, clock) = IcarusSystemBuilder::new()
+            .with_aegis_config(AegisConfig { autonomy_level: 0.4, ..Default::default() })
+            .with_firewall_config(NeuroFireWallConfig { anomaly_threshold: 0.9, ..Default::default() })
+            .with_warpshield_config(WarpShieldConfig { max_virtual_environments: 7, ..Default::default() })
+            .with_dashboard_config(DashboardConfig { theme: "light".to_string(), ..Default::default() })
+            .with_quantum_vault_config(QuantumVaultConfig {
+                encryption_algorithm: PostQuantumAlgorithm::Kyber768,
+                ..Default::default()
+            })
+            .with_neural_net_config(NeuralNetConfig { attention_heads: 4, ..Default::default() })
+            .build_for_test()
+            .unwrap();
+        
+        assert_eq!(system.health().status, HealthStatus::Healthy);
+        assert_eq!(system.aegis().get_config().autonomy_level, 0.4);
+        assert_eq!(system.neurofirewall().get_config().anomaly_threshold, 0.9);
+        assert_eq!(system.warpshield().get_config().max_virtual_environments, 7);
+        assert_eq!(system.dashboard().get_config().theme, "light");
+        assert_eq!(system.quantum_vault().get_config().encryption_algorithm, PostQuantumAlgorithm::Kyber768);
+        assert_eq!(system.neural_net().get_config().attention_heads, 4);
+        
+        // Système reproductible : graines fixées et horloge simulée partagée
+        assert_eq!(system.neurofirewall().get_config().model_seed, Some(TEST_RNG_SEED));
+        assert_eq!(system.warpshield().get_config().rng_seed, Some(TEST_RNG_SEED));
+        let event = ThreatEvent {
+            id: "threat-builder-1".to_string(),
+            threat_type: ThreatType::PortScan,
+            severity: ThreatSeverity::Medium,
+            confidence: 0.85,
+            source: "192.168.1.100".to_string(),
+            target: "192.168.1.1".to_string(),
+            timestamp: clock.now(),
+            metadata: HashMap::new(),
+        };
+        let plan = system.aegis().process_threat_event(event).unwrap();
+        assert_eq!(plan.created_at, SystemTime::UNIX_EPOCH + TEST_CLOCK_START);
+        
+        // Une configuration invalide est refusée avant la construction des modules
+        let error = IcarusSystemBuilder::new()
+            .with_warpshield_config(WarpShieldConfig { environment_fidelity: 1.5, ..Default::default() })
+            .build()
+            .err()
+            .unwrap();
+        assert!(matches!(error, IcarusError::Config(_)));
+    }
+    
+    #[test]
+    fn test_analysis_pipeline_spans_all_modules() {
+        let (system, _) = IcarusSystemBuilder::new().build_for_test().unwrap();
+        let pipeline = system.analysis_pipeline();
+        assert_eq!(pipeline.stage_names(), vec!["neurofirewall", "warpshield", "aegis", "dashboard"]);
+        
+        let mut events = system.dashboard().subscribe_events().unwrap();
+        system.neurofirewall().block_source("198.51.100.23");
+        let packet = NetworkPacket::builder()
+            .source("198.51.100.23", 40000)
+            .destination("192.168.1.1", 80)
+            .build()
+            .unwrap();
+        
+        let context = pipeline.run(packet).unwrap();
+        assert_eq!(context.decision, Some(FirewallDecision::Block));
+        assert_eq!(context.plans.len(), 1);
+        assert!(matches!(events.try_recv().unwrap(), LiveEvent::Threat(threat) if threat.source == "198.51.100.23"));
+    }
+}
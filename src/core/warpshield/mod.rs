@@ -11,12 +11,24 @@
 //! - Génération de signatures d'attaque et de contre-mesures
 //! - Protection des systèmes critiques par isolation dimensionnelle
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime};
 
+use log::Level;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::aegis::{ThreatEvent, ThreatSeverity, ThreatType};
+use crate::bus::{BusEvent, EventBus};
+use crate::clock::{system_clock, SharedClock};
+use crate::id::{random_ids, IdGenerator, SharedIdGenerator};
+use crate::logging::module_log;
+
 /// Configuration du système WarpShield
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct WarpShieldConfig {
     /// Nombre maximal d'environnements virtuels simultanés
     pub max_virtual_environments: usize,
@@ -34,6 +46,13 @@ pub struct WarpShieldConfig {
     pub log_level: u8,
     /// Ressources maximales allouées (pourcentage du système)
     pub max_resource_allocation: f32,
+    /// Graine du générateur aléatoire (adresses IP virtuelles reproductibles si définie)
+    pub rng_seed: Option<u64>,
+    /// Nombre maximal d'événements d'attaque conservés par environnement (les plus anciens sont
+    /// oubliés au-delà)
+    pub max_events_per_environment: usize,
+    /// Nombre maximal de signatures conservées (les plus anciennes sont oubliées au-delà)
+    pub max_signatures: usize,
 }
 
 impl Default for WarpShieldConfig {
@@ -47,12 +66,15 @@ impl Default for WarpShieldConfig {
             enable_adaptive_learning: true,
             log_level: 3,
             max_resource_allocation: 0.3,
+            rng_seed: None,
+            max_events_per_environment: 1000,
+            max_signatures: 10_000,
         }
     }
 }
 
 /// Types d'environnements virtuels
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum VirtualEnvironmentType {
     /// Serveur web
     WebServer,
@@ -74,6 +96,31 @@ pub enum VirtualEnvironmentType {
     Custom(String),
 }
 
+impl VirtualEnvironmentType {
+    /// Valeur de l'environnement en tant que leurre (0.0 - 1.0)
+    ///
+    /// Une attaque contre un leurre critique (contrôleur de domaine, SCADA) est jugée plus grave
+    /// qu'une attaque contre un poste de travail. La valeur médiane 0.5 laisse la gravité inchangée.
+    pub fn criticality(&self) -> f32 {
+        match self {
+            VirtualEnvironmentType::DomainController | VirtualEnvironmentType::Industrial => 1.0,
+            VirtualEnvironmentType::Database => 0.8,
+            VirtualEnvironmentType::FileServer | VirtualEnvironmentType::Cloud => 0.7,
+            VirtualEnvironmentType::WebServer => 0.6,
+            VirtualEnvironmentType::IoT | VirtualEnvironmentType::Custom(_) => 0.5,
+            VirtualEnvironmentType::Workstation => 0.3,
+        }
+    }
+    
+    /// Nom d'un environnement personnalisé, `None` pour les types prédéfinis
+    pub fn custom_name(&self) -> Option<&str> {
+        match self {
+            VirtualEnvironmentType::Custom(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
 /// État d'un environnement virtuel
 #[derive(Debug, Clone, PartialEq)]
 pub enum VirtualEnvironmentState {
@@ -93,6 +140,27 @@ pub enum VirtualEnvironmentState {
     Terminated,
 }
 
+impl VirtualEnvironmentState {
+    /// Indique si le passage de cet état vers `next` est autorisé
+    ///
+    /// Cycle de vie : Initializing → Ready → Active → Analyzing → Resetting → Ready.
+    /// Tout état non terminal peut passer en erreur ou être terminé.
+    pub fn can_transition_to(&self, next: &VirtualEnvironmentState) -> bool {
+        use VirtualEnvironmentState::*;
+        
+        match (self, next) {
+            (Terminated, _) => false,
+            (_, Error(_)) | (_, Terminated) => true,
+            (Initializing, Ready)
+            | (Ready, Active)
+            | (Active, Analyzing)
+            | (Analyzing, Resetting)
+            | (Resetting, Ready) => true,
+            _ => false,
+        }
+    }
+}
+
 /// Environnement virtuel
 #[derive(Debug, Clone)]
 pub struct VirtualEnvironment {
@@ -116,10 +184,69 @@ pub struct VirtualEnvironment {
     pub attacker_data: HashMap<String, String>,
     /// Ressources allouées (pourcentage du système)
     pub resource_allocation: f32,
+    /// Octets reçus de l'attaquant depuis l'activation
+    pub bytes_received: u64,
+    /// Octets envoyés à l'attaquant depuis l'activation (volume potentiellement exfiltré)
+    pub bytes_sent: u64,
 }
 
-/// Événement d'attaque
+/// Modèle d'environnement virtuel : type, services et vulnérabilités communs à un lot de leurres
 #[derive(Debug, Clone)]
+pub struct EnvironmentTemplate {
+    /// Type d'environnement
+    pub env_type: VirtualEnvironmentType,
+    /// Services exposés
+    pub exposed_services: Vec<String>,
+    /// Vulnérabilités simulées (identifiants CVE)
+    pub simulated_vulnerabilities: Vec<String>,
+    /// Ressources allouées à chaque environnement (pourcentage du système)
+    pub resource_allocation: f32,
+}
+
+impl EnvironmentTemplate {
+    /// Ressources allouées par défaut à un environnement
+    const DEFAULT_RESOURCE_ALLOCATION: f32 = 0.05;
+    
+    /// Modèle par défaut d'un type d'environnement
+    pub fn for_type(env_type: VirtualEnvironmentType) -> Self {
+        let (exposed_services, simulated_vulnerabilities) = match env_type {
+            VirtualEnvironmentType::WebServer => (
+                vec!["http", "https", "ssh"],
+                vec![
+                    "CVE-2021-44228", // Log4j
+                    "CVE-2021-26855", // Exchange Server
+                ],
+            ),
+            VirtualEnvironmentType::Database => (
+                vec!["mysql", "postgresql", "ssh"],
+                vec![
+                    "CVE-2021-3506", // PostgreSQL
+                    "CVE-2021-2307", // MySQL
+                ],
+            ),
+            // Un environnement personnalisé n'expose qu'un accès d'administration : ses
+            // vulnérabilités proviennent du profil enregistré sous son nom
+            VirtualEnvironmentType::Custom(_) => (vec!["ssh"], vec![]),
+            // Services par défaut pour les autres types
+            _ => (
+                vec!["ssh", "http"],
+                vec![
+                    "CVE-2021-28041", // OpenSSH
+                ],
+            ),
+        };
+        
+        Self {
+            env_type,
+            exposed_services: exposed_services.into_iter().map(String::from).collect(),
+            simulated_vulnerabilities: simulated_vulnerabilities.into_iter().map(String::from).collect(),
+            resource_allocation: Self::DEFAULT_RESOURCE_ALLOCATION,
+        }
+    }
+}
+
+/// Événement d'attaque
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttackEvent {
     /// Identifiant unique de l'événement
     pub id: String,
@@ -137,8 +264,78 @@ pub struct AttackEvent {
     pub severity: f32,
 }
 
+impl AttackEvent {
+    /// Confiance attribuée aux attaques observées : un leurre ne reçoit aucun trafic légitime
+    const CONFIDENCE: f32 = 1.0;
+    /// Clé des données d'attaque portant le nombre d'octets reçus par l'environnement
+    pub const BYTES_RECEIVED: &'static str = "bytes_received";
+    /// Clé des données d'attaque portant le nombre d'octets envoyés par l'environnement
+    pub const BYTES_SENT: &'static str = "bytes_sent";
+    
+    /// Encode l'événement au format binaire bincode (plus compact et plus rapide que JSON)
+    pub fn to_bincode(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(self).map_err(|e| format!("Encodage bincode de l'événement d'attaque impossible: {}", e))
+    }
+    
+    /// Décode un événement encodé par `to_bincode`
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, String> {
+        bincode::deserialize(bytes).map_err(|e| format!("Décodage bincode de l'événement d'attaque impossible: {}", e))
+    }
+    
+    /// Type de menace AEGIS correspondant au type d'attaque (`UnknownZeroDay` si inconnu)
+    ///
+    /// La comparaison ignore la casse et accepte `-` comme séparateur.
+    pub fn threat_type(&self) -> ThreatType {
+        match self.attack_type.to_lowercase().replace('-', "_").as_str() {
+            "dos" | "ddos" | "denial_of_service" => ThreatType::DenialOfService,
+            "port_scan" | "network_scan" | "web_scan" => ThreatType::PortScan,
+            "exfiltration" | "data_exfiltration" => ThreatType::DataExfiltration,
+            "sqli" | "sql_injection" => ThreatType::SqlInjection,
+            "xss" | "cross_site_scripting" => ThreatType::Xss,
+            "brute_force" | "credential_stuffing" => ThreatType::BruteForce,
+            "malware" => ThreatType::Malware,
+            "c2" | "command_and_control" => ThreatType::CommandAndControl,
+            _ => ThreatType::UnknownZeroDay,
+        }
+    }
+    
+    /// Gravité AEGIS correspondant au score de gravité
+    pub fn threat_severity(&self) -> ThreatSeverity {
+        match self.severity {
+            s if s >= 0.9 => ThreatSeverity::Critical,
+            s if s >= 0.7 => ThreatSeverity::High,
+            s if s >= 0.4 => ThreatSeverity::Medium,
+            s if s >= 0.2 => ThreatSeverity::Low,
+            _ => ThreatSeverity::Info,
+        }
+    }
+    
+    /// Conversion en événement de menace transmissible à AEGIS, identifié par `ids`
+    ///
+    /// La cible est l'environnement virtuel attaqué ; les données de l'attaque sont reprises
+    /// dans les métadonnées, avec l'identifiant de l'événement d'origine et le type d'attaque brut.
+    pub fn to_threat_event(&self, ids: &dyn IdGenerator) -> ThreatEvent {
+        let mut metadata = self.data.clone();
+        metadata.insert("attack_event".to_string(), self.id.clone());
+        metadata.insert("attack_type".to_string(), self.attack_type.clone());
+        
+        ThreatEvent {
+            id: ids.next_id("threat"),
+            threat_type: self.threat_type(),
+            severity: self.threat_severity(),
+            confidence: Self::CONFIDENCE,
+            source: self.source.clone(),
+            target: self.environment_id.clone(),
+            timestamp: self.timestamp,
+            metadata,
+        }
+    }
+}
+
 /// Signature d'attaque générée
-#[derive(Debug, Clone)]
+///
+/// Sérialisable pour être partagée entre déploiements (voir `WarpShield::export_signatures`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttackSignature {
     /// Identifiant unique de la signature
     pub id: String,
@@ -158,8 +355,35 @@ pub struct AttackSignature {
     pub recommended_countermeasures: Vec<String>,
 }
 
+impl AttackSignature {
+    /// Ensemble des motifs, indépendant de leur ordre et de leurs répétitions
+    fn pattern_set(&self) -> Vec<&str> {
+        let mut patterns: Vec<&str> = self.patterns.iter().map(String::as_str).collect();
+        patterns.sort_unstable();
+        patterns.dedup();
+        patterns
+    }
+}
+
+/// Profil d'un attaquant agrégé sur l'ensemble des environnements
+#[derive(Debug, Clone, Default)]
+pub struct AttackerProfile {
+    /// Source de l'attaquant (IP, utilisateur, etc.)
+    pub source: String,
+    /// Nombre total d'événements d'attaque enregistrés
+    pub total_events: usize,
+    /// Identifiants des environnements touchés (triés)
+    pub environments: Vec<String>,
+    /// Types d'attaque observés (triés, sans doublon)
+    pub attack_types: Vec<String>,
+    /// Première activité observée
+    pub first_seen: Option<SystemTime>,
+    /// Dernière activité observée
+    pub last_seen: Option<SystemTime>,
+}
+
 /// Statistiques de WarpShield
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WarpShieldStats {
     /// Nombre total d'environnements virtuels créés
     pub total_environments_created: u64,
@@ -167,11 +391,19 @@ pub struct WarpShieldStats {
     pub active_environments: usize,
     /// Nombre total d'attaques détectées
     pub total_attacks_detected: u64,
+    /// Nombre d'activations d'environnements
+    pub environments_activated: u64,
+    /// Nombre d'environnements ayant enregistré au moins une attaque
+    pub environments_attacked: u64,
     /// Nombre de signatures générées
     pub signatures_generated: u64,
+    /// Total des octets reçus par les environnements
+    pub total_bytes_received: u64,
+    /// Total des octets envoyés par les environnements
+    pub total_bytes_sent: u64,
     /// Temps moyen d'analyse (en secondes)
     pub avg_analysis_time: f64,
-    /// Taux de détection d'attaques
+    /// Taux de détection d'attaques (environnements attaqués / environnements activés)
     pub attack_detection_rate: f32,
     /// Utilisation des ressources (pourcentage)
     pub resource_utilization: f32,
@@ -180,7 +412,7 @@ pub struct WarpShieldStats {
 }
 
 /// État du système WarpShield
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WarpShieldState {
     /// Initialisation en cours
     Initializing,
@@ -200,8 +432,26 @@ pub enum WarpShieldState {
 pub struct WarpShield {
     config: WarpShieldConfig,
     state: Arc<Mutex<WarpShieldState>>,
+    /// Instant de passage à l'état opérationnel (None si arrêté)
+    started_at: Arc<Mutex<Option<SystemTime>>>,
     stats: Arc<Mutex<WarpShieldStats>>,
     environments: Arc<Mutex<HashMap<String, VirtualEnvironment>>>,
+    /// Environnements existants ayant enregistré au moins une attaque depuis leur activation
+    attacked_environments: Arc<Mutex<HashSet<String>>>,
+    /// Événements d'attaque récents par environnement existant (au plus `max_events_per_environment`)
+    attack_events: Arc<Mutex<HashMap<String, VecDeque<AttackEvent>>>>,
+    /// Vulnérabilités simulées définies par l'opérateur, remplaçant celles par défaut
+    vulnerability_profiles: Arc<Mutex<HashMap<VirtualEnvironmentType, Vec<String>>>>,
+    /// Signatures générées ou importées, uniques par ensemble de motifs
+    signatures: Arc<Mutex<VecDeque<AttackSignature>>>,
+    /// Source de temps (horodatages, expiration des sessions, temps d'activité)
+    clock: SharedClock,
+    /// Générateur des identifiants d'environnements, d'attaques et de signatures
+    ids: SharedIdGenerator,
+    /// Générateur aléatoire utilisé pour l'attribution des adresses IP virtuelles
+    rng: Arc<Mutex<StdRng>>,
+    /// Bus sur lequel les événements d'attaque sont publiés
+    event_bus: Option<EventBus>,
     // Les champs suivants seront implémentés dans les versions futures
     // environment_manager: EnvironmentManager,
     // attack_analyzer: AttackAnalyzer,
@@ -210,24 +460,51 @@ pub struct WarpShield {
 }
 
 impl WarpShield {
+    /// Gravité d'une attaque dans un environnement de criticité médiane
+    const BASE_ATTACK_SEVERITY: f32 = 0.7;
+    /// Tolérance d'arrondi sur la somme des ressources allouées
+    const RESOURCE_EPSILON: f32 = 1e-4;
+    /// Nombre d'hôtes du sous-réseau virtuel 10.0.0.0/16 (10.0.x.1 à 10.0.x.254)
+    const VIRTUAL_SUBNET_SIZE: usize = 256 * 254;
+    /// Tirages aléatoires tentés avant le parcours exhaustif du sous-réseau virtuel
+    const RANDOM_IP_ATTEMPTS: usize = 64;
+    
     /// Crée une nouvelle instance de WarpShield
     pub fn new(config: WarpShieldConfig) -> Self {
         let stats = WarpShieldStats {
             total_environments_created: 0,
             active_environments: 0,
             total_attacks_detected: 0,
+            environments_activated: 0,
+            environments_attacked: 0,
             signatures_generated: 0,
+            total_bytes_received: 0,
+            total_bytes_sent: 0,
             avg_analysis_time: 0.0,
             attack_detection_rate: 0.0,
             resource_utilization: 0.0,
             uptime_seconds: 0,
         };
         
+        let rng = match config.rng_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        
         Self {
             config,
             state: Arc::new(Mutex::new(WarpShieldState::Initializing)),
+            started_at: Arc::new(Mutex::new(None)),
             stats: Arc::new(Mutex::new(stats)),
             environments: Arc::new(Mutex::new(HashMap::new())),
+            attacked_environments: Arc::new(Mutex::new(HashSet::new())),
+            attack_events: Arc::new(Mutex::new(HashMap::new())),
+            vulnerability_profiles: Arc::new(Mutex::new(HashMap::new())),
+            signatures: Arc::new(Mutex::new(VecDeque::new())),
+            clock: system_clock(),
+            ids: random_ids(),
+            rng: Arc::new(Mutex::new(rng)),
+            event_bus: None,
             // Les champs suivants seront initialisés dans les versions futures
         }
     }
@@ -239,98 +516,219 @@ impl WarpShield {
         
         let mut state = self.state.lock().unwrap();
         *state = WarpShieldState::Operational;
+        *self.started_at.lock().unwrap() = Some(self.clock.now());
+        
+        module_log!(self.config.log_level, Level::Info, "WarpShield opérationnel");
         
         Ok(())
     }
     
     /// Crée un nouvel environnement virtuel
+    ///
+    /// Un environnement `Custom` reprend les vulnérabilités du profil enregistré sous son nom
+    /// (`set_vulnerability_profile`) ; son nom ne peut pas être vide.
     pub fn create_virtual_environment(&self, env_type: VirtualEnvironmentType) -> Result<VirtualEnvironment, String> {
-        // Vérifier l'état du système
+        if env_type.custom_name().is_some_and(|name| name.trim().is_empty()) {
+            return Err("Le nom d'un environnement personnalisé ne peut pas être vide".to_string());
+        }
+        
+        let mut template = EnvironmentTemplate::for_type(env_type);
+        
+        // Un profil défini par l'opérateur remplace les vulnérabilités par défaut
+        if let Some(cves) = self.vulnerability_profiles.lock().unwrap().get(&template.env_type) {
+            template.simulated_vulnerabilities = cves.clone();
+        }
+        
+        self.check_operational()?;
+        
+        // Le verrou est conservé jusqu'à l'insertion pour garantir l'unicité des adresses IP
+        let mut environments = self.environments.lock().unwrap();
+        self.spawn_environment(&mut environments, &template)
+    }
+    
+    /// Crée `count` environnements identiques à partir d'un modèle, avec des adresses IP distinctes
+    ///
+    /// Le lot est refusé si les ressources allouées aux environnements existants et au lot
+    /// dépassent `max_resource_allocation`. Si la capacité `max_virtual_environments` est atteinte
+    /// en cours de lot, les environnements déjà créés sont conservés et l'erreur indique leur nombre.
+    pub fn create_environments_from_template(&self, template: EnvironmentTemplate, count: usize) -> Result<Vec<VirtualEnvironment>, String> {
+        if let Some(invalid) = template.simulated_vulnerabilities.iter().find(|cve| !is_valid_cve_id(cve)) {
+            return Err(format!("Identifiant CVE invalide dans le modèle: '{}'", invalid));
+        }
+        if !(template.resource_allocation > 0.0 && template.resource_allocation <= self.config.max_resource_allocation) {
+            return Err(format!(
+                "Allocation de ressources du modèle hors limites: {} (maximum: {})",
+                template.resource_allocation, self.config.max_resource_allocation
+            ));
+        }
+        
+        self.check_operational()?;
+        
+        let mut environments = self.environments.lock().unwrap();
+        let allocated: f32 = environments.values().map(|env| env.resource_allocation).sum();
+        let requested = template.resource_allocation * count as f32;
+        if allocated + requested > self.config.max_resource_allocation + Self::RESOURCE_EPSILON {
+            return Err(format!(
+                "Budget de ressources dépassé: {:.2} déjà allouées + {:.2} demandées (maximum: {})",
+                allocated, requested, self.config.max_resource_allocation
+            ));
+        }
+        
+        let mut created = Vec::with_capacity(count);
+        for _ in 0..count {
+            match self.spawn_environment(&mut environments, &template) {
+                Ok(env) => created.push(env),
+                Err(e) if created.is_empty() => return Err(e),
+                Err(e) => return Err(format!("{} ({} environnements créés sur {})", e, created.len(), count)),
+            }
+        }
+        
+        Ok(created)
+    }
+    
+    /// Vérifie que WarpShield est opérationnel
+    fn check_operational(&self) -> Result<(), String> {
         let state = self.state.lock().unwrap();
         if *state != WarpShieldState::Operational {
             return Err(format!("WarpShield n'est pas opérationnel, état actuel: {:?}", state));
         }
-        drop(state);
         
-        // Vérifier le nombre d'environnements actifs
-        let environments = self.environments.lock().unwrap();
+        Ok(())
+    }
+    
+    /// Crée et enregistre un environnement à partir d'un modèle, dans la limite de
+    /// `max_virtual_environments`
+    fn spawn_environment(
+        &self,
+        environments: &mut HashMap<String, VirtualEnvironment>,
+        template: &EnvironmentTemplate,
+    ) -> Result<VirtualEnvironment, String> {
         if environments.len() >= self.config.max_virtual_environments {
+            module_log!(self.config.log_level, Level::Warn, "Capacité d'environnements virtuels atteinte");
             return Err(format!(
                 "Nombre maximal d'environnements virtuels atteint ({})",
                 self.config.max_virtual_environments
             ));
         }
-        drop(environments);
         
         // Générer un ID unique pour l'environnement
-        let env_id = format!("env-{}", uuid::Uuid::new_v4());
+        let env_id = self.ids.next_id("env");
+        let now = self.clock.now();
         
         // Créer l'environnement virtuel
-        let environment = VirtualEnvironment {
+        let mut env = VirtualEnvironment {
             id: env_id.clone(),
-            env_type: env_type.clone(),
+            env_type: template.env_type.clone(),
             state: VirtualEnvironmentState::Initializing,
-            created_at: SystemTime::now(),
-            last_activity: SystemTime::now(),
-            virtual_ip: format!("10.0.{}.{}", rand::random::<u8>(), rand::random::<u8>()),
-            exposed_services: Vec::new(),
-            simulated_vulnerabilities: Vec::new(),
+            created_at: now,
+            last_activity: now,
+            virtual_ip: self.allocate_virtual_ip(environments)?,
+            exposed_services: template.exposed_services.clone(),
+            simulated_vulnerabilities: template.simulated_vulnerabilities.clone(),
             attacker_data: HashMap::new(),
-            resource_allocation: 0.05,
+            resource_allocation: template.resource_allocation,
+            bytes_received: 0,
+            bytes_sent: 0,
         };
         
-        // Ajouter des services exposés selon le type d'environnement
-        let mut env = environment.clone();
-        match env_type {
-            VirtualEnvironmentType::WebServer => {
-                env.exposed_services = vec![
-                    "http".to_string(),
-                    "https".to_string(),
-                    "ssh".to_string(),
-                ];
-                env.simulated_vulnerabilities = vec![
-                    "CVE-2021-44228".to_string(), // Log4j
-                    "CVE-2021-26855".to_string(), // Exchange Server
-                ];
-            },
-            VirtualEnvironmentType::Database => {
-                env.exposed_services = vec![
-                    "mysql".to_string(),
-                    "postgresql".to_string(),
-                    "ssh".to_string(),
-                ];
-                env.simulated_vulnerabilities = vec![
-                    "CVE-2021-3506".to_string(), // PostgreSQL
-                    "CVE-2021-2307".to_string(), // MySQL
-                ];
-            },
-            _ => {
-                // Services par défaut pour les autres types
-                env.exposed_services = vec![
-                    "ssh".to_string(),
-                    "http".to_string(),
-                ];
-                env.simulated_vulnerabilities = vec![
-                    "CVE-2021-28041".to_string(), // OpenSSH
-                ];
-            }
-        }
-        
         // Mettre à jour l'état de l'environnement
-        env.state = VirtualEnvironmentState::Ready;
+        Self::transition(&mut env, VirtualEnvironmentState::Ready)?;
         
         // Ajouter l'environnement à la liste
-        let mut environments = self.environments.lock().unwrap();
-        environments.insert(env_id.clone(), env.clone());
+        environments.insert(env_id, env.clone());
         
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
         stats.total_environments_created += 1;
         stats.active_environments = environments.len();
         
+        module_log!(self.config.log_level, Level::Debug, "Environnement {} créé ({:?}, {})", env.id, env.env_type, env.virtual_ip);
+        
         Ok(env)
     }
     
+    /// Remplace la source de temps (horloge simulée dans les tests)
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+    
+    /// Remplace le générateur d'identifiants (identifiants séquentiels dans les tests)
+    pub fn set_id_generator(&mut self, ids: SharedIdGenerator) {
+        self.ids = ids;
+    }
+    
+    /// Publie les événements d'attaque sur le bus d'événements
+    pub fn set_event_bus(&mut self, bus: EventBus) {
+        self.event_bus = Some(bus);
+    }
+    
+    /// Termine les sessions actives inactives depuis plus de `max_session_duration`
+    ///
+    /// Renvoie le nombre d'environnements retirés.
+    pub fn expire_stale_environments(&self) -> usize {
+        let now = self.clock.now();
+        let timeout = Duration::from_secs(self.config.max_session_duration);
+        
+        let mut environments = self.environments.lock().unwrap();
+        let stale: Vec<String> = environments
+            .values()
+            .filter(|env| env.state == VirtualEnvironmentState::Active)
+            .filter(|env| now.duration_since(env.last_activity).unwrap_or_default() > timeout)
+            .map(|env| env.id.clone())
+            .collect();
+        
+        for env_id in &stale {
+            if let Some(mut env) = environments.remove(env_id) {
+                // Active -> Terminated est toujours autorisé
+                let _ = Self::transition(&mut env, VirtualEnvironmentState::Terminated);
+                self.forget_attacks(env_id);
+                module_log!(self.config.log_level, Level::Debug, "Session {} expirée après inactivité", env_id);
+            }
+        }
+        
+        if !stale.is_empty() {
+            let mut stats = self.stats.lock().unwrap();
+            stats.active_environments = environments.len();
+        }
+        
+        stale.len()
+    }
+    
+    /// Définit les vulnérabilités (identifiants CVE) simulées par les futurs environnements d'un type
+    ///
+    /// Les environnements déjà créés ne sont pas modifiés.
+    pub fn set_vulnerability_profile(&self, env_type: VirtualEnvironmentType, cves: Vec<String>) -> Result<(), String> {
+        if let Some(invalid) = cves.iter().find(|cve| !is_valid_cve_id(cve)) {
+            return Err(format!("Identifiant CVE invalide: '{}' (format attendu: CVE-AAAA-NNNN)", invalid));
+        }
+        
+        module_log!(self.config.log_level, Level::Info, "Profil de vulnérabilités {:?}: {:?}", env_type, cves);
+        self.vulnerability_profiles.lock().unwrap().insert(env_type, cves);
+        
+        Ok(())
+    }
+    
+    /// Attribue une adresse IP virtuelle (10.0.x.y) non utilisée par les environnements existants
+    ///
+    /// Après `RANDOM_IP_ATTEMPTS` tirages aléatoires infructueux, le sous-réseau est parcouru
+    /// dans l'ordre ; renvoie une erreur s'il est épuisé.
+    fn allocate_virtual_ip(&self, environments: &HashMap<String, VirtualEnvironment>) -> Result<String, String> {
+        let used: HashSet<&str> = environments.values().map(|env| env.virtual_ip.as_str()).collect();
+        let mut rng = self.rng.lock().unwrap();
+        
+        for _ in 0..Self::RANDOM_IP_ATTEMPTS {
+            let ip = format!("10.0.{}.{}", rng.gen::<u8>(), rng.gen_range(1..=254u8));
+            if !used.contains(ip.as_str()) {
+                return Ok(ip);
+            }
+        }
+        
+        (0..Self::VIRTUAL_SUBNET_SIZE)
+            .map(|index| format!("10.0.{}.{}", index / 254, index % 254 + 1))
+            .find(|ip| !used.contains(ip.as_str()))
+            .ok_or_else(|| format!("Sous-réseau virtuel épuisé ({} adresses attribuées)", Self::VIRTUAL_SUBNET_SIZE))
+    }
+    
     /// Active un environnement virtuel pour rediriger un attaquant
     pub fn activate_environment(&self, env_id: &str, attacker_source: &str) -> Result<(), String> {
         // Vérifier l'état du système
@@ -353,19 +751,31 @@ impl WarpShield {
         }
         
         // Mettre à jour l'état de l'environnement
-        env.state = VirtualEnvironmentState::Active;
-        env.last_activity = SystemTime::now();
+        Self::transition(env, VirtualEnvironmentState::Active)?;
+        env.last_activity = self.clock.now();
         env.attacker_data.insert("source".to_string(), attacker_source.to_string());
-        env.attacker_data.insert("activation_time".to_string(), SystemTime::now()
+        if let Some(name) = env.env_type.custom_name() {
+            env.attacker_data.insert("environment_profile".to_string(), name.to_string());
+        }
+        env.attacker_data.insert("activation_time".to_string(), env.last_activity
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs()
             .to_string());
         
+        let mut stats = self.stats.lock().unwrap();
+        stats.environments_activated += 1;
+        Self::update_detection_rate(&mut stats);
+        
+        module_log!(self.config.log_level, Level::Info, "Environnement {} activé pour {}", env_id, attacker_source);
+        
         Ok(())
     }
     
     /// Enregistre un événement d'attaque dans un environnement virtuel
+    ///
+    /// Les volumes échangés, fournis dans `data` sous les clés `AttackEvent::BYTES_RECEIVED` et
+    /// `AttackEvent::BYTES_SENT`, s'ajoutent aux totaux de l'environnement et des statistiques.
     pub fn record_attack_event(&self, env_id: &str, attack_type: &str, data: HashMap<String, String>) -> Result<AttackEvent, String> {
         // Vérifier l'état du système
         let state = self.state.lock().unwrap();
@@ -374,6 +784,9 @@ impl WarpShield {
         }
         drop(state);
         
+        let bytes_received = Self::byte_count(&data, AttackEvent::BYTES_RECEIVED)?;
+        let bytes_sent = Self::byte_count(&data, AttackEvent::BYTES_SENT)?;
+        
         // Récupérer l'environnement
         let mut environments = self.environments.lock().unwrap();
         let env = environments.get_mut(env_id).ok_or(format!("Environnement non trouvé: {}", env_id))?;
@@ -386,29 +799,124 @@ impl WarpShield {
             ));
         }
         
-        // Mettre à jour l'horodatage de dernière activité
-        env.last_activity = SystemTime::now();
+        // Mettre à jour l'horodatage de dernière activité et les volumes échangés
+        env.last_activity = self.clock.now();
+        env.bytes_received = env.bytes_received.saturating_add(bytes_received);
+        env.bytes_sent = env.bytes_sent.saturating_add(bytes_sent);
         
         // Créer l'événement d'attaque
         let event = AttackEvent {
-            id: format!("attack-{}", uuid::Uuid::new_v4()),
+            id: self.ids.next_id("attack"),
             environment_id: env_id.to_string(),
             attack_type: attack_type.to_string(),
             source: env.attacker_data.get("source").cloned().unwrap_or_default(),
-            timestamp: SystemTime::now(),
+            timestamp: self.clock.now(),
             data,
-            severity: 0.7, // Valeur par défaut, sera calculée dans les versions futures
+            // Gravité de base pondérée par la criticité du leurre attaqué
+            severity: (Self::BASE_ATTACK_SEVERITY * (0.5 + env.env_type.criticality())).min(1.0),
         };
         
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
         stats.total_attacks_detected += 1;
+        stats.total_bytes_received = stats.total_bytes_received.saturating_add(bytes_received);
+        stats.total_bytes_sent = stats.total_bytes_sent.saturating_add(bytes_sent);
+        if self.attacked_environments.lock().unwrap().insert(env_id.to_string()) {
+            stats.environments_attacked += 1;
+            Self::update_detection_rate(&mut stats);
+        }
+        
+        drop(stats);
+        
+        // Conserver l'événement pour la corrélation des attaquants
+        {
+            let mut attack_events = self.attack_events.lock().unwrap();
+            let env_events = attack_events.entry(env_id.to_string()).or_default();
+            if env_events.len() >= self.config.max_events_per_environment {
+                env_events.pop_front();
+            }
+            env_events.push_back(event.clone());
+        }
+        
+        module_log!(self.config.log_level, Level::Warn, "Attaque {} depuis {} dans {}", event.attack_type, event.source, env_id);
+        
+        if let Some(bus) = &self.event_bus {
+            bus.publish(BusEvent::Attack(event.clone()));
+        }
         
         Ok(event)
     }
     
+    /// Nombre d'octets porté par la clé `key` des données d'une attaque (0 si absente)
+    fn byte_count(data: &HashMap<String, String>, key: &str) -> Result<u64, String> {
+        data.get(key).map_or(Ok(0), |value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| format!("Nombre d'octets invalide pour {}: {}", key, value))
+        })
+    }
+    
+    /// Agrège l'activité d'une source sur tous les environnements
+    ///
+    /// Combine les environnements actuellement activés par cette source (données de l'attaquant)
+    /// et les événements d'attaque conservés par les environnements existants ; l'historique d'un
+    /// environnement terminé ou réinitialisé n'est plus pris en compte.
+    pub fn correlate_attacker(&self, source: &str) -> AttackerProfile {
+        let mut environments = HashSet::new();
+        let mut attack_types = HashSet::new();
+        let mut total_events = 0;
+        let mut first_seen: Option<SystemTime> = None;
+        let mut last_seen: Option<SystemTime> = None;
+        let mut observe = |timestamp: SystemTime| {
+            first_seen = Some(first_seen.map_or(timestamp, |first| first.min(timestamp)));
+            last_seen = Some(last_seen.map_or(timestamp, |last| last.max(timestamp)));
+        };
+        
+        for env in self.environments.lock().unwrap().values() {
+            if env.attacker_data.get("source").map(String::as_str) != Some(source) {
+                continue;
+            }
+            environments.insert(env.id.clone());
+            
+            let activation_time = env
+                .attacker_data
+                .get("activation_time")
+                .and_then(|secs| secs.parse::<u64>().ok())
+                .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+            if let Some(activation_time) = activation_time {
+                observe(activation_time);
+            }
+        }
+        
+        for (env_id, env_events) in self.attack_events.lock().unwrap().iter() {
+            for event in env_events.iter().filter(|event| event.source == source) {
+                environments.insert(env_id.clone());
+                attack_types.insert(event.attack_type.clone());
+                total_events += 1;
+                observe(event.timestamp);
+            }
+        }
+        
+        let mut environments: Vec<String> = environments.into_iter().collect();
+        environments.sort();
+        let mut attack_types: Vec<String> = attack_types.into_iter().collect();
+        attack_types.sort();
+        
+        AttackerProfile {
+            source: source.to_string(),
+            total_events,
+            environments,
+            attack_types,
+            first_seen,
+            last_seen,
+        }
+    }
+    
     /// Génère une signature d'attaque à partir des événements enregistrés
     pub fn generate_attack_signature(&self, env_id: &str, name: &str, description: &str) -> Result<AttackSignature, String> {
+        let start_time = Instant::now();
+        
         // Vérifier si la génération de signatures est activée
         if !self.config.enable_signature_generation {
             return Err("La génération de signatures est désactivée".to_string());
@@ -426,16 +934,20 @@ impl WarpShield {
         let env = environments.get(env_id).ok_or(format!("Environnement non trouvé: {}", env_id))?;
         
         // Créer la signature (dans les versions futures, elle sera générée automatiquement)
+        let mut patterns = vec![
+            format!("source:{}", env.attacker_data.get("source").cloned().unwrap_or_default()),
+            format!("env_type:{:?}", env.env_type),
+        ];
+        if let Some(profile) = env.env_type.custom_name() {
+            patterns.push(format!("env_profile:{}", profile));
+        }
         let signature = AttackSignature {
-            id: format!("sig-{}", uuid::Uuid::new_v4()),
+            id: self.ids.next_id("sig"),
             name: name.to_string(),
             description: description.to_string(),
-            patterns: vec![
-                format!("source:{}", env.attacker_data.get("source").cloned().unwrap_or_default()),
-                format!("env_type:{:?}", env.env_type),
-            ],
+            patterns,
             confidence: 0.85,
-            created_at: SystemTime::now(),
+            created_at: self.clock.now(),
             related_attack_events: vec![],
             recommended_countermeasures: vec![
                 "block_ip".to_string(),
@@ -447,9 +959,170 @@ impl WarpShield {
         let mut stats = self.stats.lock().unwrap();
         stats.signatures_generated += 1;
         
+        // Mettre à jour le temps d'analyse moyen (moyenne incrémentale)
+        let analysis_time = start_time.elapsed().as_secs_f64();
+        stats.avg_analysis_time += (analysis_time - stats.avg_analysis_time) / stats.signatures_generated as f64;
+        
+        drop(stats);
+        drop(environments);
+        self.store_signature(&mut self.signatures.lock().unwrap(), signature.clone());
+        
+        module_log!(self.config.log_level, Level::Info, "Signature {} générée pour {}", signature.name, env_id);
+        
         Ok(signature)
     }
     
+    /// Obtient les signatures générées ou importées
+    pub fn get_signatures(&self) -> Vec<AttackSignature> {
+        self.signatures.lock().unwrap().iter().cloned().collect()
+    }
+    
+    /// Obtient une signature par son identifiant
+    pub fn get_signature(&self, signature_id: &str) -> Option<AttackSignature> {
+        self.signatures.lock().unwrap().iter().find(|signature| signature.id == signature_id).cloned()
+    }
+    
+    /// Exporte les signatures sous forme de tableau JSON, partageable avec un autre déploiement
+    pub fn export_signatures(&self) -> String {
+        let signatures = self.signatures.lock().unwrap();
+        serde_json::to_string(&*signatures).unwrap_or_else(|_| "[]".to_string())
+    }
+    
+    /// Importe des signatures depuis un tableau JSON produit par `export_signatures`
+    ///
+    /// Les signatures dont l'identifiant ou l'ensemble de motifs est déjà connu sont ignorées,
+    /// et seules les `max_signatures` plus récentes sont conservées. L'import est
+    /// rejeté en entier si une signature est invalide (aucun motif, confiance hors de [0, 1]).
+    /// Renvoie le nombre de signatures ajoutées.
+    pub fn import_signatures(&self, json: &str) -> Result<usize, String> {
+        let imported: Vec<AttackSignature> =
+            serde_json::from_str(json).map_err(|e| format!("Signatures invalides: {}", e))?;
+        
+        for signature in &imported {
+            if !(0.0..=1.0).contains(&signature.confidence) {
+                return Err(format!(
+                    "Confiance de la signature {} hors de [0, 1]: {}",
+                    signature.id, signature.confidence
+                ));
+            }
+            if signature.patterns.is_empty() {
+                return Err(format!("La signature {} ne contient aucun motif", signature.id));
+            }
+        }
+        
+        let mut signatures = self.signatures.lock().unwrap();
+        let count = imported
+            .into_iter()
+            .filter(|signature| self.store_signature(&mut signatures, signature.clone()))
+            .count();
+        
+        module_log!(self.config.log_level, Level::Info, "{} signature(s) importée(s)", count);
+        
+        Ok(count)
+    }
+    
+    /// Ajoute une signature si ni son identifiant ni son ensemble de motifs ne sont déjà connus
+    ///
+    /// Au-delà de `max_signatures`, la plus ancienne signature est oubliée.
+    fn store_signature(&self, signatures: &mut VecDeque<AttackSignature>, signature: AttackSignature) -> bool {
+        let patterns = signature.pattern_set();
+        if signatures
+            .iter()
+            .any(|existing| existing.id == signature.id || existing.pattern_set() == patterns)
+        {
+            return false;
+        }
+        while signatures.len() >= self.config.max_signatures.max(1) {
+            signatures.pop_front();
+        }
+        signatures.push_back(signature);
+        true
+    }
+    
+    /// Passe un environnement actif en phase d'analyse
+    ///
+    /// Les événements d'attaque ne sont plus acceptés tant que l'analyse est en cours.
+    pub fn begin_analysis(&self, env_id: &str) -> Result<(), String> {
+        let mut environments = self.environments.lock().unwrap();
+        let env = environments.get_mut(env_id).ok_or(format!("Environnement non trouvé: {}", env_id))?;
+        
+        Self::transition(env, VirtualEnvironmentState::Analyzing)?;
+        env.last_activity = self.clock.now();
+        
+        module_log!(self.config.log_level, Level::Debug, "Analyse de l'environnement {} démarrée", env_id);
+        
+        Ok(())
+    }
+    
+    /// Termine la phase d'analyse d'un environnement
+    ///
+    /// Avec `reset`, l'environnement est réinitialisé (données de l'attaquant et volumes échangés
+    /// effacés) et redevient `Ready` ; sinon il est terminé et retiré de la liste.
+    pub fn finish_analysis(&self, env_id: &str, reset: bool) -> Result<(), String> {
+        let mut environments = self.environments.lock().unwrap();
+        let env = environments.get_mut(env_id).ok_or(format!("Environnement non trouvé: {}", env_id))?;
+        
+        if env.state != VirtualEnvironmentState::Analyzing {
+            return Err(format!(
+                "L'environnement n'est pas en cours d'analyse, état actuel: {:?}",
+                env.state
+            ));
+        }
+        
+        if reset {
+            Self::transition(env, VirtualEnvironmentState::Resetting)?;
+            env.attacker_data.clear();
+            env.bytes_received = 0;
+            env.bytes_sent = 0;
+            env.last_activity = self.clock.now();
+            Self::transition(env, VirtualEnvironmentState::Ready)?;
+            
+            // Une nouvelle activation pourra de nouveau compter comme environnement attaqué
+            self.forget_attacks(env_id);
+            
+            module_log!(self.config.log_level, Level::Debug, "Environnement {} réinitialisé", env_id);
+        } else {
+            Self::transition(env, VirtualEnvironmentState::Terminated)?;
+            environments.remove(env_id);
+            self.forget_attacks(env_id);
+            
+            let mut stats = self.stats.lock().unwrap();
+            stats.active_environments = environments.len();
+            
+            module_log!(self.config.log_level, Level::Debug, "Environnement {} terminé après analyse", env_id);
+        }
+        
+        Ok(())
+    }
+    
+    /// Applique une transition d'état en rejetant les transitions illégales
+    fn transition(env: &mut VirtualEnvironment, next: VirtualEnvironmentState) -> Result<(), String> {
+        if !env.state.can_transition_to(&next) {
+            return Err(format!(
+                "Transition illégale de l'environnement {}: {:?} -> {:?}",
+                env.id, env.state, next
+            ));
+        }
+        
+        env.state = next;
+        Ok(())
+    }
+    
+    /// Oublie le suivi et l'historique des attaques d'un environnement réinitialisé ou retiré
+    fn forget_attacks(&self, env_id: &str) {
+        self.attacked_environments.lock().unwrap().remove(env_id);
+        self.attack_events.lock().unwrap().remove(env_id);
+    }
+    
+    /// Recalcule le taux de détection à partir des compteurs d'activation et d'attaque
+    fn update_detection_rate(stats: &mut WarpShieldStats) {
+        stats.attack_detection_rate = if stats.environments_activated > 0 {
+            stats.environments_attacked as f32 / stats.environments_activated as f32
+        } else {
+            0.0
+        };
+    }
+    
     /// Termine et nettoie un environnement virtuel
     pub fn terminate_environment(&self, env_id: &str) -> Result<(), String> {
         // Vérifier l'état du système
@@ -462,11 +1135,14 @@ impl WarpShield {
         // Récupérer et supprimer l'environnement
         let mut environments = self.environments.lock().unwrap();
         let env = environments.remove(env_id).ok_or(format!("Environnement non trouvé: {}", env_id))?;
+        self.forget_attacks(env_id);
         
         // Mettre à jour les statistiques
         let mut stats = self.stats.lock().unwrap();
         stats.active_environments = environments.len();
         
+        module_log!(self.config.log_level, Level::Debug, "Environnement {} ({:?}) terminé", env.id, env.env_type);
+        
         Ok(())
     }
     
@@ -475,9 +1151,41 @@ impl WarpShield {
         self.state.lock().unwrap().clone()
     }
     
+    /// Obtient la configuration du système
+    pub fn get_config(&self) -> &WarpShieldConfig {
+        &self.config
+    }
+    
     /// Obtient les statistiques actuelles
     pub fn get_stats(&self) -> WarpShieldStats {
-        self.stats.lock().unwrap().clone()
+        let mut stats = self.stats.lock().unwrap().clone();
+        stats.uptime_seconds = self.uptime_seconds();
+        stats
+    }
+    
+    /// Temps écoulé depuis le dernier démarrage (en secondes)
+    fn uptime_seconds(&self) -> u64 {
+        let now = self.clock.now();
+        self.started_at
+            .lock()
+            .unwrap()
+            .map_or(0, |started_at| now.duration_since(started_at).unwrap_or_default().as_secs())
+    }
+    
+    /// Remet à zéro les compteurs statistiques sans modifier l'état opérationnel
+    ///
+    /// Peut être appelé sans risque entre deux phases d'un benchmark (échauffement, mesure).
+    ///
+    /// Le nombre d'environnements actifs reflète l'état courant et est conservé. Les environnements
+    /// déjà attaqués compteront de nouveau à leur prochaine attaque.
+    pub fn reset_stats(&self) {
+        self.attacked_environments.lock().unwrap().clear();
+        let mut stats = self.stats.lock().unwrap();
+        *stats = WarpShieldStats {
+            active_environments: stats.active_environments,
+            resource_utilization: stats.resource_utilization,
+            ..Default::default()
+        };
     }
     
     /// Obtient la liste des environnements virtuels
@@ -493,15 +1201,34 @@ impl WarpShield {
         
         let mut state = self.state.lock().unwrap();
         *state = WarpShieldState::Shutdown;
+        *self.started_at.lock().unwrap() = None;
+        
+        module_log!(self.config.log_level, Level::Info, "WarpShield arrêté");
         
         Ok(())
     }
 }
 
+/// Vérifie qu'un identifiant respecte le format `CVE-\d{4}-\d+`
+fn is_valid_cve_id(id: &str) -> bool {
+    let Some(rest) = id.strip_prefix("CVE-") else {
+        return false;
+    };
+    let Some((year, number)) = rest.split_once('-') else {
+        return false;
+    };
+    
+    let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    year.len() == 4 && all_digits(year) && all_digits(number)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     
+    use crate::clock::MockClock;
+    use crate::id::SequentialIdGenerator;
+    
     #[test]
     fn test_warpshield_initialization() {
         let config = WarpShieldConfig::default();
@@ -573,6 +1300,38 @@ mod tests {
         assert_eq!(event.data.get("payload").unwrap(), "malicious_script.php");
     }
     
+    #[test]
+    fn test_attack_event_to_threat_event() {
+        let mut data = HashMap::new();
+        data.insert("payload".to_string(), "' OR 1=1 --".to_string());
+        let event = AttackEvent {
+            id: "attack-1".to_string(),
+            environment_id: "env-1".to_string(),
+            attack_type: "sql_injection".to_string(),
+            source: "203.0.113.7".to_string(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000),
+            data,
+            severity: 0.95,
+        };
+        
+        let ids = SequentialIdGenerator::new();
+        let threat = event.to_threat_event(&ids);
+        assert_eq!(threat.id, "threat-0");
+        assert_eq!(threat.threat_type, ThreatType::SqlInjection);
+        assert_eq!(threat.severity, ThreatSeverity::Critical);
+        assert_eq!(threat.source, "203.0.113.7");
+        assert_eq!(threat.target, "env-1");
+        assert_eq!(threat.timestamp, event.timestamp);
+        assert_eq!(threat.metadata.get("payload").unwrap(), "' OR 1=1 --");
+        assert_eq!(threat.metadata.get("attack_event").unwrap(), "attack-1");
+        
+        let unknown = AttackEvent { attack_type: "firmware_tampering".to_string(), severity: 0.3, ..event };
+        let threat = unknown.to_threat_event(&ids);
+        assert_eq!(threat.id, "threat-1");
+        assert_eq!(threat.threat_type, ThreatType::UnknownZeroDay);
+        assert_eq!(threat.severity, ThreatSeverity::Low);
+    }
+    
     #[test]
     fn test_generate_attack_signature() {
         let mut config = WarpShieldConfig::default();
@@ -601,4 +1360,569 @@ mod tests {
         assert!(signature.patterns.contains(&"source:192.168.1.100".to_string()));
         assert!(!signature.recommended_countermeasures.is_empty());
     }
+    
+    #[test]
+    fn test_log_level_controls_emitted_records() {
+        use crate::logging::capture;
+        
+        let run = |log_level: u8| {
+            capture::start();
+            let mut warpshield = WarpShield::new(WarpShieldConfig { log_level, ..Default::default() });
+            warpshield.initialize().unwrap();
+            let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+            warpshield.terminate_environment(&env.id).unwrap();
+            capture::take()
+        };
+        
+        assert!(run(0).is_empty());
+        
+        let records = run(4);
+        assert!(records.iter().any(|(level, _)| *level == log::Level::Info));
+        assert!(records.iter().any(|(level, _)| *level == log::Level::Debug));
+    }
+    
+    #[test]
+    fn test_reset_stats_keeps_state() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        warpshield.activate_environment(&env.id, "192.168.1.100").unwrap();
+        warpshield.record_attack_event(&env.id, "brute_force", HashMap::new()).unwrap();
+        
+        warpshield.reset_stats();
+        
+        let stats = warpshield.get_stats();
+        assert_eq!(stats.total_environments_created, 0);
+        assert_eq!(stats.total_attacks_detected, 0);
+        assert_eq!(stats.active_environments, 1);
+        assert_eq!(warpshield.get_state(), WarpShieldState::Operational);
+    }
+    
+    #[test]
+    fn test_uptime_tracking() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        assert_eq!(warpshield.get_stats().uptime_seconds, 0);
+        
+        warpshield.initialize().unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(warpshield.get_stats().uptime_seconds >= 1);
+        
+        warpshield.shutdown().unwrap();
+        assert_eq!(warpshield.get_stats().uptime_seconds, 0);
+    }
+    
+    #[test]
+    fn test_attack_detection_rate() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let attacked = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        let idle = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        warpshield.activate_environment(&attacked.id, "192.168.1.100").unwrap();
+        warpshield.activate_environment(&idle.id, "192.168.1.101").unwrap();
+        
+        // Plusieurs attaques dans le même environnement comptent une seule fois
+        warpshield.record_attack_event(&attacked.id, "sql_injection", HashMap::new()).unwrap();
+        warpshield.record_attack_event(&attacked.id, "xss", HashMap::new()).unwrap();
+        
+        let stats = warpshield.get_stats();
+        assert_eq!(stats.environments_activated, 2);
+        assert_eq!(stats.environments_attacked, 1);
+        assert_eq!(stats.attack_detection_rate, 0.5);
+    }
+    
+    #[test]
+    fn test_removed_environments_leave_attack_tracking() {
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.initialize().unwrap();
+        
+        let first = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&first.id, "192.168.1.100").unwrap();
+        warpshield.record_attack_event(&first.id, "sql_injection", HashMap::new()).unwrap();
+        warpshield.terminate_environment(&first.id).unwrap();
+        assert!(warpshield.attacked_environments.lock().unwrap().is_empty());
+        
+        let fresh = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&fresh.id, "192.168.1.101").unwrap();
+        warpshield.record_attack_event(&fresh.id, "xss", HashMap::new()).unwrap();
+        let stats = warpshield.get_stats();
+        assert_eq!(stats.environments_attacked, 2);
+        assert_eq!(stats.attack_detection_rate, 1.0);
+        
+        // Après remise à zéro, une nouvelle attaque du même environnement compte de nouveau
+        warpshield.reset_stats();
+        assert!(warpshield.attacked_environments.lock().unwrap().is_empty());
+        warpshield.record_attack_event(&fresh.id, "xss", HashMap::new()).unwrap();
+        assert_eq!(warpshield.get_stats().environments_attacked, 1);
+    }
+    
+    #[test]
+    fn test_attack_history_is_capped_and_released() {
+        let config = WarpShieldConfig { max_events_per_environment: 3, ..Default::default() };
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&env.id, "203.0.113.7").unwrap();
+        for attack_type in ["scan", "sql_injection", "xss", "web_shell_upload", "data_exfiltration"] {
+            warpshield.record_attack_event(&env.id, attack_type, HashMap::new()).unwrap();
+        }
+        
+        // Seuls les trois événements les plus récents sont conservés
+        let kept: Vec<String> = warpshield.attack_events.lock().unwrap()[&env.id]
+            .iter()
+            .map(|event| event.attack_type.clone())
+            .collect();
+        assert_eq!(kept, vec!["xss", "web_shell_upload", "data_exfiltration"]);
+        assert_eq!(warpshield.get_stats().total_attacks_detected, 5);
+        
+        // La réinitialisation de l'environnement libère son historique
+        warpshield.begin_analysis(&env.id).unwrap();
+        warpshield.finish_analysis(&env.id, true).unwrap();
+        assert!(warpshield.attack_events.lock().unwrap().is_empty());
+        assert_eq!(warpshield.correlate_attacker("203.0.113.7").total_events, 0);
+    }
+    
+    #[test]
+    fn test_analysis_cycle() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&env.id, "192.168.1.100").unwrap();
+        
+        warpshield.begin_analysis(&env.id).unwrap();
+        assert_eq!(warpshield.get_environments()[0].state, VirtualEnvironmentState::Analyzing);
+        assert!(warpshield.record_attack_event(&env.id, "sql_injection", HashMap::new()).is_err());
+        
+        warpshield.finish_analysis(&env.id, true).unwrap();
+        let reset_env = &warpshield.get_environments()[0];
+        assert_eq!(reset_env.state, VirtualEnvironmentState::Ready);
+        assert!(reset_env.attacker_data.is_empty());
+        
+        // L'environnement réinitialisé peut être réactivé puis retiré après analyse
+        warpshield.activate_environment(&env.id, "192.168.1.101").unwrap();
+        warpshield.begin_analysis(&env.id).unwrap();
+        warpshield.finish_analysis(&env.id, false).unwrap();
+        assert!(warpshield.get_environments().is_empty());
+        assert_eq!(warpshield.get_stats().active_environments, 0);
+    }
+    
+    #[test]
+    fn test_illegal_state_transition() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        
+        // Ready -> Analyzing est interdit
+        let error = warpshield.begin_analysis(&env.id).unwrap_err();
+        assert!(error.contains("Transition illégale"));
+        assert!(warpshield.finish_analysis(&env.id, true).is_err());
+        assert_eq!(warpshield.get_environments()[0].state, VirtualEnvironmentState::Ready);
+        
+        assert!(!VirtualEnvironmentState::Terminated.can_transition_to(&VirtualEnvironmentState::Ready));
+    }
+    
+    #[test]
+    fn test_seeded_virtual_ips() {
+        let config = WarpShieldConfig {
+            max_virtual_environments: 300,
+            rng_seed: Some(42),
+            ..Default::default()
+        };
+        
+        let mut first = WarpShield::new(config.clone());
+        first.initialize().unwrap();
+        let mut second = WarpShield::new(config);
+        second.initialize().unwrap();
+        
+        // Une même graine produit la même première adresse
+        let first_ip = first.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap().virtual_ip;
+        let second_ip = second.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap().virtual_ip;
+        assert_eq!(first_ip, second_ip);
+        
+        for _ in 1..300 {
+            first.create_virtual_environment(VirtualEnvironmentType::Custom("iot".to_string())).unwrap();
+        }
+        
+        let ips: HashSet<String> = first.get_environments().into_iter().map(|env| env.virtual_ip).collect();
+        assert_eq!(ips.len(), 300);
+    }
+    
+    #[test]
+    fn test_virtual_ip_allocation_fails_when_subnet_is_exhausted() {
+        let mut warpshield = WarpShield::new(WarpShieldConfig { rng_seed: Some(7), ..Default::default() });
+        warpshield.initialize().unwrap();
+        let template = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        
+        // Toutes les adresses sauf 10.0.255.254 sont attribuées : le parcours exhaustif la trouve
+        let mut environments: HashMap<String, VirtualEnvironment> = (0..WarpShield::VIRTUAL_SUBNET_SIZE - 1)
+            .map(|index| {
+                let id = format!("env-{}", index);
+                let env = VirtualEnvironment {
+                    id: id.clone(),
+                    virtual_ip: format!("10.0.{}.{}", index / 254, index % 254 + 1),
+                    ..template.clone()
+                };
+                (id, env)
+            })
+            .collect();
+        assert_eq!(warpshield.allocate_virtual_ip(&environments).unwrap(), "10.0.255.254");
+        
+        let last = VirtualEnvironment { id: "env-last".to_string(), virtual_ip: "10.0.255.254".to_string(), ..template };
+        environments.insert(last.id.clone(), last);
+        let error = warpshield.allocate_virtual_ip(&environments).unwrap_err();
+        assert!(error.contains("épuisé"), "{}", error);
+    }
+    
+    #[test]
+    fn test_attack_severity_follows_criticality() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let severity = |env_type: VirtualEnvironmentType| {
+            let env = warpshield.create_virtual_environment(env_type).unwrap();
+            warpshield.activate_environment(&env.id, "192.168.1.100").unwrap();
+            warpshield.record_attack_event(&env.id, "credential_dumping", HashMap::new()).unwrap().severity
+        };
+        
+        let domain_controller = severity(VirtualEnvironmentType::DomainController);
+        let workstation = severity(VirtualEnvironmentType::Workstation);
+        let custom = severity(VirtualEnvironmentType::Custom("lab".to_string()));
+        
+        assert!(domain_controller > workstation);
+        assert!(domain_controller <= 1.0);
+        assert!((custom - 0.7).abs() < 1e-6);
+    }
+    
+    #[test]
+    fn test_correlate_attacker_across_environments() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let web = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        let database = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        let other = warpshield.create_virtual_environment(VirtualEnvironmentType::Workstation).unwrap();
+        warpshield.activate_environment(&web.id, "203.0.113.7").unwrap();
+        warpshield.activate_environment(&database.id, "203.0.113.7").unwrap();
+        warpshield.activate_environment(&other.id, "198.51.100.1").unwrap();
+        
+        warpshield.record_attack_event(&web.id, "sql_injection", HashMap::new()).unwrap();
+        warpshield.record_attack_event(&web.id, "xss", HashMap::new()).unwrap();
+        warpshield.record_attack_event(&database.id, "sql_injection", HashMap::new()).unwrap();
+        warpshield.record_attack_event(&other.id, "brute_force", HashMap::new()).unwrap();
+        
+        let profile = warpshield.correlate_attacker("203.0.113.7");
+        let mut expected = vec![web.id.clone(), database.id.clone()];
+        expected.sort();
+        assert_eq!(profile.environments, expected);
+        assert_eq!(profile.total_events, 3);
+        assert_eq!(profile.attack_types, vec!["sql_injection".to_string(), "xss".to_string()]);
+        assert!(profile.first_seen.unwrap() <= profile.last_seen.unwrap());
+        
+        // L'historique d'un environnement terminé est libéré
+        warpshield.terminate_environment(&web.id).unwrap();
+        let profile = warpshield.correlate_attacker("203.0.113.7");
+        assert_eq!(profile.environments, vec![database.id.clone()]);
+        assert_eq!(profile.total_events, 1);
+        assert_eq!(profile.attack_types, vec!["sql_injection".to_string()]);
+        
+        let unknown = warpshield.correlate_attacker("192.0.2.1");
+        assert_eq!(unknown.total_events, 0);
+        assert!(unknown.environments.is_empty() && unknown.first_seen.is_none());
+    }
+    
+    #[test]
+    fn test_custom_vulnerability_profile() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let cves = vec!["CVE-2023-4966".to_string(), "CVE-2024-3400".to_string()];
+        warpshield.set_vulnerability_profile(VirtualEnvironmentType::WebServer, cves.clone()).unwrap();
+        
+        let web = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        assert_eq!(web.simulated_vulnerabilities, cves);
+        
+        // Les autres types conservent leurs vulnérabilités par défaut
+        let database = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        assert!(database.simulated_vulnerabilities.contains(&"CVE-2021-3506".to_string()));
+        
+        for invalid in ["CVE-21-1234", "cve-2021-1234", "CVE-2021-", "CVE-2021-12a4", "Log4Shell"] {
+            let result = warpshield.set_vulnerability_profile(VirtualEnvironmentType::Database, vec![invalid.to_string()]);
+            assert!(result.is_err(), "{} devrait être rejeté", invalid);
+        }
+    }
+    
+    #[test]
+    fn test_attack_events_accumulate_exchanged_bytes() {
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.initialize().unwrap();
+        
+        let web = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        let database = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        warpshield.activate_environment(&web.id, "203.0.113.7").unwrap();
+        warpshield.activate_environment(&database.id, "203.0.113.7").unwrap();
+        
+        let transfer = |received: &str, sent: &str| {
+            HashMap::from([
+                (AttackEvent::BYTES_RECEIVED.to_string(), received.to_string()),
+                (AttackEvent::BYTES_SENT.to_string(), sent.to_string()),
+            ])
+        };
+        warpshield.record_attack_event(&web.id, "sql_injection", transfer("512", "128")).unwrap();
+        warpshield.record_attack_event(&web.id, "data_exfiltration", transfer("64", "1048576")).unwrap();
+        warpshield.record_attack_event(&database.id, "brute_force", transfer("2048", "0")).unwrap();
+        // Un événement sans volume ne modifie pas les totaux
+        warpshield.record_attack_event(&database.id, "port_scan", HashMap::new()).unwrap();
+        
+        let environments: HashMap<String, VirtualEnvironment> =
+            warpshield.get_environments().into_iter().map(|env| (env.id.clone(), env)).collect();
+        assert_eq!(environments[&web.id].bytes_received, 576);
+        assert_eq!(environments[&web.id].bytes_sent, 1_048_704);
+        assert_eq!(environments[&database.id].bytes_received, 2048);
+        assert_eq!(environments[&database.id].bytes_sent, 0);
+        
+        let stats = warpshield.get_stats();
+        assert_eq!(stats.total_bytes_received, 2624);
+        assert_eq!(stats.total_bytes_sent, 1_048_704);
+        
+        // Un volume invalide est rejeté sans être comptabilisé
+        assert!(warpshield.record_attack_event(&web.id, "sql_injection", transfer("beaucoup", "0")).is_err());
+        assert_eq!(warpshield.get_stats().total_attacks_detected, 4);
+        
+        // La réinitialisation du leurre remet ses compteurs à zéro, pas les totaux globaux
+        warpshield.begin_analysis(&web.id).unwrap();
+        warpshield.finish_analysis(&web.id, true).unwrap();
+        let web = warpshield.get_environments().into_iter().find(|env| env.id == web.id).unwrap();
+        assert_eq!((web.bytes_received, web.bytes_sent), (0, 0));
+        assert_eq!(warpshield.get_stats().total_bytes_sent, 1_048_704);
+    }
+    
+    #[test]
+    fn test_custom_environment_keeps_its_name() {
+        let config = WarpShieldConfig::default();
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let scada = VirtualEnvironmentType::Custom("scada-hmi".to_string());
+        let cves = vec!["CVE-2015-5374".to_string()];
+        warpshield.set_vulnerability_profile(scada.clone(), cves.clone()).unwrap();
+        
+        let env = warpshield.create_virtual_environment(scada).unwrap();
+        assert_eq!(env.env_type.custom_name(), Some("scada-hmi"));
+        assert_eq!(env.simulated_vulnerabilities, cves);
+        
+        // Un autre nom ne reprend pas ce profil
+        let lab = warpshield.create_virtual_environment(VirtualEnvironmentType::Custom("lab".to_string())).unwrap();
+        assert!(lab.simulated_vulnerabilities.is_empty());
+        assert!(warpshield.create_virtual_environment(VirtualEnvironmentType::Custom(" ".to_string())).is_err());
+        
+        warpshield.activate_environment(&env.id, "203.0.113.7").unwrap();
+        let activated = warpshield.get_environments().into_iter().find(|candidate| candidate.id == env.id).unwrap();
+        assert_eq!(activated.attacker_data.get("environment_profile").unwrap(), "scada-hmi");
+        
+        warpshield.record_attack_event(&env.id, "modbus_write", HashMap::new()).unwrap();
+        let signature = warpshield.generate_attack_signature(&env.id, "SCADA", "Écriture Modbus").unwrap();
+        assert!(signature.patterns.contains(&"env_type:Custom(\"scada-hmi\")".to_string()));
+        assert!(signature.patterns.contains(&"env_profile:scada-hmi".to_string()));
+    }
+    
+    #[test]
+    fn test_expire_stale_environments_with_mock_clock() {
+        let clock = Arc::new(MockClock::new(SystemTime::now()));
+        let config = WarpShieldConfig { max_session_duration: 600, ..Default::default() };
+        let mut warpshield = WarpShield::new(config);
+        warpshield.set_clock(clock.clone());
+        warpshield.initialize().unwrap();
+        
+        let stale = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        let idle = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        warpshield.activate_environment(&stale.id, "192.168.1.100").unwrap();
+        
+        clock.advance(Duration::from_secs(300));
+        assert_eq!(warpshield.expire_stale_environments(), 0);
+        
+        clock.advance(Duration::from_secs(301));
+        assert_eq!(warpshield.expire_stale_environments(), 1);
+        
+        // Seule la session active expirée est retirée ; le leurre en attente reste disponible
+        let remaining: Vec<String> = warpshield.get_environments().into_iter().map(|env| env.id).collect();
+        assert_eq!(remaining, vec![idle.id]);
+        assert_eq!(warpshield.get_stats().active_environments, 1);
+        assert_eq!(warpshield.get_stats().uptime_seconds, 601);
+    }
+    
+    #[test]
+    fn test_sequential_id_generator_gives_reproducible_ids() {
+        let mut warpshield = WarpShield::new(WarpShieldConfig::default());
+        warpshield.set_id_generator(Arc::new(SequentialIdGenerator::new()));
+        warpshield.initialize().unwrap();
+        
+        let web = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        let database = warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        assert_eq!(web.id, "env-0");
+        assert_eq!(database.id, "env-1");
+        
+        // Les attaques et signatures ont leur propre numérotation
+        warpshield.activate_environment(&web.id, "192.168.1.100").unwrap();
+        let attack = warpshield.record_attack_event(&web.id, "sql_injection", HashMap::new()).unwrap();
+        assert_eq!(attack.id, "attack-0");
+        let signature = warpshield.generate_attack_signature(&web.id, "SQLi", "Injection SQL").unwrap();
+        assert_eq!(signature.id, "sig-0");
+        
+        let environment = warpshield.create_virtual_environment(VirtualEnvironmentType::FileServer).unwrap();
+        assert_eq!(environment.id, "env-2");
+    }
+    
+    #[test]
+    fn test_attack_event_bincode_round_trip() {
+        let mut data = HashMap::new();
+        data.insert("payload".to_string(), "malicious_script.php".to_string());
+        let event = AttackEvent {
+            id: "attack-1".to_string(),
+            environment_id: "env-1".to_string(),
+            attack_type: "web_shell_upload".to_string(),
+            source: "203.0.113.7".to_string(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000),
+            data,
+            severity: 0.7,
+        };
+        
+        let decoded = AttackEvent::from_bincode(&event.to_bincode().unwrap()).unwrap();
+        assert_eq!(decoded.id, event.id);
+        assert_eq!(decoded.environment_id, event.environment_id);
+        assert_eq!(decoded.attack_type, event.attack_type);
+        assert_eq!(decoded.source, event.source);
+        assert_eq!(decoded.timestamp, event.timestamp);
+        assert_eq!(decoded.data, event.data);
+        assert_eq!(decoded.severity, event.severity);
+    }
+    
+    #[test]
+    fn test_create_environments_from_template() {
+        let config = WarpShieldConfig {
+            max_virtual_environments: 7,
+            max_resource_allocation: 1.0,
+            rng_seed: Some(7),
+            ..Default::default()
+        };
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        
+        let template = EnvironmentTemplate {
+            exposed_services: vec!["http".to_string(), "https".to_string()],
+            ..EnvironmentTemplate::for_type(VirtualEnvironmentType::WebServer)
+        };
+        let decoys = warpshield.create_environments_from_template(template.clone(), 5).unwrap();
+        assert_eq!(decoys.len(), 5);
+        assert!(decoys.iter().all(|env| env.env_type == VirtualEnvironmentType::WebServer));
+        assert!(decoys.iter().all(|env| env.exposed_services == template.exposed_services));
+        assert!(decoys.iter().all(|env| env.simulated_vulnerabilities == template.simulated_vulnerabilities));
+        
+        let ips: HashSet<&str> = decoys.iter().map(|env| env.virtual_ip.as_str()).collect();
+        assert_eq!(ips.len(), 5);
+        
+        // La capacité est atteinte en cours de lot : les environnements créés sont conservés
+        let error = warpshield.create_environments_from_template(template.clone(), 5).unwrap_err();
+        assert!(error.contains("2 environnements créés sur 5"), "{}", error);
+        assert_eq!(warpshield.get_environments().len(), 7);
+        assert_eq!(warpshield.get_stats().total_environments_created, 7);
+        
+        let greedy = EnvironmentTemplate { resource_allocation: 1.5, ..template };
+        assert!(warpshield.create_environments_from_template(greedy, 1).is_err());
+    }
+    
+    #[test]
+    fn test_template_batches_respect_resource_budget() {
+        let config = WarpShieldConfig { max_resource_allocation: 0.3, ..Default::default() };
+        let mut warpshield = WarpShield::new(config);
+        warpshield.initialize().unwrap();
+        let template = EnvironmentTemplate {
+            resource_allocation: 0.05,
+            ..EnvironmentTemplate::for_type(VirtualEnvironmentType::WebServer)
+        };
+        
+        // Sept environnements à 0.05 dépassent un budget de 0.3
+        let error = warpshield.create_environments_from_template(template.clone(), 7).unwrap_err();
+        assert!(error.contains("Budget de ressources"), "{}", error);
+        assert!(warpshield.get_environments().is_empty());
+        
+        // Les environnements existants sont déduits du budget
+        warpshield.create_virtual_environment(VirtualEnvironmentType::Database).unwrap();
+        assert_eq!(warpshield.create_environments_from_template(template.clone(), 5).unwrap().len(), 5);
+        assert!(warpshield.create_environments_from_template(template, 1).is_err());
+        assert_eq!(warpshield.get_environments().len(), 6);
+    }
+    
+    #[test]
+    fn test_export_import_signatures() {
+        let config = WarpShieldConfig { enable_signature_generation: true, ..Default::default() };
+        let mut warpshield = WarpShield::new(config.clone());
+        warpshield.initialize().unwrap();
+        
+        let env = warpshield.create_virtual_environment(VirtualEnvironmentType::WebServer).unwrap();
+        warpshield.activate_environment(&env.id, "192.168.1.100").unwrap();
+        let signature = warpshield.generate_attack_signature(&env.id, "Scan web", "Scan de serveur web").unwrap();
+        let exported = warpshield.export_signatures();
+        
+        let fresh = WarpShield::new(config);
+        assert_eq!(fresh.import_signatures(&exported).unwrap(), 1);
+        let imported = fresh.get_signature(&signature.id).unwrap();
+        assert_eq!(imported.name, "Scan web");
+        assert_eq!(imported.patterns, signature.patterns);
+        
+        // Un ensemble de motifs déjà connu n'est pas réimporté, même réordonné
+        assert_eq!(fresh.import_signatures(&exported).unwrap(), 0);
+        let mut reordered = signature.clone();
+        reordered.id = "sig-reordered".to_string();
+        reordered.patterns.reverse();
+        let json = serde_json::to_string(&vec![reordered]).unwrap();
+        assert_eq!(fresh.import_signatures(&json).unwrap(), 0);
+        assert_eq!(fresh.get_signatures().len(), 1);
+        
+        let mut invalid = signature;
+        invalid.patterns = vec!["payload:autre".to_string()];
+        invalid.confidence = 1.5;
+        let json = serde_json::to_string(&vec![invalid]).unwrap();
+        assert!(fresh.import_signatures(&json).is_err());
+        assert!(fresh.import_signatures("pas du json").is_err());
+        assert_eq!(fresh.get_signatures().len(), 1);
+    }
+    
+    #[test]
+    fn test_signature_store_dedups_ids_and_evicts_oldest() {
+        let warpshield = WarpShield::new(WarpShieldConfig { max_signatures: 2, ..Default::default() });
+        let signature = |id: &str, pattern: &str| AttackSignature {
+            id: id.to_string(),
+            name: format!("Signature {}", id),
+            description: String::new(),
+            patterns: vec![pattern.to_string()],
+            confidence: 0.9,
+            created_at: SystemTime::UNIX_EPOCH,
+            related_attack_events: Vec::new(),
+            recommended_countermeasures: Vec::new(),
+        };
+        let import = |signatures: Vec<AttackSignature>| warpshield.import_signatures(&serde_json::to_string(&signatures).unwrap()).unwrap();
+        
+        // Un identifiant déjà connu n'est pas réimporté, même avec d'autres motifs
+        assert_eq!(import(vec![signature("sig-a", "payload:a")]), 1);
+        assert_eq!(import(vec![signature("sig-a", "payload:autre")]), 0);
+        assert_eq!(warpshield.get_signature("sig-a").unwrap().patterns, vec!["payload:a".to_string()]);
+        
+        // Au-delà de la capacité, la plus ancienne signature est oubliée
+        assert_eq!(import(vec![signature("sig-b", "payload:b"), signature("sig-c", "payload:c")]), 2);
+        let ids: Vec<String> = warpshield.get_signatures().into_iter().map(|signature| signature.id).collect();
+        assert_eq!(ids, vec!["sig-b".to_string(), "sig-c".to_string()]);
+        assert!(warpshield.get_signature("sig-a").is_none());
+    }
 }
@@ -26,78 +26,25 @@ mod warpshield;
 #[path = "../../src/core/neurofirewall/mod.rs"]
 mod neurofirewall;
 
-/// Structure pour stocker les résultats de benchmark
-#[derive(Debug, Clone)]
-struct BenchmarkResult {
-    /// Nom du test
-    name: String,
-    /// Durée moyenne d'exécution (en microsecondes)
-    avg_duration_us: f64,
-    /// Durée minimale d'exécution (en microsecondes)
-    min_duration_us: f64,
-    /// Durée maximale d'exécution (en microsecondes)
-    max_duration_us: f64,
-    /// Nombre d'itérations
-    iterations: usize,
-    /// Utilisation mémoire (en Ko)
-    memory_usage_kb: f64,
-    /// Métadonnées supplémentaires
-    metadata: HashMap<String, String>,
-}
+#[path = "../../src/core/bench/mod.rs"]
+mod bench;
+
+use bench::{Benchmark, BenchmarkResult};
 
 /// Fonction utilitaire pour exécuter un benchmark
 fn run_benchmark<F>(name: &str, iterations: usize, f: F) -> BenchmarkResult 
 where
     F: Fn() -> ()
 {
-    let mut durations = Vec::with_capacity(iterations);
-    
-    // Échauffement
-    for _ in 0..5 {
-        f();
-    }
-    
-    // Benchmark réel
-    for _ in 0..iterations {
-        let start = Instant::now();
-        f();
-        let duration = start.elapsed();
-        durations.push(duration.as_micros() as f64);
-    }
-    
-    // Calcul des statistiques
-    let total_duration: f64 = durations.iter().sum();
-    let avg_duration = total_duration / iterations as f64;
-    let min_duration = *durations.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-    let max_duration = *durations.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap();
-    
-    // Estimation simplifiée de l'utilisation mémoire (à remplacer par une mesure réelle)
-    let memory_usage = 1024.0; // 1 Mo par défaut
-    
-    BenchmarkResult {
-        name: name.to_string(),
-        avg_duration_us: avg_duration,
-        min_duration_us: min_duration,
-        max_duration_us: max_duration,
-        iterations,
-        memory_usage_kb: memory_usage,
-        metadata: HashMap::new(),
-    }
+    Benchmark::new(name)
+        .iterations(iterations)
+        .run(f)
+        .expect("un benchmark mesure au moins une itération")
 }
 
 /// Affiche les résultats d'un benchmark
 fn print_benchmark_result(result: &BenchmarkResult) {
-    println!("=== Benchmark: {} ===", result.name);
-    println!("Itérations: {}", result.iterations);
-    println!("Durée moyenne: {:.2} µs", result.avg_duration_us);
-    println!("Durée min: {:.2} µs", result.min_duration_us);
-    println!("Durée max: {:.2} µs", result.max_duration_us);
-    println!("Utilisation mémoire: {:.2} Ko", result.memory_usage_kb);
-    println!("Métadonnées:");
-    for (key, value) in &result.metadata {
-        println!("  {}: {}", key, value);
-    }
-    println!();
+    println!("{}", result);
 }
 
 /// Test de performance du NeuralNet Engine
@@ -483,6 +430,71 @@ fn stress_test_neurofirewall() {
     assert!(packets_per_second > 500.0, "Le taux d'analyse est inférieur à 500 paquets par seconde");
 }
 
+/// Test de charge concurrent du NeuroFireWall
+///
+/// Mesure le débit d'analyse lorsque plusieurs threads partagent la même instance ;
+/// les compteurs statistiques atomiques évitent de sérialiser les analyses sur un verrou.
+#[test]
+fn stress_test_neurofirewall_concurrent() {
+    // Initialisation du module
+    let config = neurofirewall::NeuroFireWallConfig {
+        packets_per_second: 1_000_000,
+        ..Default::default()
+    };
+    let mut neurofirewall = neurofirewall::NeuroFireWall::new(config);
+    neurofirewall.initialize().expect("Échec de l'initialisation de NeuroFireWall");
+    let neurofirewall = Arc::new(neurofirewall);
+    
+    // Nombre de paquets à analyser et de threads
+    let num_packets = 10000;
+    let num_threads = 8;
+    
+    // Exécution du test de charge
+    let start_time = Instant::now();
+    
+    let workers: Vec<_> = (0..num_threads)
+        .map(|worker| {
+            let neurofirewall = Arc::clone(&neurofirewall);
+            std::thread::spawn(move || {
+                for i in 0..num_packets / num_threads {
+                    let packet = neurofirewall::NetworkPacket {
+                        id: format!("packet-concurrent-{}-{}", worker, i),
+                        source_ip: format!("192.168.{}.{}", worker, i % 255),
+                        destination_ip: "192.168.1.1".to_string(),
+                        source_port: 10000 + (i % 55535) as u16,
+                        destination_port: 80,
+                        protocol: "TCP".to_string(),
+                        size: 1024,
+                        timestamp: SystemTime::now(),
+                        traffic_type: neurofirewall::TrafficType::Web,
+                        payload_sample: vec![0, 1, 2, 3, 4],
+                        metadata: HashMap::new(),
+                    };
+                    let _ = neurofirewall.analyze_packet(packet);
+                }
+            })
+        })
+        .collect();
+    
+    for worker in workers {
+        worker.join().expect("Échec d'un thread d'analyse");
+    }
+    
+    let total_duration = start_time.elapsed();
+    let packets_per_second = num_packets as f64 / total_duration.as_secs_f64();
+    
+    println!("=== Test de charge concurrent NeuroFireWall ===");
+    println!("Threads: {}", num_threads);
+    println!("Paquets analysés: {}", num_packets);
+    println!("Durée totale: {:.2} s", total_duration.as_secs_f64());
+    println!("Paquets par seconde: {:.2}", packets_per_second);
+    println!();
+    
+    // Les compteurs doivent rester exacts sous contention
+    assert_eq!(neurofirewall.get_stats().total_packets_analyzed, num_packets as u64);
+    assert!(packets_per_second > 500.0, "Le taux d'analyse est inférieur à 500 paquets par seconde");
+}
+
 /// Test d'optimisation mémoire
 #[test]
 fn memory_optimization_test() {
@@ -201,7 +201,7 @@ fn resistance_fuzzing_neurofirewall() {
             source_port: 1234,
             destination_port: 80,
             protocol: "TCP".to_string(),
-            size: fuzz_data.len() as u64,
+            size: fuzz_data.len() as u32,
             timestamp: SystemTime::now(),
             traffic_type: neurofirewall::TrafficType::Unknown,
             payload_sample: fuzz_data,